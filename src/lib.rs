@@ -9,26 +9,26 @@ pub mod xhook;
 pub mod xserver;
 pub mod xutil;
 
-#[cfg(feature = "log")]
-pub mod xlog;
-#[cfg(feature = "trace")]
-pub mod xtrace;
-#[cfg(feature = "http")]
-pub mod xhttp;
-#[cfg(feature = "orm")]
-pub mod xorm;
-#[cfg(feature = "cache")]
-pub mod xcache;
 #[cfg(feature = "axum-server")]
 pub mod xaxum;
-#[cfg(feature = "redis-store")]
-pub mod xredis;
-#[cfg(feature = "metric")]
-pub mod xmetric;
+#[cfg(feature = "cache")]
+pub mod xcache;
 #[cfg(feature = "flow")]
 pub mod xflow;
+#[cfg(feature = "http")]
+pub mod xhttp;
+#[cfg(feature = "log")]
+pub mod xlog;
+#[cfg(feature = "metric")]
+pub mod xmetric;
+#[cfg(feature = "orm")]
+pub mod xorm;
 #[cfg(feature = "pipeline")]
 pub mod xpipeline;
+#[cfg(feature = "redis-store")]
+pub mod xredis;
+#[cfg(feature = "trace")]
+pub mod xtrace;
 
 pub use error::{Result, XOneError};
 #[cfg(feature = "axum-server")]
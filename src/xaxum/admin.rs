@@ -0,0 +1,120 @@
+//! 管理接口路由
+//!
+//! 通过 [`XAxum::enable_log_level_route`](super::XAxum::enable_log_level_route) 开启，
+//! 默认不注册。
+//!
+//! 修改级别需要 `Authorization: Bearer {token}`，token 由
+//! [`sign_token`](crate::xlog::request_debug::sign_token) 使用 `XLog.RequestDebug.Secret` 签名，
+//! 校验规则与按请求 DEBUG 的请求头相同；未启用 `XLog.RequestDebug` 时拒绝修改。
+//!
+//! ```text
+//! GET /admin/log/level
+//! -> {"level":"info"}
+//!
+//! PUT /admin/log/level
+//! Authorization: Bearer 1767225600.xxxx
+//! {"level":"info,sqlx=debug"}                 # 持久修改
+//! {"level":"debug","ttl":"10m"}               # 临时修改，10 分钟后恢复
+//! -> {"level":"debug"}
+//! ```
+
+use crate::xlog;
+use crate::xlog::request_debug;
+use crate::xutil;
+use axum::Json;
+use axum::Router;
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use serde::{Deserialize, Serialize};
+
+/// 日志级别接口路径
+pub const LOG_LEVEL_ROUTE_PATH: &str = "/admin/log/level";
+
+/// 修改日志级别请求
+#[derive(Debug, Clone, Deserialize)]
+pub struct SetLogLevelRequest {
+    /// 过滤指令（语法同 `RUST_LOG`）
+    pub level: String,
+    /// 临时生效时长（duration 字符串，如 "10m"），为空时持久生效
+    #[serde(default)]
+    pub ttl: Option<String>,
+}
+
+/// 日志级别响应
+#[derive(Debug, Clone, Serialize)]
+struct LogLevelResponse {
+    level: Option<String>,
+}
+
+/// 错误响应
+#[derive(Debug, Clone, Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+/// 日志级别查询与修改路由
+pub fn log_level_router() -> Router {
+    Router::new().route(LOG_LEVEL_ROUTE_PATH, get(get_log_level).put(put_log_level))
+}
+
+// ---- 以下为私有实现 ----
+
+async fn get_log_level() -> Json<LogLevelResponse> {
+    Json(LogLevelResponse {
+        level: xlog::get_level(),
+    })
+}
+
+async fn put_log_level(headers: HeaderMap, Json(req): Json<SetLogLevelRequest>) -> Response {
+    if let Some(rejection) = check_authorization(&headers) {
+        return rejection;
+    }
+
+    let result = match req.ttl.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        Some(ttl) => match xutil::to_duration(ttl) {
+            Some(ttl) => xlog::set_level_for(&req.level, ttl),
+            None => return bad_request(format!("invalid ttl [{ttl}], expect format like 30s/10m")),
+        },
+        None => xlog::set_level(&req.level),
+    };
+
+    match result {
+        Ok(()) => get_log_level().await.into_response(),
+        Err(e) => bad_request(e.to_string()),
+    }
+}
+
+/// 校验 `Authorization: Bearer {token}` 的 HMAC 签名与有效期，未通过时返回拒绝响应
+fn check_authorization(headers: &HeaderMap) -> Option<Response> {
+    let Some(policy) = request_debug::policy() else {
+        return Some(error_response(
+            StatusCode::FORBIDDEN,
+            "change log level requires XLog.RequestDebug to be enabled".to_string(),
+        ));
+    };
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    match token {
+        Some(token) if policy.verify(token) => None,
+        _ => {
+            xutil::warn_if_enable_debug(
+                "XAxum change log level rejected, authorization invalid or expired",
+            );
+            Some(error_response(
+                StatusCode::UNAUTHORIZED,
+                "authorization token missing, invalid or expired".to_string(),
+            ))
+        }
+    }
+}
+
+fn bad_request(error: String) -> Response {
+    error_response(StatusCode::BAD_REQUEST, error)
+}
+
+fn error_response(status: StatusCode, error: String) -> Response {
+    (status, Json(ErrorResponse { error })).into_response()
+}
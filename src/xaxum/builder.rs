@@ -28,6 +28,8 @@ pub struct XAxum {
     use_http2: Option<bool>,
     enable_log_middleware: bool,
     enable_trace_middleware: bool,
+    #[cfg(feature = "log")]
+    enable_log_level_route: bool,
     route_registers: Vec<Box<dyn FnOnce(Router) -> Router + Send>>,
     middlewares: Vec<Box<dyn FnOnce(Router) -> Router + Send>>,
     router: Option<Router>,
@@ -44,6 +46,8 @@ impl XAxum {
             use_http2: None,
             enable_log_middleware: true,
             enable_trace_middleware: true,
+            #[cfg(feature = "log")]
+            enable_log_level_route: false,
             route_registers: Vec::new(),
             middlewares: Vec::new(),
             router: None,
@@ -60,6 +64,8 @@ impl XAxum {
             use_http2: None,
             enable_log_middleware: true,
             enable_trace_middleware: true,
+            #[cfg(feature = "log")]
+            enable_log_level_route: false,
             route_registers: Vec::new(),
             middlewares: Vec::new(),
             router: Some(router),
@@ -108,6 +114,16 @@ impl XAxum {
         self
     }
 
+    /// 设置是否注册日志级别管理接口（默认 false）
+    ///
+    /// 开启后注册 `GET/PUT /admin/log/level`，详见 [`admin`](super::admin)。
+    /// 修改级别需携带 `XLog.RequestDebug.Secret` 签名的 token，未启用 `XLog.RequestDebug` 时只能查询。
+    #[cfg(feature = "log")]
+    pub fn enable_log_level_route(mut self, enable: bool) -> Self {
+        self.enable_log_level_route = enable;
+        self
+    }
+
     /// 注册路由回调（可多次调用）
     ///
     /// 回调按注册顺序依次执行。
@@ -141,7 +157,7 @@ impl XAxum {
     /// 配置项（addr、banner、http2）延迟到 `run()` 阶段解析，
     /// 确保 `run_server()` 中的 `init()` 先加载配置。
     ///
-    /// 请求处理顺序：trace → log → 用户中间件 → handler
+    /// 请求处理顺序：trace → request_debug → log → 用户中间件 → handler
     pub fn build(self) -> XAxumServer {
         // 1. 创建或使用已有 Router
         let mut router = self.router.unwrap_or_default();
@@ -150,6 +166,10 @@ impl XAxum {
         for register in self.route_registers {
             router = register(router);
         }
+        #[cfg(feature = "log")]
+        if self.enable_log_level_route {
+            router = router.merge(super::admin::log_level_router());
+        }
 
         // 3. 注册用户自定义中间件
         for mw in self.middlewares {
@@ -158,7 +178,7 @@ impl XAxum {
 
         // 4. 注册内置中间件（外层，优先级高于用户中间件）
         //    log 先注册（内层），trace 后注册（外层）
-        //    请求顺序：trace → log → 用户中间件 → handler
+        //    请求顺序：trace → request_debug → log → 用户中间件 → handler
         //    trace 先建立上下文，log 才能打印 trace_id
         if self.enable_log_middleware {
             router = router.layer(axum::middleware::from_fn::<_, (axum::extract::Request,)>(
@@ -166,6 +186,14 @@ impl XAxum {
             ));
         }
 
+        // 按请求开启 DEBUG 日志（XLog.RequestDebug 未启用时直接透传）
+        #[cfg(feature = "log")]
+        {
+            router = router.layer(axum::middleware::from_fn::<_, (axum::extract::Request,)>(
+                middleware::request_debug_middleware,
+            ));
+        }
+
         if self.enable_trace_middleware {
             router = router.layer(axum::middleware::from_fn::<_, (axum::extract::Request,)>(
                 middleware::trace_middleware,
@@ -2,6 +2,7 @@
 //!
 //! 记录请求/响应的关键信息（method、path、status、headers、body、耗时），
 //! 敏感 header 自动脱敏，二进制/大体积 body 自动跳过。
+//! 启用 `log` 特性时，header 与 body 同时按 `XLog.Redact` 脱敏策略处理。
 
 use axum::body::Body;
 use axum::extract::Request;
@@ -42,12 +43,24 @@ const BINARY_CT_EXACT: &[&str] = &[
 
 /// 判断是否为敏感 header（大小写不敏感）
 ///
-/// 使用 `eq_ignore_ascii_case` 避免分配临时 String。
+/// 使用 `eq_ignore_ascii_case` 避免分配临时 String；
+/// 启用 `log` 特性时，匹配 `XLog.Redact.Keys` 的 header 同样视为敏感。
 #[doc(hidden)]
 pub fn is_sensitive_header(name: &str) -> bool {
-    SENSITIVE_HEADERS
+    if SENSITIVE_HEADERS
         .iter()
         .any(|s| name.eq_ignore_ascii_case(s))
+    {
+        return true;
+    }
+    #[cfg(feature = "log")]
+    {
+        crate::xlog::redact::policy().is_sensitive_key(name)
+    }
+    #[cfg(not(feature = "log"))]
+    {
+        false
+    }
 }
 
 /// 将 header 转为 JSON 字符串，敏感值脱敏为 `***`
@@ -211,12 +224,18 @@ fn content_length(headers: &HeaderMap) -> Option<usize> {
 
 /// 将已确认为文本的 body 字节转为可显示字符串（内部使用）
 ///
+/// 先按脱敏策略处理（JSON body 需完整解析），再截断；
 /// 截断时按 char boundary 对齐，避免多字节 UTF-8 字符被截断导致 panic。
 fn body_display_string(bytes: &[u8]) -> String {
     if bytes.is_empty() {
         return String::new();
     }
     let text = String::from_utf8_lossy(bytes);
+    #[cfg(feature = "log")]
+    let text = match crate::xlog::redact::policy().redact_body(&text) {
+        std::borrow::Cow::Owned(redacted) => std::borrow::Cow::Owned(redacted),
+        std::borrow::Cow::Borrowed(_) => text,
+    };
     if text.len() > MAX_BODY_DISPLAY {
         // 从 MAX_BODY_DISPLAY 向前找到最近的 char boundary
         let mut end = MAX_BODY_DISPLAY;
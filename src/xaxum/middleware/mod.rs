@@ -1,15 +1,19 @@
 //! HTTP 中间件集合
 //!
-//! 包含日志、链路追踪、panic 恢复、session 和指标中间件。
+//! 包含日志、链路追踪、panic 恢复、session、指标和按请求开启 DEBUG 日志中间件。
 
 pub mod log;
 pub mod metric;
 pub mod recover;
+#[cfg(feature = "log")]
+pub mod request_debug;
 pub mod session;
 pub mod trace;
 
 pub use log::log_middleware;
 pub use metric::metric_middleware;
 pub use recover::recover_middleware;
+#[cfg(feature = "log")]
+pub use request_debug::request_debug_middleware;
 pub use session::{SessionContext, session_middleware};
 pub use trace::trace_middleware;
@@ -0,0 +1,46 @@
+//! 按请求开启 DEBUG 日志中间件
+//!
+//! 请求携带签名有效的 `XLog.RequestDebug.Header`（默认 `X-Debug-Log`）时，
+//! 在带 [`REQUEST_DEBUG_FIELD`] 字段的 span 内处理该请求，
+//! 该请求的 DEBUG 日志不受 `XLog.Level` 限制，xhttp 出站请求自动携带同一 token。
+
+use crate::xlog::request_debug::{self, REQUEST_DEBUG_FIELD};
+use crate::xutil;
+use axum::{extract::Request, middleware::Next, response::Response};
+use tracing::Instrument;
+
+/// 按请求开启 DEBUG 日志中间件
+///
+/// `XLog.RequestDebug` 未启用、请求未携带请求头或签名无效时直接透传请求。
+///
+/// # Examples
+///
+/// ```ignore
+/// use axum::{Router, middleware};
+/// use x_one::xaxum::middleware::request_debug_middleware;
+///
+/// let app = Router::new()
+///     .layer(middleware::from_fn(request_debug_middleware));
+/// ```
+pub async fn request_debug_middleware(req: Request, next: Next) -> Response {
+    let Some(policy) = request_debug::policy() else {
+        return next.run(req).await;
+    };
+    let Some(value) = req.headers().get(policy.header()) else {
+        return next.run(req).await;
+    };
+    let token = match value.to_str() {
+        Ok(token) if policy.verify(token) => token.to_string(),
+        _ => {
+            xutil::warn_if_enable_debug(&format!(
+                "XAxum request debug header [{}] invalid or expired, path=[{}]",
+                policy.header(),
+                req.uri().path()
+            ));
+            return next.run(req).await;
+        }
+    };
+
+    let span = tracing::info_span!("request_debug", { REQUEST_DEBUG_FIELD } = token.as_str());
+    next.run(req).instrument(span).await
+}
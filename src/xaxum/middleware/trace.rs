@@ -1,7 +1,8 @@
 //! Trace 中间件
 //!
 //! 为 axum 请求自动注入 OpenTelemetry trace 上下文，
-//! 支持从入站 HTTP header 提取 W3C traceparent。
+//! 支持从入站 HTTP header 提取 W3C traceparent，
+//! 并在创建 span 前按 `XTrace.Sampler.Rules` 决定该请求的采样。
 
 use axum::{extract::Request, middleware::Next, response::Response};
 use opentelemetry::{
@@ -9,6 +10,7 @@ use opentelemetry::{
     propagation::Extractor,
     trace::{SpanKind, Status, TraceContextExt, Tracer},
 };
+use opentelemetry_sdk::trace::{IdGenerator, RandomIdGenerator};
 use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context as TaskContext, Poll};
@@ -50,6 +52,9 @@ impl<F: Future> Future for OtelContextFuture<F> {
 /// 将 trace 上下文传递给下游 handler。handler 中通过
 /// `opentelemetry::Context::current()` 可获取当前 trace_id。
 ///
+/// 命中 `XTrace.Sampler.Rules` 中的规则时按规则比例采样，
+/// 命中 `Ratio: 0` 的规则（如 `/healthz`）时不创建 span。
+///
 /// 当 xtrace 未启用时直接透传请求，不产生额外开销。
 pub async fn trace_middleware(req: Request, next: Next) -> Response {
     if !crate::xtrace::is_trace_enabled() {
@@ -61,22 +66,43 @@ pub async fn trace_middleware(req: Request, next: Next) -> Response {
         propagator.extract(&HeaderExtractor(req.headers()))
     });
 
-    let tracer = global::tracer("x-one-http-server");
     // clone 替代 to_string：Method 是小枚举（栈拷贝），Uri 是引用计数（原子 +1）
     let method = req.method().clone();
     let uri = req.uri().clone();
 
-    let span = tracer
-        .span_builder(format!("{} {}", method.as_str(), uri.path()))
+    let policy = crate::xtrace::sampler::policy();
+    let rule = policy
+        .as_deref()
+        .and_then(|p| p.match_rule(method.as_str(), uri.path()));
+    if rule.is_some_and(|r| r.is_drop()) {
+        return next.run(req).await;
+    }
+
+    let tracer = global::tracer("x-one-http-server");
+    let name = format!("{} {}", method.as_str(), uri.path());
+    let mut builder = tracer
+        .span_builder(name)
         .with_kind(SpanKind::Server)
         // 数组替代 vec!：避免每请求的 Vec 堆分配
         .with_attributes([
             KeyValue::new("http.method", method.as_str().to_owned()),
             KeyValue::new("http.target", uri.path().to_owned()),
-        ])
-        .start_with_context(&tracer, &parent_cx);
+        ]);
+    if let Some(rule) = rule {
+        // 按规则预先采样：有父 span 时沿用父级 trace_id，否则在此生成，保证比例采样与 span 的 trace_id 一致
+        let parent_sc = parent_cx.span().span_context().clone();
+        let trace_id = if parent_sc.is_valid() {
+            parent_sc.trace_id()
+        } else {
+            RandomIdGenerator::default().new_trace_id()
+        };
+        let result = rule.should_sample(&parent_cx, trace_id, &builder.name, &SpanKind::Server);
+        builder = builder.with_trace_id(trace_id).with_sampling_result(result);
+    }
+    let span = builder.start_with_context(&tracer, &parent_cx);
 
-    let cx = Context::current_with_span(span);
+    // 基于提取的上下文创建，保留入站 baggage 供下游读取与出站传播
+    let cx = parent_cx.with_span(span);
 
     // 使用 OtelContextFuture 确保每次 poll 时 context 都正确绑定到当前线程
     let response = OtelContextFuture {
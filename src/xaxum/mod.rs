@@ -12,6 +12,8 @@
 //! x_one::run_server(&server).await?;
 //! ```
 
+#[cfg(feature = "log")]
+pub mod admin;
 pub mod banner;
 pub mod builder;
 pub mod config;
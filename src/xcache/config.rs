@@ -18,7 +18,8 @@
 //!     Name: "user"
 //! ```
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// XCache 配置 key
 pub const XCACHE_CONFIG_KEY: &str = "XCache";
@@ -32,7 +33,7 @@ pub const XCACHE_CONFIG_KEY: &str = "XCache";
 ///   DefaultTTL: "5m"
 ///   Name: ""
 /// ```
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(default)]
 pub struct XCacheConfig {
     /// 最大缓存容量（对应 moka 的 max_capacity，默认 100_000）
@@ -40,16 +41,16 @@ pub struct XCacheConfig {
     pub max_capacity: u64,
 
     /// 默认 TTL（duration 字符串，默认 "5m"）
-    #[serde(rename = "DefaultTTL")]
-    pub default_ttl: String,
+    #[serde(rename = "DefaultTTL", with = "crate::xutil::serde_duration")]
+    pub default_ttl: Duration,
 
     /// 实例名称（多实例模式标识，默认空）
     #[serde(rename = "Name")]
     pub name: String,
 }
 
-/// 加载 XCache 配置（支持单实例和多实例模式）
-pub(crate) fn load_configs() -> Vec<XCacheConfig> {
+/// 加载 XCache 配置（支持单实例和多实例模式），配置值非法时返回错误
+pub(crate) fn load_configs() -> Result<Vec<XCacheConfig>, crate::error::XOneError> {
     crate::xconfig::parse_config_list::<XCacheConfig>(XCACHE_CONFIG_KEY)
 }
 
@@ -57,7 +58,7 @@ impl Default for XCacheConfig {
     fn default() -> Self {
         Self {
             max_capacity: 100_000,
-            default_ttl: "5m".to_string(),
+            default_ttl: Duration::from_secs(300),
             name: String::new(),
         }
     }
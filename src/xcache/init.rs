@@ -9,7 +9,7 @@ use super::config::XCacheConfig;
 
 /// 初始化 xcache
 pub fn init_xcache() -> Result<(), crate::error::XOneError> {
-    let configs = super::config::load_configs();
+    let configs = super::config::load_configs()?;
 
     if configs.is_empty() {
         xutil::info_if_enable_debug("XCache no config found, using default");
@@ -46,13 +46,7 @@ pub fn create_cache_instance(config: &XCacheConfig) -> Result<(), crate::error::
         100_000
     };
 
-    let default_ttl = xutil::to_duration(&config.default_ttl).unwrap_or_else(|| {
-        xutil::warn_if_enable_debug(&format!(
-            "XCache invalid default_ttl=[{}], using 300s",
-            config.default_ttl
-        ));
-        std::time::Duration::from_secs(300)
-    });
+    let default_ttl = config.default_ttl;
 
     let cache_instance = Arc::new(Cache::new(max_capacity, default_ttl));
 
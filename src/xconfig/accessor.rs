@@ -12,16 +12,37 @@
 //! let name = x_one::xconfig::get_string("Server.Name");       // "my-app"
 //! let port = x_one::xconfig::get_int("Server.Port");           // 8080
 //! let tags = x_one::xconfig::get_string_slice("Server.Tags");  // ["api", "v2"]
+//!
+//! // 类型化访问
+//! let timeout = x_one::xconfig::get_duration("XHttp.Timeout");  // Some(30s)
+//! let port = x_one::xconfig::get_or("XAxum.Port", 8000_u16);
+//! let level = x_one::xconfig::try_get::<String>("XLog.Level")?; // 类型不符时返回带 key 路径的错误
 //! ```
 
 use super::config_store;
+use crate::error::XOneError;
+use crate::xutil;
+use std::collections::HashMap;
+use std::time::Duration;
 
 /// 获取配置值（支持点分路径，如 `"Server.Name"`）
 ///
 /// 返回原始 `serde_yaml::Value`，不存在时返回 `None`。
 pub fn get_value(key: &str) -> Option<serde_yaml::Value> {
     let store = config_store().read();
-    let config = store.as_ref()?;
+    lookup(store.as_ref()?, key).cloned()
+}
+
+/// 在指定配置树中按点分路径查找节点
+///
+/// 空 key 表示整棵配置树。
+pub(crate) fn lookup<'a>(
+    config: &'a serde_yaml::Value,
+    key: &str,
+) -> Option<&'a serde_yaml::Value> {
+    if key.is_empty() {
+        return Some(config);
+    }
 
     let mut current = config;
     for k in key.split('.') {
@@ -30,7 +51,7 @@ pub fn get_value(key: &str) -> Option<serde_yaml::Value> {
             None => return None,
         }
     }
-    Some(current.clone())
+    Some(current)
 }
 
 /// 获取字符串配置值，不存在时返回空字符串
@@ -68,6 +89,47 @@ pub fn get_string_slice(key: &str) -> Vec<String> {
         .unwrap_or_default()
 }
 
+/// 获取时长配置值（如 `"500ms"`、`"1h30m"`），不存在或无法解析时返回 `None`
+pub fn get_duration(key: &str) -> Option<Duration> {
+    get_value(key).and_then(|v| v.as_str().and_then(xutil::to_duration))
+}
+
+/// 获取字节大小配置值（如 `"64MiB"`、`"1GB"`，整数按字节处理），不存在或无法解析时返回 `None`
+pub fn get_byte_size(key: &str) -> Option<u64> {
+    match get_value(key)? {
+        serde_yaml::Value::Number(n) => n.as_u64(),
+        serde_yaml::Value::String(s) => xutil::to_byte_size(&s),
+        _ => None,
+    }
+}
+
+/// 获取字符串映射配置值，标量值转为字符串，非标量值忽略，不存在时返回空 Map
+pub fn get_map(key: &str) -> HashMap<String, String> {
+    let Some(serde_yaml::Value::Mapping(map)) = get_value(key) else {
+        return HashMap::new();
+    };
+    map.into_iter()
+        .filter_map(|(k, v)| Some((scalar_to_string(&k)?, scalar_to_string(&v)?)))
+        .collect()
+}
+
+/// 获取配置值并反序列化为指定类型，不存在或类型不符时返回 `default`
+pub fn get_or<T: serde::de::DeserializeOwned>(key: &str, default: T) -> T {
+    try_get(key).ok().flatten().unwrap_or(default)
+}
+
+/// 获取配置值并反序列化为指定类型
+///
+/// 不存在时返回 `Ok(None)`；类型不符时返回 `XOneError::Config`，错误信息包含 key 路径。
+pub fn try_get<T: serde::de::DeserializeOwned>(key: &str) -> Result<Option<T>, XOneError> {
+    get_value(key)
+        .map(|value| {
+            serde_yaml::from_value(value)
+                .map_err(|e| XOneError::Config(format!("parse config [{key}] failed, err=[{e}]")))
+        })
+        .transpose()
+}
+
 /// 检查配置中是否包含指定 key
 pub fn contain_key(key: &str) -> bool {
     get_value(key).is_some()
@@ -91,31 +153,33 @@ pub fn parse_config<T: serde::de::DeserializeOwned>(
     })
 }
 
-/// 解析配置值为单个或列表
+/// 解析配置值为单个或列表，不存在时返回空列表
+///
+/// 值既不能解析为单个也不能解析为列表时返回错误（Sequence 报告列表解析错误，其他报告单个解析错误）。
 #[cfg_attr(
     not(any(feature = "orm", feature = "cache", feature = "redis-store")),
     allow(dead_code)
 )]
-pub(crate) fn parse_config_list<T: serde::de::DeserializeOwned>(key: &str) -> Vec<T> {
-    match parse_config::<T>(key) {
-        Ok(config) => return vec![config],
-        Err(crate::error::XOneError::Config(ref msg)) if !msg.contains("not found") => {
-            crate::xutil::info_if_enable_debug(&format!(
-                "parse config [{key}] as single failed: {msg}"
-            ));
-        }
-        _ => {}
+pub(crate) fn parse_config_list<T: serde::de::DeserializeOwned>(
+    key: &str,
+) -> Result<Vec<T>, XOneError> {
+    let Some(value) = get_value(key) else {
+        return Ok(Vec::new());
+    };
+    if value.is_sequence() {
+        return try_get::<Vec<T>>(key).map(Option::unwrap_or_default);
     }
-    match parse_config::<Vec<T>>(key) {
-        Ok(configs) => return configs,
-        Err(crate::error::XOneError::Config(ref msg)) if !msg.contains("not found") => {
-            crate::xutil::info_if_enable_debug(&format!(
-                "parse config [{key}] as list failed: {msg}"
-            ));
-        }
-        _ => {}
+    try_get::<T>(key).map(|c| c.into_iter().collect())
+}
+
+/// 标量配置值转为字符串
+fn scalar_to_string(v: &serde_yaml::Value) -> Option<String> {
+    match v {
+        serde_yaml::Value::String(s) => Some(s.clone()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        serde_yaml::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
     }
-    Vec::new()
 }
 
 #[cfg(test)]
@@ -139,7 +203,7 @@ mod tests {
         crate::xconfig::set_config(config);
 
         // 执行
-        let result = parse_config_list::<SimpleConfig>("TestModule");
+        let result = parse_config_list::<SimpleConfig>("TestModule").unwrap();
 
         // 断言
         assert_eq!(result.len(), 1);
@@ -158,7 +222,7 @@ mod tests {
         crate::xconfig::set_config(config);
 
         // 执行
-        let result = parse_config_list::<SimpleConfig>("TestModule");
+        let result = parse_config_list::<SimpleConfig>("TestModule").unwrap();
 
         // 断言
         assert_eq!(result.len(), 2);
@@ -176,7 +240,7 @@ mod tests {
         let config: serde_yaml::Value = serde_yaml::from_str(yaml).unwrap();
         crate::xconfig::set_config(config);
 
-        let result = parse_config_list::<SimpleConfig>("NonExistent");
+        let result = parse_config_list::<SimpleConfig>("NonExistent").unwrap();
         assert!(result.is_empty());
 
         crate::xconfig::reset_config();
@@ -184,15 +248,15 @@ mod tests {
 
     #[test]
     #[serial]
-    fn test_parse_config_list_invalid_value_returns_empty() {
+    fn test_parse_config_list_invalid_value_returns_error() {
         // 准备：值既不能解析为单实例也不能解析为列表
         crate::xconfig::reset_config();
         let yaml = "TestModule: just_a_string\n";
         let config: serde_yaml::Value = serde_yaml::from_str(yaml).unwrap();
         crate::xconfig::set_config(config);
 
-        let result = parse_config_list::<SimpleConfig>("TestModule");
-        assert!(result.is_empty());
+        let err = parse_config_list::<SimpleConfig>("TestModule").unwrap_err();
+        assert!(err.to_string().contains("parse config [TestModule] failed"));
 
         crate::xconfig::reset_config();
     }
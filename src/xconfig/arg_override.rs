@@ -0,0 +1,61 @@
+//! 命令行参数覆盖配置
+//!
+//! `--XAxum.Port=9000` 形式的启动参数在配置源和环境变量覆盖之后应用，优先级最高。
+//! key 为点分路径，匹配规则与值类型转换同 [`env_override`](super::env_override)，
+//! 数字段用于索引列表（如 `--XRedis.1.Addr=redis:6379`）。
+//! 配置中缺失的 key 同样按 schema 声明的名称创建、值按 YAML 标量解析，
+//! 如文件中未配置 `Port` 时 `--XAxum.Port=9000` 写入整数 `9000`。
+
+use super::env_override;
+use crate::xutil;
+
+/// 生效的命令行覆盖
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArgOverride {
+    /// 命令行参数 key（不含 `--`）
+    pub arg_key: String,
+    /// 被覆盖的配置路径（点分，列表索引为数字）
+    pub key: String,
+}
+
+/// 是否存在命令行配置覆盖参数
+pub fn has_arg_overrides() -> bool {
+    !xutil::get_override_args().is_empty()
+}
+
+/// 使用当前进程启动参数覆盖配置，返回生效的覆盖列表
+pub fn apply_arg_overrides(config: &mut serde_yaml::Value) -> Vec<ArgOverride> {
+    let applied = apply_arg_overrides_from(config, xutil::get_override_args());
+    for o in &applied {
+        xutil::info_if_enable_debug(&format!(
+            "XOne config arg override applied, arg=[--{}], key=[{}]",
+            o.arg_key, o.key
+        ));
+    }
+    applied
+}
+
+/// 使用给定的 `(key, value)` 覆盖配置，按顺序应用，返回生效的覆盖列表
+pub fn apply_arg_overrides_from(
+    config: &mut serde_yaml::Value,
+    args: impl IntoIterator<Item = (String, String)>,
+) -> Vec<ArgOverride> {
+    let mut applied = Vec::new();
+    for (arg_key, value) in args {
+        let segments = arg_key.split('.').collect::<Vec<_>>();
+        if segments.iter().any(|s| s.is_empty()) {
+            xutil::warn_if_enable_debug(&format!(
+                "XOne config arg override ignored, invalid key, arg=[--{arg_key}]"
+            ));
+            continue;
+        }
+
+        match env_override::override_value(config, &segments, &value) {
+            Ok(key) => applied.push(ArgOverride { arg_key, key }),
+            Err(reason) => xutil::warn_if_enable_debug(&format!(
+                "XOne config arg override ignored, arg=[--{arg_key}], reason=[{reason}]"
+            )),
+        }
+    }
+    applied
+}
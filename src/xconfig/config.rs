@@ -7,10 +7,23 @@
 //!   Name: "my-app"
 //!   Version: "v1.0.0"
 //!   Profiles:
-//!     Active: "dev"
+//!     Active: "prod,canary"
+//!     Groups:
+//!       prod: ["cn-east", "prod-db"]
+//!   Reload:
+//!     Enable: true
+//!     Interval: "2s"
+//!   Imports: ["db.yml", "redis.toml"]
+//!   Sources:
+//!     - Type: "dir"
+//!       Path: "/run/secrets"
+//!   Validation:
+//!     Mode: "strict"
 //! ```
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
 
 /// Server 配置 key
 pub const SERVER_CONFIG_KEY: &str = "Server";
@@ -30,6 +43,22 @@ pub struct ServerConfig {
     /// 环境相关配置
     #[serde(rename = "Profiles")]
     pub profiles: Option<ProfilesConfig>,
+
+    /// 配置热更新
+    #[serde(rename = "Reload")]
+    pub reload: Option<ReloadConfig>,
+
+    /// 导入的配置文件（相对于声明文件所在目录，按声明顺序合并）
+    #[serde(rename = "Imports")]
+    pub imports: Vec<String>,
+
+    /// 额外配置源（按声明顺序叠加在本地配置文件之上）
+    #[serde(rename = "Sources")]
+    pub sources: Vec<SourceConfig>,
+
+    /// 配置 schema 校验
+    #[serde(rename = "Validation")]
+    pub validation: Option<ValidationConfig>,
 }
 
 impl Default for ServerConfig {
@@ -38,6 +67,10 @@ impl Default for ServerConfig {
             name: String::new(),
             version: "v0.0.1".to_string(),
             profiles: None,
+            reload: None,
+            imports: Vec::new(),
+            sources: Vec::new(),
+            validation: None,
         }
     }
 }
@@ -46,7 +79,105 @@ impl Default for ServerConfig {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(default)]
 pub struct ProfilesConfig {
-    /// 指定启用的环境
+    /// 指定启用的环境（逗号分隔的有序列表，如 "prod,cn-east,canary"）
     #[serde(rename = "Active")]
     pub active: String,
+
+    /// profile 组（组名 -> 成员列表），激活组时依次激活组名及其成员
+    #[serde(rename = "Groups")]
+    pub groups: HashMap<String, Vec<String>>,
+}
+
+/// 配置热更新
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReloadConfig {
+    /// 是否监听配置文件变更（默认 false）
+    #[serde(rename = "Enable")]
+    pub enable: bool,
+
+    /// 文件变更检查间隔（duration 字符串，默认 "2s"）
+    #[serde(rename = "Interval", with = "crate::xutil::serde_duration")]
+    pub interval: Duration,
+}
+
+impl Default for ReloadConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            interval: Duration::from_secs(2),
+        }
+    }
+}
+
+/// 配置 schema 校验
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ValidationConfig {
+    /// 校验模式：lenient（仅告警）/ strict（校验失败时启动报错）/ off（不校验），默认 "lenient"
+    #[serde(rename = "Mode")]
+    pub mode: String,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        Self {
+            mode: "lenient".to_string(),
+        }
+    }
+}
+
+/// 额外配置源声明
+///
+/// `Type` 决定使用的字段：
+/// - `file`：`Path` 指向 YAML 文件
+/// - `dir`：`Path` 指向目录，每个文件名为点分 key、内容为值（`.yml`/`.yaml`/`.json` 文件按配置树合并）
+/// - `env`：`Prefix` 指定环境变量前缀，`__` 分隔层级
+/// - `http`：`Url` 指向返回 YAML/JSON 的 KV 接口（需启用 `http` feature）
+///
+/// 相对路径相对于主配置文件所在目录解析。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SourceConfig {
+    /// 配置源类型：file / dir / env / http
+    #[serde(rename = "Type")]
+    pub source_type: String,
+
+    /// 文件或目录路径（file、dir）
+    #[serde(rename = "Path")]
+    pub path: String,
+
+    /// 环境变量前缀（env）
+    #[serde(rename = "Prefix")]
+    pub prefix: String,
+
+    /// KV 接口地址（http）
+    #[serde(rename = "Url")]
+    pub url: String,
+
+    /// 请求头（http）
+    #[serde(rename = "Headers")]
+    pub headers: HashMap<String, String>,
+
+    /// 请求超时（http，duration 字符串，默认 "5s"）
+    #[serde(rename = "Timeout", with = "crate::xutil::serde_duration")]
+    pub timeout: Duration,
+
+    /// 配置源不存在或加载失败时是否忽略（默认 false，即启动失败）
+    #[serde(rename = "Optional")]
+    pub optional: bool,
+}
+
+impl Default for SourceConfig {
+    fn default() -> Self {
+        Self {
+            source_type: String::new(),
+            path: String::new(),
+            prefix: String::new(),
+            url: String::new(),
+            headers: HashMap::new(),
+            timeout: Duration::from_secs(5),
+            optional: false,
+        }
+    }
 }
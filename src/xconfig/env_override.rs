@@ -0,0 +1,217 @@
+//! 环境变量覆盖配置
+//!
+//! 以 `XONE_` 为前缀、`__` 分隔层级的环境变量覆盖合并后配置树中的对应节点，
+//! 无需修改 YAML 即可调整任意配置：
+//!
+//! - `XONE_XORM__DSN=...` → `XOrm.DSN`
+//! - `XONE_XAXUM__PORT=9000` → `XAxum.Port`
+//! - `XONE_XREDIS__1__ADDR=...` → `XRedis` 列表中第 2 个实例的 `Addr`
+//!
+//! key 先精确匹配，找不到再忽略 ASCII 大小写匹配已有 key；数字段用于索引列表。
+//! 值按已有节点的 YAML 类型转换（bool / int / float / string），
+//! 已有节点为 Mapping 或列表时按 YAML 解析整体替换，转换失败时忽略该覆盖。
+//! 节点不存在时按 schema 中声明的 key 名（忽略大小写）创建，未声明时沿用环境变量中的写法；
+//! 值按 YAML 标量解析（如 `9000` 为整数、`true` 为布尔），schema 声明为 string 的节点保持字符串。
+//!
+//! 不含 `__` 的环境变量只覆盖已存在的顶层 key，避免与 `XONE_ENABLE_DEBUG` 等框架变量冲突。
+
+use super::{schema, secret};
+use crate::xutil;
+
+/// 环境变量覆盖前缀
+pub const ENV_OVERRIDE_PREFIX: &str = "XONE_";
+
+/// 环境变量覆盖层级分隔符
+const ENV_OVERRIDE_SEPARATOR: &str = "__";
+
+/// 生效的环境变量覆盖
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvOverride {
+    /// 环境变量名
+    pub env_key: String,
+    /// 被覆盖的配置路径（点分，列表索引为数字）
+    pub key: String,
+}
+
+/// 是否存在 `XONE_` 前缀的覆盖环境变量
+pub fn has_env_overrides() -> bool {
+    !collect_env_vars(std::env::vars()).is_empty()
+}
+
+/// 使用当前进程环境变量覆盖配置，返回生效的覆盖列表
+pub fn apply_env_overrides(config: &mut serde_yaml::Value) -> Vec<EnvOverride> {
+    let applied = apply_env_overrides_from(config, std::env::vars());
+    for o in &applied {
+        xutil::info_if_enable_debug(&format!(
+            "XOne config env override applied, env=[{}], key=[{}]",
+            o.env_key, o.key
+        ));
+    }
+    applied
+}
+
+/// 使用给定的环境变量覆盖配置，返回生效的覆盖列表
+///
+/// 变量按名称排序后依次应用，非 `XONE_` 前缀的变量被忽略。
+pub fn apply_env_overrides_from(
+    config: &mut serde_yaml::Value,
+    vars: impl IntoIterator<Item = (String, String)>,
+) -> Vec<EnvOverride> {
+    let mut applied = Vec::new();
+    for (env_key, path, value) in collect_env_vars(vars) {
+        let segments = path.split(ENV_OVERRIDE_SEPARATOR).collect::<Vec<_>>();
+        if segments.iter().any(|s| s.is_empty()) {
+            xutil::warn_if_enable_debug(&format!(
+                "XOne config env override ignored, invalid key, env=[{env_key}]"
+            ));
+            continue;
+        }
+        if segments.len() == 1 && find_key(config, segments[0]).is_none() {
+            continue;
+        }
+
+        match override_value(config, &segments, &value) {
+            Ok(key) => applied.push(EnvOverride { env_key, key }),
+            Err(reason) => xutil::warn_if_enable_debug(&format!(
+                "XOne config env override ignored, env=[{env_key}], reason=[{reason}]"
+            )),
+        }
+    }
+    applied
+}
+
+// ---- 以下为私有实现 ----
+
+/// 筛选并排序 `XONE_` 前缀的环境变量，返回 (变量名, 去前缀路径, 值)
+fn collect_env_vars(
+    vars: impl IntoIterator<Item = (String, String)>,
+) -> Vec<(String, String, String)> {
+    let mut result = vars
+        .into_iter()
+        .filter(|(k, _)| k != xutil::DEBUG_KEY && k != secret::SECRET_KEY_ENV_KEY)
+        .filter_map(|(k, v)| {
+            let path = k.strip_prefix(ENV_OVERRIDE_PREFIX)?.to_string();
+            (!path.is_empty()).then_some((k, path, v))
+        })
+        .collect::<Vec<_>>();
+    result.sort();
+    result
+}
+
+/// 沿路径定位节点并写入转换后的值，返回实际覆盖的配置路径（命令行覆盖复用）
+///
+/// 同步沿 schema 下降，用于确定缺失节点的 key 名及新值的类型。
+pub(crate) fn override_value(
+    config: &mut serde_yaml::Value,
+    segments: &[&str],
+    raw: &str,
+) -> Result<String, String> {
+    let root_schema = schema::effective_schema();
+    let mut node_schema = Some(&root_schema);
+    let mut resolved = Vec::with_capacity(segments.len());
+    let mut current = config;
+
+    for segment in segments {
+        if current.is_null() {
+            *current = serde_yaml::Value::Mapping(serde_yaml::Mapping::new());
+        }
+        current = match current {
+            serde_yaml::Value::Sequence(seq) => {
+                let index = segment
+                    .parse::<usize>()
+                    .map_err(|_| format!("[{segment}] is not a list index"))?;
+                let len = seq.len();
+                resolved.push(index.to_string());
+                node_schema = node_schema.and_then(|s| schema::items_schema(s, &root_schema));
+                seq.get_mut(index)
+                    .ok_or_else(|| format!("index [{index}] out of range, len=[{len}]"))?
+            }
+            serde_yaml::Value::Mapping(map) => {
+                let declared =
+                    node_schema.and_then(|s| schema::property_schema(s, &root_schema, segment));
+                node_schema = declared.map(|(_, s)| s);
+                let key = find_key_in_mapping(map, segment).unwrap_or_else(|| {
+                    let name = declared.map_or(*segment, |(name, _)| name);
+                    serde_yaml::Value::String(name.to_string())
+                });
+                resolved.push(key.as_str().unwrap_or(segment).to_string());
+                map.entry(key).or_insert(serde_yaml::Value::Null)
+            }
+            _ => {
+                return Err(format!(
+                    "[{}] is a scalar and has no child",
+                    resolved.join(".")
+                ));
+            }
+        };
+    }
+
+    *current = coerce_value(current, node_schema, raw)?;
+    Ok(resolved.join("."))
+}
+
+/// 按已有节点的 YAML 类型转换环境变量值，节点不存在时按 YAML 标量解析
+fn coerce_value(
+    existing: &serde_yaml::Value,
+    node_schema: Option<&serde_json::Value>,
+    raw: &str,
+) -> Result<serde_yaml::Value, String> {
+    let type_mismatch = |expect: &str| format!("value [{raw}] is not a valid {expect}");
+    match existing {
+        serde_yaml::Value::Null => Ok(parse_scalar(node_schema, raw)),
+        serde_yaml::Value::String(_) => Ok(serde_yaml::Value::String(raw.to_string())),
+        serde_yaml::Value::Bool(_) => match raw.trim().to_ascii_lowercase().as_str() {
+            "true" => Ok(serde_yaml::Value::Bool(true)),
+            "false" => Ok(serde_yaml::Value::Bool(false)),
+            _ => Err(type_mismatch("bool")),
+        },
+        serde_yaml::Value::Number(n) if n.is_f64() => raw
+            .trim()
+            .parse::<f64>()
+            .map(serde_yaml::Value::from)
+            .map_err(|_| type_mismatch("float")),
+        serde_yaml::Value::Number(_) => raw
+            .trim()
+            .parse::<i64>()
+            .map(serde_yaml::Value::from)
+            .map_err(|_| type_mismatch("integer")),
+        serde_yaml::Value::Sequence(_) => match serde_yaml::from_str(raw) {
+            Ok(v @ serde_yaml::Value::Sequence(_)) => Ok(v),
+            _ => Err(type_mismatch("list")),
+        },
+        serde_yaml::Value::Mapping(_) => match serde_yaml::from_str(raw) {
+            Ok(v @ serde_yaml::Value::Mapping(_)) => Ok(v),
+            _ => Err(type_mismatch("mapping")),
+        },
+        serde_yaml::Value::Tagged(_) => Err("tagged value is not supported".to_string()),
+    }
+}
+
+/// 将值解析为 YAML 标量（bool / int / float / string），schema 声明为 string 或解析结果非标量时保持字符串
+fn parse_scalar(node_schema: Option<&serde_json::Value>, raw: &str) -> serde_yaml::Value {
+    if node_schema.is_some_and(schema::is_string_schema) {
+        return serde_yaml::Value::String(raw.to_string());
+    }
+    match serde_yaml::from_str(raw) {
+        Ok(v @ (serde_yaml::Value::Bool(_) | serde_yaml::Value::Number(_))) => v,
+        _ => serde_yaml::Value::String(raw.to_string()),
+    }
+}
+
+/// 在配置根节点中查找 key
+fn find_key(config: &serde_yaml::Value, segment: &str) -> Option<serde_yaml::Value> {
+    config
+        .as_mapping()
+        .and_then(|map| find_key_in_mapping(map, segment))
+}
+
+/// 查找 Mapping 中的 key：先精确匹配，再忽略 ASCII 大小写匹配
+fn find_key_in_mapping(map: &serde_yaml::Mapping, segment: &str) -> Option<serde_yaml::Value> {
+    let exact = serde_yaml::Value::String(segment.to_string());
+    if map.contains_key(&exact) {
+        return Some(exact);
+    }
+    map.keys()
+        .find(|k| k.as_str().is_some_and(|s| s.eq_ignore_ascii_case(segment)))
+        .cloned()
+}
@@ -0,0 +1,63 @@
+//! 配置文件格式
+//!
+//! 按文件扩展名选择解析器，统一解析为 `serde_yaml::Value` 配置树：
+//!
+//! - `.yml` / `.yaml`：YAML（未知扩展名同样按 YAML 解析）
+//! - `.json`：JSON
+//! - `.toml`：TOML
+
+use crate::error::XOneError;
+use std::path::Path;
+
+/// 支持的配置文件扩展名（按默认搜索优先级排序）
+pub const CONFIG_FILE_EXTENSIONS: &[&str] = &["yml", "yaml", "toml", "json"];
+
+/// 配置文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    /// YAML
+    Yaml,
+    /// JSON
+    Json,
+    /// TOML
+    Toml,
+}
+
+impl ConfigFormat {
+    /// 根据文件扩展名判断格式（忽略大小写，未知扩展名按 YAML 处理）
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        let ext = path
+            .as_ref()
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match ext.as_str() {
+            "json" => ConfigFormat::Json,
+            "toml" => ConfigFormat::Toml,
+            _ => ConfigFormat::Yaml,
+        }
+    }
+
+    /// 扩展名是否为可识别的配置文件格式
+    pub fn is_config_file(path: impl AsRef<Path>) -> bool {
+        path.as_ref()
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| CONFIG_FILE_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+    }
+
+    /// 解析配置内容，`path` 仅用于错误信息
+    pub fn parse(self, content: &str, path: &str) -> Result<serde_yaml::Value, XOneError> {
+        let result = match self {
+            ConfigFormat::Yaml => serde_yaml::from_str(content).map_err(|e| e.to_string()),
+            ConfigFormat::Json => serde_json::from_str(content).map_err(|e| e.to_string()),
+            ConfigFormat::Toml => toml::from_str(content).map_err(|e| e.to_string()),
+        };
+        result.map_err(|e| {
+            XOneError::Config(format!(
+                "parse config file failed, path=[{path}], err=[{e}]"
+            ))
+        })
+    }
+}
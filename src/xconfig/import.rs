@@ -0,0 +1,121 @@
+//! 配置文件导入
+//!
+//! 配置文件可通过 `Server.Imports` 拆分为多个文件：
+//!
+//! ```yaml
+//! Server:
+//!   Name: "my-app"
+//!   Imports: ["db.yml", "redis.toml", "${CONFIG_DIR:-.}/extra.json"]
+//! ```
+//!
+//! - 导入路径相对于声明它的文件所在目录解析，支持 `${VAR:-default}` 占位符
+//! - 被导入文件按声明顺序深度合并（后导入的覆盖先导入的），声明文件自身的配置优先级最高
+//! - 被导入文件可继续声明 `Server.Imports`，出现循环导入时返回错误
+//! - 解析器按扩展名选择（见 [`format`](super::format)），profile 文件同样支持导入
+
+use super::accessor;
+use super::env_expand;
+use super::init;
+use crate::error::XOneError;
+use crate::xutil;
+use parking_lot::Mutex;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Server.Imports 配置路径
+const IMPORTS_CONFIG_KEY: &str = "Server.Imports";
+
+/// 最近一次加载配置时导入的文件（供热更新监听使用）
+fn imported_files_store() -> &'static Mutex<Vec<String>> {
+    static INSTANCE: OnceLock<Mutex<Vec<String>>> = OnceLock::new();
+    INSTANCE.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+/// 加载配置文件并递归合并其导入的文件
+///
+/// 返回合并后的配置树，以及所有被导入文件的路径（按加载顺序，不含 `path` 自身）。
+pub fn load_config_with_imports(path: &str) -> Result<(serde_yaml::Value, Vec<String>), XOneError> {
+    let mut imported = Vec::new();
+    let config = load_recursive(Path::new(path), &mut Vec::new(), &mut imported)?;
+    Ok((config, imported))
+}
+
+/// 最近一次加载配置时导入的文件
+pub(crate) fn imported_files() -> Vec<String> {
+    imported_files_store().lock().clone()
+}
+
+/// 记录最近一次加载配置时导入的文件
+pub(crate) fn set_imported_files(files: Vec<String>) {
+    *imported_files_store().lock() = files;
+}
+
+// ---- 以下为私有实现 ----
+
+fn load_recursive(
+    path: &Path,
+    chain: &mut Vec<PathBuf>,
+    imported: &mut Vec<String>,
+) -> Result<serde_yaml::Value, XOneError> {
+    let canonical = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    if chain.contains(&canonical) {
+        let cycle = chain
+            .iter()
+            .chain(std::iter::once(&canonical))
+            .map(|p| p.display().to_string())
+            .collect::<Vec<_>>()
+            .join(" -> ");
+        return Err(XOneError::Config(format!(
+            "config import cycle detected, chain=[{cycle}]"
+        )));
+    }
+
+    let config = init::load_local_config(&path.to_string_lossy())?;
+    let imports = declared_imports(&config, path)?;
+    if imports.is_empty() {
+        return Ok(config);
+    }
+
+    let base_dir = path.parent().unwrap_or_else(|| Path::new(""));
+    chain.push(canonical);
+    let mut merged = serde_yaml::Value::Mapping(serde_yaml::Mapping::new());
+    for import in imports {
+        let import_path = base_dir.join(&import);
+        if !xutil::file_exist(&import_path) {
+            return Err(XOneError::Config(format!(
+                "config import file not found, path=[{}], imported by=[{}]",
+                import_path.display(),
+                path.display()
+            )));
+        }
+
+        let overlay = load_recursive(&import_path, chain, imported)?;
+        xutil::info_if_enable_debug(&format!(
+            "XOne config import [{}] loaded",
+            import_path.display()
+        ));
+        imported.push(import_path.to_string_lossy().to_string());
+        merged = init::deep_merge_config(merged, overlay);
+    }
+    chain.pop();
+
+    Ok(init::deep_merge_config(merged, config))
+}
+
+/// 读取文件中声明的导入路径（展开环境变量占位符）
+fn declared_imports(config: &serde_yaml::Value, path: &Path) -> Result<Vec<String>, XOneError> {
+    let Some(value) = accessor::lookup(config, IMPORTS_CONFIG_KEY) else {
+        return Ok(Vec::new());
+    };
+    let imports = serde_yaml::from_value::<Vec<String>>(value.clone()).map_err(|e| {
+        XOneError::Config(format!(
+            "parse config [{IMPORTS_CONFIG_KEY}] failed, path=[{}], err=[{e}]",
+            path.display()
+        ))
+    })?;
+    Ok(imports
+        .iter()
+        .map(|i| env_expand::expand_env_placeholder(i).into_owned())
+        .filter(|i| !i.is_empty())
+        .collect())
+}
@@ -1,8 +1,15 @@
 //! 配置初始化
 
+use super::arg_override;
 use super::env_expand;
+use super::env_override;
+use super::format::ConfigFormat;
+use super::import;
 use super::location;
 use super::profiles;
+use super::schema;
+use super::secret;
+use super::source;
 use crate::error::XOneError;
 use crate::xutil;
 use std::path::Path;
@@ -10,12 +17,71 @@ use std::path::Path;
 /// .env 文件名
 const DOT_ENV_FILENAME: &str = ".env";
 
+/// 一次加载的结果：合并后的配置树及加载过程中收集的元信息
+///
+/// 元信息只在配置被接受后通过 [`LoadedConfig::commit`] 写入全局状态，
+/// 被拒绝的热更新不会影响当前生效的状态。
+pub(crate) struct LoadedConfig {
+    /// 合并后的配置树
+    pub(crate) config: serde_yaml::Value,
+    /// 按合并顺序激活的 profile
+    active_profiles: Vec<String>,
+    /// 通过 `Server.Imports` 导入的文件
+    imported_files: Vec<String>,
+    /// 解析出密钥引用或加密值的配置路径
+    secret_paths: Vec<String>,
+}
+
+impl LoadedConfig {
+    /// 将加载过程中收集的元信息写入全局状态
+    pub(crate) fn commit(&self) {
+        profiles::set_active_profiles(self.active_profiles.clone());
+        import::set_imported_files(self.imported_files.clone());
+        secret::set_secret_paths(self.secret_paths.clone());
+    }
+}
+
 /// 初始化配置系统
 ///
 /// 检测配置文件位置、加载 .env 文件、解析配置
 pub fn init_xconfig() -> Result<Option<serde_yaml::Value>, XOneError> {
+    Ok(init_xconfig_with_location()?.map(|(_, config)| config))
+}
+
+/// 初始化配置系统，同时返回探测到的配置文件路径（供热更新使用）
+///
+/// 未找到配置文件但注册了配置源或存在覆盖项（`XONE_` 环境变量、命令行参数）时，
+/// 仅由配置源和覆盖项构建配置，路径为 `None`。
+pub(crate) fn init_xconfig_with_location()
+-> Result<Option<(Option<String>, serde_yaml::Value)>, XOneError> {
     let config_location = match location::detect_config_location() {
         Some(loc) => loc,
+        None if source::has_registered_sources()
+            || env_override::has_env_overrides()
+            || arg_override::has_arg_overrides() =>
+        {
+            xutil::info_if_enable_debug(
+                "XOne initXConfig config file location not found, load registered sources and overrides only",
+            );
+            let (mut config, mut secret_paths) = source::apply_sources(
+                serde_yaml::Value::Mapping(serde_yaml::Mapping::new()),
+                None,
+            )?;
+            env_expand::expand_env_placeholders_in_value(&mut config);
+            env_override::apply_env_overrides(&mut config);
+            arg_override::apply_arg_overrides(&mut config);
+            secret_paths.extend(secret::resolve_secrets(&mut config)?);
+            let loaded = LoadedConfig {
+                config,
+                active_profiles: Vec::new(),
+                imported_files: Vec::new(),
+                secret_paths,
+            };
+            print_final_config(&loaded);
+            schema::check_config(&loaded.config)?;
+            loaded.commit();
+            return Ok(Some((None, loaded.config)));
+        }
         None => {
             xutil::warn_if_enable_debug(
                 "XOne initXConfig config file location not found, use default config",
@@ -26,23 +92,42 @@ pub fn init_xconfig() -> Result<Option<serde_yaml::Value>, XOneError> {
 
     load_dot_env_if_exist(&config_location)?;
 
-    let config = parse_config(&config_location)?;
+    let loaded = parse_config(&config_location)?;
 
-    print_final_config(&config);
+    print_final_config(&loaded);
 
-    Ok(Some(config))
+    schema::check_config(&loaded.config)?;
+
+    loaded.commit();
+    Ok(Some((Some(config_location), loaded.config)))
+}
+
+/// 重新加载配置（热更新使用）
+///
+/// 重新执行环境变量展开与 profile 合并，不重复加载 .env 文件。
+/// 解析失败、根节点不是 Mapping 或 strict 模式下 schema 校验失败时返回错误，调用方应保留旧配置。
+/// 返回结果的元信息尚未写入全局状态，由调用方在接受配置后提交。
+pub(crate) fn reload_xconfig(config_location: &str) -> Result<LoadedConfig, XOneError> {
+    let loaded = parse_config(config_location)?;
+    if !loaded.config.is_mapping() {
+        return Err(XOneError::Config(format!(
+            "reload config failed, root node is not a mapping, path=[{config_location}]"
+        )));
+    }
+
+    print_final_config(&loaded);
+
+    schema::check_config(&loaded.config)?;
+
+    Ok(loaded)
 }
 
-/// 加载本地配置文件
+/// 加载本地配置文件（按扩展名选择 YAML / JSON / TOML 解析器，不处理导入）
 #[doc(hidden)]
 pub fn load_local_config(path: &str) -> Result<serde_yaml::Value, XOneError> {
     let content = std::fs::read_to_string(path)
         .map_err(|e| XOneError::Config(format!("load config file failed, err=[{e}]")))?;
-    serde_yaml::from_str(&content).map_err(|e| {
-        XOneError::Config(format!(
-            "parse config file failed, path=[{path}], err=[{e}]"
-        ))
-    })
+    ConfigFormat::from_path(path).parse(&content, path)
 }
 
 /// 合并环境配置到基础配置
@@ -83,6 +168,44 @@ pub fn merge_profiles_config(
     base
 }
 
+/// 深度合并配置树
+///
+/// Mapping 按 key 递归合并，其他类型（标量、列表）由 `overlay` 整体覆盖。
+/// `overlay` 中的 key 在 `base` 中无精确匹配时，忽略 ASCII 大小写匹配已有 key，
+/// 合并结果保留 `base` 中的 key 写法。
+#[doc(hidden)]
+pub fn deep_merge_config(base: serde_yaml::Value, overlay: serde_yaml::Value) -> serde_yaml::Value {
+    let (mut base_map, overlay_map) = match (base, overlay) {
+        (serde_yaml::Value::Mapping(b), serde_yaml::Value::Mapping(o)) => (b, o),
+        (_, overlay) => return overlay,
+    };
+
+    for (key, value) in overlay_map {
+        let target_key = if base_map.contains_key(&key) {
+            key
+        } else {
+            find_key_ignore_ascii_case(&base_map, &key).unwrap_or(key)
+        };
+        let merged = match base_map.remove(&target_key) {
+            Some(existing) => deep_merge_config(existing, value),
+            None => value,
+        };
+        base_map.insert(target_key, merged);
+    }
+    serde_yaml::Value::Mapping(base_map)
+}
+
+/// 忽略 ASCII 大小写查找 Mapping 中已有的字符串 key
+fn find_key_ignore_ascii_case(
+    map: &serde_yaml::Mapping,
+    key: &serde_yaml::Value,
+) -> Option<serde_yaml::Value> {
+    let key = key.as_str()?;
+    map.keys()
+        .find(|k| k.as_str().is_some_and(|s| s.eq_ignore_ascii_case(key)))
+        .cloned()
+}
+
 /// 加载 .env 文件（如果存在）
 fn load_dot_env_if_exist(config_location: &str) -> Result<(), XOneError> {
     let dot_env_path = Path::new(config_location)
@@ -98,27 +221,30 @@ fn load_dot_env_if_exist(config_location: &str) -> Result<(), XOneError> {
     Ok(())
 }
 
-/// 解析配置文件
-fn parse_config(config_location: &str) -> Result<serde_yaml::Value, XOneError> {
-    // 加载基础配置
-    let mut base_config = load_local_config(config_location)?;
+/// 解析配置文件（不修改全局状态）
+fn parse_config(config_location: &str) -> Result<LoadedConfig, XOneError> {
+    // 加载基础配置（含 Server.Imports 导入的文件）
+    let (mut base_config, mut imported_files) = import::load_config_with_imports(config_location)?;
 
     // 先展开基础配置中的环境变量占位符，使 profiles.active 可引用环境变量
     env_expand::expand_env_placeholders_in_value(&mut base_config);
 
-    // 检测激活的环境
-    if let Some(pa) = profiles::detect_profiles_active(&base_config) {
+    // 检测激活的环境，按顺序合并各 profile 配置（靠后的优先级更高）
+    let profiles_active = profiles::detect_profiles_active(&base_config);
+    for pa in &profiles_active {
         let env_config_location =
-            profiles::to_profiles_active_config_location(config_location, &pa)?;
+            profiles::to_profiles_active_config_location(config_location, pa)?;
 
         if !xutil::file_exist(&env_config_location) {
             xutil::warn_if_enable_debug(&format!(
                 "XOne profiles active config file not found, ignore, env_config_location=[{env_config_location}]"
             ));
-        } else {
-            let env_config = load_local_config(&env_config_location)?;
-            base_config = merge_profiles_config(base_config, env_config);
+            continue;
         }
+
+        let (env_config, env_imported) = import::load_config_with_imports(&env_config_location)?;
+        imported_files.extend(env_imported);
+        base_config = merge_profiles_config(base_config, env_config);
     }
 
     // 检查 Server.Name 是否为空
@@ -132,19 +258,35 @@ fn parse_config(config_location: &str) -> Result<serde_yaml::Value, XOneError> {
         );
     }
 
-    // 合并 profile 配置后再次展开，处理 profile 配置中的环境变量占位符
+    // 叠加额外配置源（Server.Sources 声明的源在前，代码注册的源在后）
+    let (mut base_config, mut secret_paths) =
+        source::apply_sources(base_config, Some(config_location))?;
+
+    // 合并 profile 配置和配置源后再次展开，处理其中的环境变量占位符
     env_expand::expand_env_placeholders_in_value(&mut base_config);
 
-    Ok(base_config)
+    // 覆盖优先级：命令行参数 > XONE_ 前缀环境变量 > 配置源 > 配置文件
+    env_override::apply_env_overrides(&mut base_config);
+    arg_override::apply_arg_overrides(&mut base_config);
+
+    // 最后解析 ${file:...} 引用与 ENC(...) 加密值，覆盖项中同样可以使用
+    secret_paths.extend(secret::resolve_secrets(&mut base_config)?);
+
+    Ok(LoadedConfig {
+        config: base_config,
+        active_profiles: profiles_active,
+        imported_files,
+        secret_paths,
+    })
 }
 
 /// 敏感配置 key（值会被脱敏为 "***"）
 const SENSITIVE_KEYS: &[&str] = &["DSN", "Password", "Secret", "Token", "Key"];
 
 /// 打印最终配置（debug 模式下，敏感字段脱敏）
-fn print_final_config(config: &serde_yaml::Value) {
+fn print_final_config(loaded: &LoadedConfig) {
     if xutil::xone_enable_debug() {
-        let sanitized = sanitize_config(config);
+        let sanitized = sanitize_node(&loaded.config, "", &loaded.secret_paths);
         let config_str = xutil::to_json_string_indent(&sanitized);
         eprintln!(
             "\n************************************** XOne load config **************************************\n{config_str}\n**********************************************************************************************\n"
@@ -152,26 +294,56 @@ fn print_final_config(config: &serde_yaml::Value) {
     }
 }
 
-/// 递归脱敏配置中的敏感字段
-fn sanitize_config(value: &serde_yaml::Value) -> serde_yaml::Value {
+/// 递归脱敏配置中的敏感字段（含解析自密钥引用或加密值的字段）
+pub(crate) fn sanitize_config(value: &serde_yaml::Value) -> serde_yaml::Value {
+    sanitize_node(value, "", &secret::secret_paths())
+}
+
+fn sanitize_node(
+    value: &serde_yaml::Value,
+    path: &str,
+    secret_paths: &[String],
+) -> serde_yaml::Value {
     match value {
         serde_yaml::Value::Mapping(map) => {
             let mut result = serde_yaml::Mapping::new();
             for (k, v) in map {
-                let is_sensitive = k
-                    .as_str()
-                    .is_some_and(|key| SENSITIVE_KEYS.iter().any(|s| key.contains(s)));
+                let key = k.as_str().unwrap_or_default();
+                let child_path = if path.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{path}.{key}")
+                };
+                let is_sensitive = SENSITIVE_KEYS.iter().any(|s| key.contains(s))
+                    || secret_paths.contains(&child_path);
                 if is_sensitive && v.is_string() {
-                    result.insert(k.clone(), serde_yaml::Value::String("***".to_string()));
+                    result.insert(
+                        k.clone(),
+                        serde_yaml::Value::String(secret::REDACTED.to_string()),
+                    );
                 } else {
-                    result.insert(k.clone(), sanitize_config(v));
+                    result.insert(k.clone(), sanitize_node(v, &child_path, secret_paths));
                 }
             }
             serde_yaml::Value::Mapping(result)
         }
-        serde_yaml::Value::Sequence(seq) => {
-            serde_yaml::Value::Sequence(seq.iter().map(sanitize_config).collect())
-        }
+        serde_yaml::Value::Sequence(seq) => serde_yaml::Value::Sequence(
+            seq.iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    let child_path = if path.is_empty() {
+                        i.to_string()
+                    } else {
+                        format!("{path}.{i}")
+                    };
+                    if secret_paths.contains(&child_path) && v.is_string() {
+                        serde_yaml::Value::String(secret::REDACTED.to_string())
+                    } else {
+                        sanitize_node(v, &child_path, secret_paths)
+                    }
+                })
+                .collect(),
+        ),
         other => other.clone(),
     }
 }
@@ -1,5 +1,6 @@
 //! 配置文件路径探测
 
+use super::format::CONFIG_FILE_EXTENSIONS;
 use crate::xutil;
 
 /// 配置文件位置命令行参数 key
@@ -7,19 +8,11 @@ const CONFIG_LOCATION_ARG_KEY: &str = "server.config.location";
 /// 配置文件位置环境变量 key
 pub const CONFIG_LOCATION_ENV_KEY: &str = "SERVER_CONFIG_LOCATION";
 
-/// 配置文件搜索路径列表，按优先级排序
-const CONFIG_LOCATION_PATHS: &[&str] = &[
-    "./application.yml",
-    "./application.yaml",
-    "./conf/application.yml",
-    "./conf/application.yaml",
-    "./config/application.yml",
-    "./config/application.yaml",
-    "./../conf/application.yml",
-    "./../conf/application.yaml",
-    "./../config/application.yml",
-    "./../config/application.yaml",
-];
+/// 配置文件搜索目录列表，按优先级排序
+const CONFIG_LOCATION_DIRS: &[&str] = &["./", "./conf/", "./config/", "./../conf/", "./../config/"];
+
+/// 配置文件名（不含扩展名）
+const CONFIG_FILE_STEM: &str = "application";
 
 /// 检测配置文件位置
 ///
@@ -52,12 +45,17 @@ pub fn get_location_from_env() -> Option<String> {
         .filter(|s| !s.is_empty())
 }
 
+/// 按目录优先级搜索配置文件，同一目录下按 yml > yaml > toml > json 顺序
 #[doc(hidden)]
 pub fn get_location_from_current_dir() -> Option<String> {
-    CONFIG_LOCATION_PATHS
+    CONFIG_LOCATION_DIRS
         .iter()
+        .flat_map(|dir| {
+            CONFIG_FILE_EXTENSIONS
+                .iter()
+                .map(move |ext| format!("{dir}{CONFIG_FILE_STEM}.{ext}"))
+        })
         .find(|loc| xutil::file_exist(loc))
-        .map(|loc| loc.to_string())
 }
 
 fn get_location_from_arg() -> Option<String> {
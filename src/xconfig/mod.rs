@@ -1,27 +1,43 @@
 //! xconfig - 配置管理模块
 //!
-//! 提供 YAML 配置文件加载、多环境 profile 支持、
-//! 环境变量占位符展开等功能。
+//! 提供 YAML / TOML / JSON 配置文件加载、文件导入、多环境 profile 支持、
+//! 环境变量占位符展开、环境变量覆盖、密钥引用与加密值解析、配置热更新、schema 校验等功能。
 
 pub mod accessor;
+pub mod arg_override;
 pub mod config;
 pub mod env_expand;
+pub mod env_override;
+pub mod format;
+pub mod import;
 pub mod init;
 pub mod location;
 pub mod profiles;
+pub mod schema;
+pub mod secret;
 pub mod server_config;
+pub mod source;
+pub mod watcher;
 
 #[cfg(any(feature = "orm", feature = "cache", feature = "redis-store"))]
 pub(crate) use accessor::parse_config_list;
 pub use accessor::{
-    contain_key, get_bool, get_float64, get_int, get_string, get_string_slice, get_value,
-    parse_config,
+    contain_key, get_bool, get_byte_size, get_duration, get_float64, get_int, get_map, get_or,
+    get_string, get_string_slice, get_value, parse_config, try_get,
 };
-pub use config::{ProfilesConfig, SERVER_CONFIG_KEY, ServerConfig};
+pub use config::{
+    ProfilesConfig, ReloadConfig, SERVER_CONFIG_KEY, ServerConfig, SourceConfig, ValidationConfig,
+};
+pub use profiles::active_profiles;
+pub use schema::{SchemaViolation, register_schema};
 pub use server_config::{
     DEFAULT_SERVER_NAME, DEFAULT_SERVER_VERSION, get_raw_server_name, get_server_name,
     get_server_version,
 };
+pub use source::{ConfigSource, add_source};
+pub use watcher::{
+    ConfigChange, SubscriptionId, reload, start_watch, stop_watch, subscribe, unsubscribe, watch,
+};
 
 use parking_lot::RwLock;
 use std::sync::OnceLock;
@@ -47,14 +63,33 @@ pub fn register_hook() {
     }
 
     crate::before_start!(init_store, crate::xhook::HookOptions::new().order(10));
+    crate::before_stop!(
+        watcher::stop_watch,
+        crate::xhook::HookOptions::new().order(i32::MAX - 10)
+    );
 }
 
 /// 初始化配置存储（供框架内部自动初始化使用）
+///
+/// 配置了 `Server.Reload.Enable: true` 时同时启动配置文件监听。
 pub fn init_store() -> Result<(), crate::error::XOneError> {
-    let config = init::init_xconfig()?;
-    let mut store = config_store().write();
-    *store = config;
-    Ok(())
+    match load_store()? {
+        Some(location) => watcher::start_watch_if_enabled(&location),
+        None => Ok(()),
+    }
+}
+
+/// 加载配置到全局存储，不启动配置文件监听（供 `--print-config` 等一次性命令使用）
+///
+/// 返回探测到的配置文件路径，仅由配置源构建配置时为 `None`。
+pub(crate) fn load_store() -> Result<Option<String>, crate::error::XOneError> {
+    let Some((location, config)) = init::init_xconfig_with_location()? else {
+        *config_store().write() = None;
+        return Ok(None);
+    };
+
+    *config_store().write() = Some(config);
+    Ok(location)
 }
 
 /// 重置配置存储（仅测试用）
@@ -1,6 +1,23 @@
 //! 多环境 profile 配置支持
+//!
+//! 激活的 profile 为逗号分隔的有序列表，按顺序将 `application-{profile}.yml` 合并到基础配置之上，
+//! 靠后的 profile 优先级更高。基础配置中可定义 profile 组，激活组时按顺序展开为组名及其成员：
+//!
+//! ```yaml
+//! Server:
+//!   Profiles:
+//!     Active: "prod,canary"
+//!     Groups:
+//!       prod: ["cn-east", "prod-db"]
+//! ```
+//!
+//! 上例最终激活 `prod, cn-east, prod-db, canary`。
 
+use super::accessor;
 use crate::xutil;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::OnceLock;
 
 /// Profile active 命令行参数 key
 const PROFILES_ACTIVE_ARG_KEY: &str = "server.profiles.active";
@@ -8,11 +25,82 @@ const PROFILES_ACTIVE_ARG_KEY: &str = "server.profiles.active";
 pub const PROFILES_ACTIVE_ENV_KEY: &str = "SERVER_PROFILES_ACTIVE";
 /// Profile active 配置文件中的 key 路径
 const PROFILES_ACTIVE_CONFIG_KEY: &str = "Server.Profiles.Active";
+/// Profile 组配置文件中的 key 路径
+const PROFILES_GROUPS_CONFIG_KEY: &str = "Server.Profiles.Groups";
 
-/// 检测激活的环境
+/// 最近一次加载配置时激活的 profile 列表
+fn active_profiles_store() -> &'static Mutex<Vec<String>> {
+    static INSTANCE: OnceLock<Mutex<Vec<String>>> = OnceLock::new();
+    INSTANCE.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+/// 检测激活的环境列表（已展开 profile 组，按合并顺序排列）
 ///
-/// 优先级：命令行参数 > 环境变量 > 配置文件中的值
-pub fn detect_profiles_active(config: &serde_yaml::Value) -> Option<String> {
+/// 优先级：命令行参数 > 环境变量 > 配置文件中的值，取到的值按逗号拆分；
+/// profile 组始终从基础配置文件中读取。未激活任何环境时返回空列表。
+pub fn detect_profiles_active(config: &serde_yaml::Value) -> Vec<String> {
+    let Some(raw) = detect_profiles_active_raw(config) else {
+        return Vec::new();
+    };
+
+    let groups = get_profile_groups_from_config(config);
+    let profiles = expand_profile_groups(&parse_profiles_list(&raw), &groups);
+    if !groups.is_empty() {
+        xutil::info_if_enable_debug(&format!(
+            "XOne profiles active expanded to [{}]",
+            profiles.join(",")
+        ));
+    }
+    profiles
+}
+
+/// 当前激活的 profile 列表（按合并顺序，配置加载前为空）
+pub fn active_profiles() -> Vec<String> {
+    active_profiles_store().lock().clone()
+}
+
+/// 记录当前生效配置激活的 profile 列表（配置被接受后调用）
+pub(crate) fn set_active_profiles(profiles: Vec<String>) {
+    *active_profiles_store().lock() = profiles;
+}
+
+/// 按逗号拆分 profile 列表，去除空白与重复项（保留首次出现的位置）
+pub fn parse_profiles_list(raw: &str) -> Vec<String> {
+    let mut result: Vec<String> = Vec::new();
+    for p in raw.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        if !result.iter().any(|r| r == p) {
+            result.push(p.to_string());
+        }
+    }
+    result
+}
+
+/// 展开 profile 组：组名之后依次插入其成员（成员可以是另一个组），重复项只保留首次出现
+pub fn expand_profile_groups(
+    profiles: &[String],
+    groups: &HashMap<String, Vec<String>>,
+) -> Vec<String> {
+    let mut result = Vec::new();
+    for p in profiles {
+        expand_profile(p, groups, &mut result);
+    }
+    result
+}
+
+#[doc(hidden)]
+pub fn get_profile_groups_from_config(config: &serde_yaml::Value) -> HashMap<String, Vec<String>> {
+    let Some(value) = accessor::lookup(config, PROFILES_GROUPS_CONFIG_KEY) else {
+        return HashMap::new();
+    };
+    serde_yaml::from_value(value.clone()).unwrap_or_else(|e| {
+        xutil::warn_if_enable_debug(&format!(
+            "XOne parse config [{PROFILES_GROUPS_CONFIG_KEY}] failed, ignore, err=[{e}]"
+        ));
+        HashMap::new()
+    })
+}
+
+fn detect_profiles_active_raw(config: &serde_yaml::Value) -> Option<String> {
     if let Some(pa) = get_profiles_active_from_arg() {
         xutil::info_if_enable_debug(&format!("XOne detect profiles active [{pa}] from arg"));
         return Some(pa);
@@ -43,16 +131,7 @@ pub fn get_profiles_active_from_env() -> Option<String> {
 
 #[doc(hidden)]
 pub fn get_profiles_active_from_config(config: &serde_yaml::Value) -> Option<String> {
-    // 按照 "Server.Profiles.Active" 的点分路径访问
-    let keys: Vec<&str> = PROFILES_ACTIVE_CONFIG_KEY.split('.').collect();
-    let mut current = config;
-    for key in keys {
-        match current.get(key) {
-            Some(v) => current = v,
-            None => return None,
-        }
-    }
-    current
+    accessor::lookup(config, PROFILES_ACTIVE_CONFIG_KEY)?
         .as_str()
         .map(|s| s.to_string())
         .filter(|s| !s.is_empty())
@@ -87,3 +166,16 @@ pub fn to_profiles_active_config_location(
 fn get_profiles_active_from_arg() -> Option<String> {
     xutil::get_config_from_args(PROFILES_ACTIVE_ARG_KEY).filter(|s| !s.is_empty())
 }
+
+fn expand_profile(name: &str, groups: &HashMap<String, Vec<String>>, result: &mut Vec<String>) {
+    // 已展开过的 profile 直接跳过，同时避免组之间循环引用导致无限递归
+    if result.iter().any(|r| r == name) {
+        return;
+    }
+    result.push(name.to_string());
+    if let Some(members) = groups.get(name) {
+        for member in members.iter().map(|m| m.trim()).filter(|m| !m.is_empty()) {
+            expand_profile(member, groups, result);
+        }
+    }
+}
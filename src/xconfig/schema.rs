@@ -0,0 +1,542 @@
+//! 配置 Schema 校验
+//!
+//! 配置合并完成后（含配置源与覆盖项），按内置的 `config_schema.json`
+//! 及业务模块注册的 schema 片段校验配置树，报告每一处违规及其配置路径。
+//!
+//! 支持 JSON Schema 子集：`type`、`enum`、`minimum`、`maximum`、`properties`、
+//! `additionalProperties`、`required`、`items` 以及文档内 `$ref`（`#/...`）；
+//! `description`、`default` 等注解被忽略，其余关键字作为违规报告，避免约束被静默跳过。
+//! 内置模块节点声明了 `additionalProperties: false`，拼错的 key 会被报告并给出最接近的已知 key，如
+//! ``unknown key `MaxOpenConn` in XOrm[0], did you mean `MaxOpenConns`?``；
+//! 根节点允许未声明的 key，供业务自定义配置使用。
+//!
+//! 校验模式由 `Server.Validation.Mode` 控制：
+//!
+//! - `lenient`（默认）：违规输出到 stderr（不受 debug 开关影响），不影响启动
+//! - `strict`：存在违规时启动（及热更新）失败
+//! - `off`：不校验
+//!
+//! ```ignore
+//! use x_one::xconfig::schema::register_schema;
+//!
+//! // 在 x_one::init() 之前注册业务配置节点的 schema
+//! register_schema("MyModule", serde_json::json!({
+//!     "type": "object",
+//!     "additionalProperties": false,
+//!     "properties": {
+//!         "Enable": { "type": "boolean" },
+//!         "Timeout": { "type": "string" }
+//!     },
+//!     "required": ["Timeout"]
+//! }));
+//! ```
+
+use super::accessor;
+use super::config::ValidationConfig;
+use crate::error::XOneError;
+use parking_lot::Mutex;
+use std::fmt;
+use std::sync::OnceLock;
+
+/// 内置配置 schema
+const BUILTIN_SCHEMA: &str = include_str!("../../config_schema.json");
+
+/// Server.Validation 配置路径
+const VALIDATION_CONFIG_KEY: &str = "Server.Validation";
+
+/// 支持的 schema 关键字（含不参与校验的注解）
+const SUPPORTED_KEYWORDS: &[&str] = &[
+    "type",
+    "enum",
+    "minimum",
+    "maximum",
+    "properties",
+    "additionalProperties",
+    "required",
+    "items",
+    "$ref",
+    "$schema",
+    "definitions",
+    "title",
+    "description",
+    "default",
+];
+
+/// 校验违规
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation {
+    /// 违规节点的配置路径（点分，列表索引为数字，空字符串表示根节点）
+    pub path: String,
+    /// 违规描述
+    pub message: String,
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "<root>"
+        } else {
+            &self.path
+        };
+        write!(f, "{path}: {}", self.message)
+    }
+}
+
+/// 校验模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationMode {
+    /// 违规仅告警
+    Lenient,
+    /// 违规时返回错误
+    Strict,
+    /// 不校验
+    Off,
+}
+
+impl ValidationMode {
+    /// 从配置值解析（忽略大小写，无法识别时为 `None`）
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "lenient" => Some(ValidationMode::Lenient),
+            "strict" => Some(ValidationMode::Strict),
+            "off" => Some(ValidationMode::Off),
+            _ => None,
+        }
+    }
+}
+
+fn registered_schemas() -> &'static Mutex<Vec<(String, serde_json::Value)>> {
+    static INSTANCE: OnceLock<Mutex<Vec<(String, serde_json::Value)>>> = OnceLock::new();
+    INSTANCE.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+/// 注册顶层配置节点的 schema 片段（需在 `x_one::init()` 之前调用）
+///
+/// 同一 key 重复注册时后注册的生效，与内置节点同名时替换内置 schema。
+/// 片段内的 `$ref` 相对于合并后的整个 schema 解析。
+pub fn register_schema(key: &str, schema: serde_json::Value) {
+    registered_schemas().lock().push((key.to_string(), schema));
+}
+
+/// 清空已注册的 schema 片段（仅测试用）
+#[doc(hidden)]
+pub fn reset_schemas() {
+    registered_schemas().lock().clear();
+}
+
+/// 内置 schema（`config_schema.json`）
+pub fn builtin_schema() -> &'static serde_json::Value {
+    static INSTANCE: OnceLock<serde_json::Value> = OnceLock::new();
+    INSTANCE.get_or_init(|| {
+        serde_json::from_str(BUILTIN_SCHEMA).expect("builtin config_schema.json should be valid")
+    })
+}
+
+/// 合并注册片段后的完整 schema
+pub fn effective_schema() -> serde_json::Value {
+    let mut schema = builtin_schema().clone();
+    let registered = registered_schemas().lock();
+    if registered.is_empty() {
+        return schema;
+    }
+
+    if let Some(root) = schema.as_object_mut() {
+        let properties = root
+            .entry("properties")
+            .or_insert_with(|| serde_json::Value::Object(Default::default()));
+        if let Some(properties) = properties.as_object_mut() {
+            for (key, fragment) in registered.iter() {
+                properties.insert(key.clone(), fragment.clone());
+            }
+        }
+    }
+    schema
+}
+
+/// 使用内置 schema 及注册片段校验配置，返回全部违规
+pub fn validate_config(config: &serde_yaml::Value) -> Vec<SchemaViolation> {
+    validate_with_schema(config, &effective_schema())
+}
+
+/// 使用指定 schema 校验配置，返回全部违规
+pub fn validate_with_schema(
+    config: &serde_yaml::Value,
+    schema: &serde_json::Value,
+) -> Vec<SchemaViolation> {
+    let mut violations = Vec::new();
+    validate_node(config, schema, schema, "", &mut violations);
+    violations
+}
+
+/// 按 `Server.Validation.Mode` 校验配置
+///
+/// lenient 模式下违规输出到 stderr（不依赖 debug 开关）后返回 `Ok`，strict 模式下存在违规时返回错误。
+pub(crate) fn check_config(config: &serde_yaml::Value) -> Result<(), XOneError> {
+    let mode = validation_mode(config);
+    if mode == ValidationMode::Off {
+        return Ok(());
+    }
+
+    let violations = validate_config(config);
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    if mode == ValidationMode::Strict {
+        return Err(violations_error(&violations));
+    }
+    for v in &violations {
+        eprintln!(
+            "XOne config schema violation, path=[{}], err=[{}]",
+            v.path, v.message
+        );
+    }
+    Ok(())
+}
+
+/// 将违规列表转换为配置错误
+pub(crate) fn violations_error(violations: &[SchemaViolation]) -> XOneError {
+    let details = violations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ");
+    XOneError::Config(format!(
+        "config schema validation failed, violations=[{details}]"
+    ))
+}
+
+/// 查找 schema 节点 `properties` 中与 `segment` 对应的声明（先精确匹配，再忽略 ASCII 大小写），
+/// 返回声明的 key 及其 schema（已展开 `$ref`），供覆盖项创建缺失节点时使用
+pub(crate) fn property_schema<'a>(
+    schema: &'a serde_json::Value,
+    root: &'a serde_json::Value,
+    segment: &str,
+) -> Option<(&'a str, &'a serde_json::Value)> {
+    let properties = resolve_ref(schema, root, "", &mut Vec::new())?
+        .get("properties")?
+        .as_object()?;
+    let (key, child) = properties.get_key_value(segment).or_else(|| {
+        properties
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(segment))
+    })?;
+    Some((key.as_str(), resolve_ref(child, root, "", &mut Vec::new())?))
+}
+
+/// schema 节点的列表元素 schema（已展开 `$ref`）
+pub(crate) fn items_schema<'a>(
+    schema: &'a serde_json::Value,
+    root: &'a serde_json::Value,
+) -> Option<&'a serde_json::Value> {
+    let items = resolve_ref(schema, root, "", &mut Vec::new())?.get("items")?;
+    resolve_ref(items, root, "", &mut Vec::new())
+}
+
+/// schema 节点是否仅声明为 `string` 类型
+pub(crate) fn is_string_schema(schema: &serde_json::Value) -> bool {
+    schema
+        .get("type")
+        .is_some_and(|t| type_names(t) == ["string"])
+}
+
+// ---- 以下为私有实现 ----
+
+/// 读取校验模式，无法识别时按 lenient 处理
+fn validation_mode(config: &serde_yaml::Value) -> ValidationMode {
+    let c = accessor::lookup(config, VALIDATION_CONFIG_KEY)
+        .cloned()
+        .and_then(|v| serde_yaml::from_value::<ValidationConfig>(v).ok())
+        .unwrap_or_default();
+    ValidationMode::parse(&c.mode).unwrap_or_else(|| {
+        eprintln!(
+            "XOne config validation mode [{}] unknown, use lenient",
+            c.mode
+        );
+        ValidationMode::Lenient
+    })
+}
+
+/// 递归校验节点
+fn validate_node(
+    value: &serde_yaml::Value,
+    schema: &serde_json::Value,
+    root: &serde_json::Value,
+    path: &str,
+    violations: &mut Vec<SchemaViolation>,
+) {
+    let Some(schema) = resolve_ref(schema, root, path, violations) else {
+        return;
+    };
+    let Some(schema_map) = schema.as_object() else {
+        return;
+    };
+    let value = match value {
+        serde_yaml::Value::Tagged(tagged) => &tagged.value,
+        other => other,
+    };
+
+    for keyword in schema_map.keys() {
+        if !SUPPORTED_KEYWORDS.contains(&keyword.as_str()) {
+            push_violation(
+                violations,
+                path,
+                format!("unsupported schema keyword `{keyword}`"),
+            );
+        }
+    }
+
+    if let Some(expected) = schema_map.get("type") {
+        let expected = type_names(expected);
+        let actual = yaml_type_name(value);
+        let matched = expected
+            .iter()
+            .any(|t| *t == actual || (*t == "number" && actual == "integer"));
+        if !expected.is_empty() && !matched {
+            push_violation(
+                violations,
+                path,
+                format!("expected type [{}], got [{actual}]", expected.join("|")),
+            );
+            return;
+        }
+    }
+
+    if let Some(allowed) = schema_map.get("enum").and_then(|e| e.as_array())
+        && !allowed.iter().any(|a| json_eq_yaml(a, value))
+    {
+        let allowed = allowed
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        push_violation(violations, path, format!("value not in enum [{allowed}]"));
+    }
+
+    if let Some(actual) = value.as_f64() {
+        if let Some(minimum) = schema_map.get("minimum").and_then(|m| m.as_f64())
+            && actual < minimum
+        {
+            push_violation(
+                violations,
+                path,
+                format!("value [{actual}] is less than minimum [{minimum}]"),
+            );
+        }
+        if let Some(maximum) = schema_map.get("maximum").and_then(|m| m.as_f64())
+            && actual > maximum
+        {
+            push_violation(
+                violations,
+                path,
+                format!("value [{actual}] is greater than maximum [{maximum}]"),
+            );
+        }
+    }
+
+    match value {
+        serde_yaml::Value::Mapping(map) => {
+            validate_mapping(map, schema_map, root, path, violations);
+        }
+        serde_yaml::Value::Sequence(seq) => {
+            if let Some(items) = schema_map.get("items") {
+                for (i, item) in seq.iter().enumerate() {
+                    validate_node(
+                        item,
+                        items,
+                        root,
+                        &join_path(path, &i.to_string()),
+                        violations,
+                    );
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 校验 Mapping 的 `properties` / `additionalProperties` / `required`
+fn validate_mapping(
+    map: &serde_yaml::Mapping,
+    schema: &serde_json::Map<String, serde_json::Value>,
+    root: &serde_json::Value,
+    path: &str,
+    violations: &mut Vec<SchemaViolation>,
+) {
+    let properties = schema.get("properties").and_then(|p| p.as_object());
+    let additional = schema.get("additionalProperties");
+
+    for (k, v) in map {
+        let key = match k {
+            serde_yaml::Value::String(s) => s.clone(),
+            other => serde_yaml::to_string(other)
+                .map(|s| s.trim_end().to_string())
+                .unwrap_or_default(),
+        };
+        let child_path = join_path(path, &key);
+
+        if let Some(prop_schema) = properties.and_then(|p| p.get(&key)) {
+            validate_node(v, prop_schema, root, &child_path, violations);
+            continue;
+        }
+        match additional {
+            Some(serde_json::Value::Bool(false)) => {
+                push_violation(
+                    violations,
+                    &child_path,
+                    unknown_key_message(&key, path, properties),
+                );
+            }
+            Some(s @ serde_json::Value::Object(_)) => {
+                validate_node(v, s, root, &child_path, violations);
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(required) = schema.get("required").and_then(|r| r.as_array()) {
+        for key in required.iter().filter_map(|r| r.as_str()) {
+            if !map.contains_key(key) {
+                push_violation(
+                    violations,
+                    &join_path(path, key),
+                    "missing required key".to_string(),
+                );
+            }
+        }
+    }
+}
+
+/// 解析 `$ref`（仅支持文档内 `#/...` 引用），无法解析时记录违规并返回 `None`
+fn resolve_ref<'a>(
+    schema: &'a serde_json::Value,
+    root: &'a serde_json::Value,
+    path: &str,
+    violations: &mut Vec<SchemaViolation>,
+) -> Option<&'a serde_json::Value> {
+    let mut current = schema;
+    // 限制解析深度，避免循环引用
+    for _ in 0..16 {
+        let Some(reference) = current.get("$ref").and_then(|r| r.as_str()) else {
+            return Some(current);
+        };
+        match reference.strip_prefix('#').and_then(|p| root.pointer(p)) {
+            Some(target) => current = target,
+            None => {
+                push_violation(
+                    violations,
+                    path,
+                    format!("schema $ref [{reference}] cannot be resolved"),
+                );
+                return None;
+            }
+        }
+    }
+    push_violation(violations, path, "schema $ref nested too deep".to_string());
+    None
+}
+
+/// schema `type` 声明的类型名列表
+fn type_names(t: &serde_json::Value) -> Vec<&str> {
+    match t {
+        serde_json::Value::String(s) => vec![s.as_str()],
+        serde_json::Value::Array(arr) => arr.iter().filter_map(|v| v.as_str()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// YAML 节点对应的 JSON Schema 类型名
+fn yaml_type_name(value: &serde_yaml::Value) -> &'static str {
+    match value {
+        serde_yaml::Value::Null => "null",
+        serde_yaml::Value::Bool(_) => "boolean",
+        serde_yaml::Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        serde_yaml::Value::Number(_) => "number",
+        serde_yaml::Value::String(_) => "string",
+        serde_yaml::Value::Sequence(_) => "array",
+        serde_yaml::Value::Mapping(_) => "object",
+        serde_yaml::Value::Tagged(tagged) => yaml_type_name(&tagged.value),
+    }
+}
+
+/// 比较 JSON 值与 YAML 值是否相等
+fn json_eq_yaml(json: &serde_json::Value, yaml: &serde_yaml::Value) -> bool {
+    serde_yaml::to_value(json).is_ok_and(|v| v == *yaml)
+}
+
+/// 未知 key 的违规描述，存在相近的已知 key 时附带建议
+fn unknown_key_message(
+    key: &str,
+    parent: &str,
+    properties: Option<&serde_json::Map<String, serde_json::Value>>,
+) -> String {
+    let mut message = format!("unknown key `{key}`");
+    if !parent.is_empty() {
+        message.push_str(&format!(" in {}", display_path(parent)));
+    }
+    let known = properties
+        .into_iter()
+        .flat_map(|p| p.keys().map(String::as_str));
+    if let Some(suggestion) = suggest_key(key, known) {
+        message.push_str(&format!(", did you mean `{suggestion}`?"));
+    }
+    message
+}
+
+/// 在已知 key 中查找与 `key` 最接近的一个（忽略大小写的编辑距离不超过 key 长度的 1/3，至少为 2）
+fn suggest_key<'a>(key: &str, known: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let lower = key.to_ascii_lowercase();
+    let threshold = (key.chars().count() / 3).max(2);
+    known
+        .into_iter()
+        .map(|k| (edit_distance(&lower, &k.to_ascii_lowercase()), k))
+        .filter(|(d, _)| *d <= threshold)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, k)| k)
+}
+
+/// Levenshtein 编辑距离
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+    prev[b.len()]
+}
+
+/// 点分路径转换为展示形式，列表索引写作下标（如 `XOrm.0` -> `XOrm[0]`）
+fn display_path(path: &str) -> String {
+    let mut result = String::new();
+    for segment in path.split('.') {
+        if !result.is_empty() && segment.chars().all(|c| c.is_ascii_digit()) {
+            result.push_str(&format!("[{segment}]"));
+        } else {
+            if !result.is_empty() {
+                result.push('.');
+            }
+            result.push_str(segment);
+        }
+    }
+    result
+}
+
+fn join_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{parent}.{key}")
+    }
+}
+
+fn push_violation(violations: &mut Vec<SchemaViolation>, path: &str, message: String) {
+    violations.push(SchemaViolation {
+        path: path.to_string(),
+        message,
+    });
+}
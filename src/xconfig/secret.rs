@@ -0,0 +1,243 @@
+//! 配置密钥引用与加密值
+//!
+//! 配置加载完成后（覆盖项之后、schema 校验之前）解析配置树中的敏感值：
+//!
+//! - `${file:/run/secrets/db_password}`：替换为文件内容（去除末尾换行），可嵌入字符串中
+//! - `ENC(...)`：整个值为 AES-256-GCM 密文，使用环境变量 `XONE_CONFIG_SECRET_KEY`
+//!   （base64 编码的 32 字节密钥）解密，需要启用 `config-encrypt` feature
+//!
+//! `${file:...}` 只允许出现在本地配置（配置文件、本地配置源、覆盖项）中，
+//! 远程配置源（如 HTTP）返回的值包含文件引用时加载失败。
+//!
+//! ```yaml
+//! XOrm:
+//!   DSN: "postgres://app:${file:/run/secrets/db_password}@db:5432/app"
+//! XRedis:
+//!   Password: "ENC(q83vEjRWeJAAAAAB...)"
+//! ```
+//!
+//! 解析出的值所在路径会被记录，`--print-config` 及 debug 配置输出中脱敏显示。
+
+use crate::error::XOneError;
+#[cfg(feature = "config-encrypt")]
+use base64::Engine;
+#[cfg(feature = "config-encrypt")]
+use base64::engine::general_purpose::STANDARD as BASE64;
+use parking_lot::Mutex;
+use regex::Regex;
+#[cfg(feature = "config-encrypt")]
+use ring::aead;
+#[cfg(feature = "config-encrypt")]
+use ring::rand::{SecureRandom, SystemRandom};
+use std::sync::{LazyLock, OnceLock};
+
+/// 解密密钥环境变量（base64 编码的 32 字节 AES-256 密钥）
+pub const SECRET_KEY_ENV_KEY: &str = "XONE_CONFIG_SECRET_KEY";
+
+/// 脱敏后的显示值
+pub const REDACTED: &str = "***";
+
+/// 文件引用占位符正则
+static FILE_REF_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\$\{file:([^}]+)\}").expect("hard-coded regex is invalid"));
+
+/// AES-GCM nonce 长度
+#[cfg(feature = "config-encrypt")]
+const NONCE_LEN: usize = 12;
+
+fn secret_paths_store() -> &'static Mutex<Vec<String>> {
+    static INSTANCE: OnceLock<Mutex<Vec<String>>> = OnceLock::new();
+    INSTANCE.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+/// 解析配置树中的文件引用和加密值，返回被替换的配置路径
+///
+/// 引用的文件不存在、密文无法解密或缺少解密密钥时返回错误。
+pub fn resolve_secrets(config: &mut serde_yaml::Value) -> Result<Vec<String>, XOneError> {
+    let mut paths = Vec::new();
+    resolve_node(config, "", &mut paths)?;
+    Ok(paths)
+}
+
+/// 查找配置树中首个 `${file:...}` 引用所在的配置路径
+pub(crate) fn find_file_ref(value: &serde_yaml::Value) -> Option<String> {
+    find_file_ref_node(value, "")
+}
+
+/// 使用 base64 编码的密钥加密明文，返回 `ENC(...)` 形式的配置值
+#[cfg(feature = "config-encrypt")]
+pub fn encrypt_value(plain: &str, key: &str) -> Result<String, XOneError> {
+    let key = build_key(key)?;
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| XOneError::Config("generate nonce failed".to_string()))?;
+
+    let mut in_out = plain.as_bytes().to_vec();
+    key.seal_in_place_append_tag(
+        aead::Nonce::assume_unique_for_key(nonce),
+        aead::Aad::empty(),
+        &mut in_out,
+    )
+    .map_err(|_| XOneError::Config("encrypt config value failed".to_string()))?;
+
+    let mut payload = nonce.to_vec();
+    payload.extend_from_slice(&in_out);
+    Ok(format!("ENC({})", BASE64.encode(payload)))
+}
+
+/// 生成随机密钥（base64 编码），可用于 `XONE_CONFIG_SECRET_KEY`
+#[cfg(feature = "config-encrypt")]
+pub fn generate_key() -> Result<String, XOneError> {
+    let mut key = [0u8; 32];
+    SystemRandom::new()
+        .fill(&mut key)
+        .map_err(|_| XOneError::Config("generate secret key failed".to_string()))?;
+    Ok(BASE64.encode(key))
+}
+
+/// 最近一次加载配置时解析出敏感值的配置路径
+pub(crate) fn secret_paths() -> Vec<String> {
+    secret_paths_store().lock().clone()
+}
+
+/// 记录解析出敏感值的配置路径
+pub(crate) fn set_secret_paths(paths: Vec<String>) {
+    *secret_paths_store().lock() = paths;
+}
+
+// ---- 以下为私有实现 ----
+
+fn resolve_node(
+    value: &mut serde_yaml::Value,
+    path: &str,
+    paths: &mut Vec<String>,
+) -> Result<(), XOneError> {
+    match value {
+        serde_yaml::Value::String(s) => {
+            if let Some(resolved) = resolve_string(s, path)? {
+                *s = resolved;
+                paths.push(path.to_string());
+            }
+        }
+        serde_yaml::Value::Mapping(map) => {
+            for (k, v) in map.iter_mut() {
+                let key = k.as_str().map(str::to_string).unwrap_or_default();
+                resolve_node(v, &join_path(path, &key), paths)?;
+            }
+        }
+        serde_yaml::Value::Sequence(seq) => {
+            for (i, v) in seq.iter_mut().enumerate() {
+                resolve_node(v, &join_path(path, &i.to_string()), paths)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn find_file_ref_node(value: &serde_yaml::Value, path: &str) -> Option<String> {
+    match value {
+        serde_yaml::Value::String(s) => FILE_REF_REGEX.is_match(s).then(|| path.to_string()),
+        serde_yaml::Value::Mapping(map) => map.iter().find_map(|(k, v)| {
+            let key = k.as_str().unwrap_or_default();
+            find_file_ref_node(v, &join_path(path, key))
+        }),
+        serde_yaml::Value::Sequence(seq) => seq
+            .iter()
+            .enumerate()
+            .find_map(|(i, v)| find_file_ref_node(v, &join_path(path, &i.to_string()))),
+        _ => None,
+    }
+}
+
+/// 解析单个字符串，无引用时返回 `None`
+fn resolve_string(s: &str, path: &str) -> Result<Option<String>, XOneError> {
+    if let Some(cipher) = s
+        .trim()
+        .strip_prefix("ENC(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        return decrypt_value(cipher)
+            .map(Some)
+            .map_err(|e| XOneError::Config(format!("decrypt config [{path}] failed, err=[{e}]")));
+    }
+
+    if !FILE_REF_REGEX.is_match(s) {
+        return Ok(None);
+    }
+    let mut error = None;
+    let resolved = FILE_REF_REGEX.replace_all(s, |caps: &regex::Captures| {
+        let file = caps[1].trim();
+        match std::fs::read_to_string(file) {
+            Ok(content) => content.trim_end_matches(['\r', '\n']).to_string(),
+            Err(e) => {
+                error.get_or_insert_with(|| {
+                    XOneError::Config(format!(
+                        "resolve config [{path}] failed, read secret file [{file}] err=[{e}]"
+                    ))
+                });
+                String::new()
+            }
+        }
+    });
+    match error {
+        Some(e) => Err(e),
+        None => Ok(Some(resolved.into_owned())),
+    }
+}
+
+/// 使用环境变量中的密钥解密 base64 密文
+#[cfg(feature = "config-encrypt")]
+fn decrypt_value(cipher: &str) -> Result<String, String> {
+    let key = std::env::var(SECRET_KEY_ENV_KEY)
+        .ok()
+        .filter(|k| !k.is_empty())
+        .ok_or_else(|| format!("env [{SECRET_KEY_ENV_KEY}] not set"))?;
+    let key = build_key(&key).map_err(|e| e.to_string())?;
+
+    let payload = BASE64
+        .decode(cipher.trim())
+        .map_err(|e| format!("invalid base64 ciphertext: {e}"))?;
+    if payload.len() < NONCE_LEN + aead::AES_256_GCM.tag_len() {
+        return Err("ciphertext too short".to_string());
+    }
+    let (nonce, sealed) = payload.split_at(NONCE_LEN);
+    let nonce =
+        aead::Nonce::try_assume_unique_for_key(nonce).map_err(|_| "invalid nonce".to_string())?;
+
+    let mut in_out = sealed.to_vec();
+    let plain = key
+        .open_in_place(nonce, aead::Aad::empty(), &mut in_out)
+        .map_err(|_| "authentication failed, wrong key or corrupted ciphertext".to_string())?;
+    String::from_utf8(plain.to_vec()).map_err(|e| format!("plaintext is not utf-8: {e}"))
+}
+
+/// 未启用 `config-encrypt` 时无法解密
+#[cfg(not(feature = "config-encrypt"))]
+fn decrypt_value(_cipher: &str) -> Result<String, String> {
+    Err("ENC(...) value requires feature `config-encrypt`".to_string())
+}
+
+/// 从 base64 编码的密钥构建 AES-256-GCM 密钥
+#[cfg(feature = "config-encrypt")]
+fn build_key(key: &str) -> Result<aead::LessSafeKey, XOneError> {
+    let bytes = BASE64
+        .decode(key.trim())
+        .map_err(|e| XOneError::Config(format!("invalid secret key, base64 decode err=[{e}]")))?;
+    let unbound = aead::UnboundKey::new(&aead::AES_256_GCM, &bytes).map_err(|_| {
+        XOneError::Config(format!(
+            "invalid secret key, expect 32 bytes, got {}",
+            bytes.len()
+        ))
+    })?;
+    Ok(aead::LessSafeKey::new(unbound))
+}
+
+fn join_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{parent}.{key}")
+    }
+}
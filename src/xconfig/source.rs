@@ -0,0 +1,612 @@
+//! 可插拔配置源
+//!
+//! 本地配置文件（含 profile 合并）之后，按顺序叠加额外配置源的配置树，
+//! 后加载的源覆盖先加载的源。优先级从低到高：
+//!
+//! 1. 本地配置文件（基础文件 + profile 文件）
+//! 2. `Server.Sources` 中声明的配置源（按声明顺序）
+//! 3. 通过 [`add_source`] 注册的配置源（按注册顺序）
+//!
+//! ```yaml
+//! Server:
+//!   Sources:
+//!     - Type: "dir"
+//!       Path: "/run/secrets"
+//!     - Type: "http"
+//!       Url: "http://config-center/api/kv/my-app"
+//!       Headers:
+//!         Authorization: "Bearer ${CONFIG_TOKEN}"
+//!       Optional: true
+//! ```
+//!
+//! ```ignore
+//! use x_one::xconfig::source::{add_source, EnvSource};
+//!
+//! // 在 x_one::init() 之前注册
+//! add_source(EnvSource::new("APP_"));
+//! ```
+
+use super::config::SourceConfig;
+use super::format::ConfigFormat;
+use super::init;
+use super::secret;
+use crate::error::XOneError;
+use crate::xutil;
+use parking_lot::Mutex;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+#[cfg(feature = "http")]
+use std::time::Duration;
+
+/// Server.Sources 配置路径
+const SOURCES_CONFIG_KEY: &str = "Server.Sources";
+
+/// 环境变量层级分隔符
+const ENV_PATH_SEPARATOR: &str = "__";
+
+/// HTTP 配置源默认超时（5 秒）
+#[cfg(feature = "http")]
+const DEFAULT_HTTP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 配置源
+///
+/// 返回的配置树会与已加载的配置深度合并：Mapping 按 key 递归合并，
+/// 其他类型（标量、列表）整体覆盖。
+pub trait ConfigSource: Send + Sync {
+    /// 配置源名称（用于日志和错误信息）
+    fn name(&self) -> String;
+
+    /// 加载配置树，配置源不存在且允许缺失时返回 `Ok(None)`
+    fn load(&self) -> Result<Option<serde_yaml::Value>, XOneError>;
+
+    /// 是否为本地配置源（本地文件、目录、环境变量等，默认 false）
+    ///
+    /// 只有本地配置源的值可以使用 `${file:...}` 引用本地文件，
+    /// 非本地配置源返回的值包含文件引用时加载失败。
+    fn is_local(&self) -> bool {
+        false
+    }
+
+    /// 加载配置树，同时返回其中敏感值的点分配置路径（默认无敏感值）
+    ///
+    /// 返回的路径会在 `--print-config` 及 debug 配置输出中脱敏显示。
+    fn load_with_secrets(&self) -> Result<Option<(serde_yaml::Value, Vec<String>)>, XOneError> {
+        Ok(self.load()?.map(|value| (value, Vec::new())))
+    }
+}
+
+fn registered_sources() -> &'static Mutex<Vec<Arc<dyn ConfigSource>>> {
+    static INSTANCE: OnceLock<Mutex<Vec<Arc<dyn ConfigSource>>>> = OnceLock::new();
+    INSTANCE.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+/// 注册配置源（需在 `x_one::init()` 之前调用）
+///
+/// 注册的配置源优先级高于本地配置文件和 `Server.Sources` 声明的配置源。
+pub fn add_source(source: impl ConfigSource + 'static) {
+    registered_sources().lock().push(Arc::new(source));
+}
+
+/// 清空已注册的配置源（仅测试用）
+#[doc(hidden)]
+pub fn reset_sources() {
+    registered_sources().lock().clear();
+}
+
+/// 按顺序加载配置源并深度合并到 `base` 之上
+pub fn load_sources(
+    base: serde_yaml::Value,
+    sources: &[Arc<dyn ConfigSource>],
+) -> Result<serde_yaml::Value, XOneError> {
+    load_sources_with_secrets(base, sources).map(|(config, _)| config)
+}
+
+/// 按顺序加载配置源并深度合并到 `base` 之上，同时返回配置源声明的敏感值路径
+///
+/// 合并时 key 忽略大小写匹配已有 key，因此路径按合并后配置树中的实际 key 返回。
+pub(crate) fn load_sources_with_secrets(
+    mut base: serde_yaml::Value,
+    sources: &[Arc<dyn ConfigSource>],
+) -> Result<(serde_yaml::Value, Vec<String>), XOneError> {
+    let mut keys = Vec::new();
+    for source in sources {
+        match source.load_with_secrets()? {
+            Some((overlay, secret_keys)) => {
+                // 非本地配置源不允许读取本地文件
+                let file_ref = (!source.is_local())
+                    .then(|| secret::find_file_ref(&overlay))
+                    .flatten();
+                if let Some(path) = file_ref {
+                    return Err(XOneError::Config(format!(
+                        "config source [{}] is not local, ${{file:...}} reference is not allowed, path=[{path}]",
+                        source.name()
+                    )));
+                }
+                xutil::info_if_enable_debug(&format!(
+                    "XOne config source [{}] loaded",
+                    source.name()
+                ));
+                base = init::deep_merge_config(base, overlay);
+                keys.extend(secret_keys);
+            }
+            None => {
+                xutil::warn_if_enable_debug(&format!(
+                    "XOne config source [{}] not found, ignore",
+                    source.name()
+                ));
+            }
+        }
+    }
+    let paths = keys
+        .iter()
+        .filter_map(|key| resolve_key_path(&base, key))
+        .collect();
+    Ok((base, paths))
+}
+
+/// 根据声明构建配置源
+///
+/// `base_dir` 为主配置文件所在目录，用于解析相对路径。
+pub fn build_source(c: &SourceConfig, base_dir: &Path) -> Result<Arc<dyn ConfigSource>, XOneError> {
+    let source: Arc<dyn ConfigSource> = match c.source_type.to_ascii_lowercase().as_str() {
+        "file" => Arc::new(FileSource::new(resolve_path(base_dir, &c.path)).optional(c.optional)),
+        "dir" => {
+            Arc::new(DirectorySource::new(resolve_path(base_dir, &c.path)).optional(c.optional))
+        }
+        "env" => Arc::new(EnvSource::new(&c.prefix)),
+        #[cfg(feature = "http")]
+        "http" => {
+            let mut source = HttpSource::new(&c.url)
+                .timeout(c.timeout)
+                .optional(c.optional);
+            for (k, v) in &c.headers {
+                source = source.header(k, v);
+            }
+            Arc::new(source)
+        }
+        #[cfg(not(feature = "http"))]
+        "http" => {
+            return Err(XOneError::Config(
+                "config source type [http] requires feature `http`".to_string(),
+            ));
+        }
+        other => {
+            return Err(XOneError::Config(format!(
+                "unknown config source type [{other}], expect one of file/dir/env/http"
+            )));
+        }
+    };
+    Ok(source)
+}
+
+/// 叠加 `config` 中 `Server.Sources` 声明的配置源及代码注册的配置源
+///
+/// 返回合并后的配置树及配置源中敏感值的配置路径。
+pub(crate) fn apply_sources(
+    config: serde_yaml::Value,
+    config_location: Option<&str>,
+) -> Result<(serde_yaml::Value, Vec<String>), XOneError> {
+    let base_dir = config_location
+        .and_then(|loc| Path::new(loc).parent())
+        .unwrap_or_else(|| Path::new(""))
+        .to_path_buf();
+
+    let declared = super::accessor::lookup(&config, SOURCES_CONFIG_KEY)
+        .cloned()
+        .map(serde_yaml::from_value::<Vec<SourceConfig>>)
+        .transpose()
+        .map_err(|e| {
+            XOneError::Config(format!(
+                "parse config [{SOURCES_CONFIG_KEY}] failed, err=[{e}]"
+            ))
+        })?
+        .unwrap_or_default();
+
+    let mut sources = declared
+        .iter()
+        .map(|c| build_source(c, &base_dir))
+        .collect::<Result<Vec<_>, _>>()?;
+    sources.extend(registered_sources().lock().iter().cloned());
+
+    load_sources_with_secrets(config, &sources)
+}
+
+/// 是否存在代码注册的配置源
+pub(crate) fn has_registered_sources() -> bool {
+    !registered_sources().lock().is_empty()
+}
+
+// ---------------------------------------------------------------------------
+// 文件配置源
+// ---------------------------------------------------------------------------
+
+/// 配置文件配置源（按扩展名解析 YAML / JSON / TOML）
+pub struct FileSource {
+    path: PathBuf,
+    optional: bool,
+}
+
+impl FileSource {
+    /// 创建文件配置源（默认文件必须存在）
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            optional: false,
+        }
+    }
+
+    /// 设置文件不存在时是否忽略
+    pub fn optional(mut self, optional: bool) -> Self {
+        self.optional = optional;
+        self
+    }
+}
+
+impl ConfigSource for FileSource {
+    fn name(&self) -> String {
+        format!("file:{}", self.path.display())
+    }
+
+    fn load(&self) -> Result<Option<serde_yaml::Value>, XOneError> {
+        if self.optional && !xutil::file_exist(&self.path) {
+            return Ok(None);
+        }
+        init::load_local_config(&self.path.to_string_lossy()).map(Some)
+    }
+
+    fn is_local(&self) -> bool {
+        true
+    }
+}
+
+// ---------------------------------------------------------------------------
+// 目录配置源
+// ---------------------------------------------------------------------------
+
+/// 目录配置源（适用于挂载的 secret 目录）
+///
+/// 目录下每个普通文件：
+/// - 扩展名为 `.yml` / `.yaml` / `.toml` / `.json` 时按配置树解析并合并
+/// - 其他文件以文件名为点分 key（如 `XOrm.DSN`），去除末尾换行后的内容为字符串值，
+///   视为敏感值，在 `--print-config` 及 debug 配置输出中脱敏显示
+///
+/// 以 `.` 开头的文件（如 Kubernetes 的 `..data`）被忽略，文件按名称顺序加载。
+pub struct DirectorySource {
+    path: PathBuf,
+    optional: bool,
+}
+
+impl DirectorySource {
+    /// 创建目录配置源（默认目录必须存在）
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            optional: false,
+        }
+    }
+
+    /// 设置目录不存在时是否忽略
+    pub fn optional(mut self, optional: bool) -> Self {
+        self.optional = optional;
+        self
+    }
+}
+
+impl ConfigSource for DirectorySource {
+    fn name(&self) -> String {
+        format!("dir:{}", self.path.display())
+    }
+
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    fn load(&self) -> Result<Option<serde_yaml::Value>, XOneError> {
+        Ok(self.load_with_secrets()?.map(|(value, _)| value))
+    }
+
+    /// 非配置文件的内容视为敏感值，返回其文件名对应的配置路径
+    fn load_with_secrets(&self) -> Result<Option<(serde_yaml::Value, Vec<String>)>, XOneError> {
+        if !xutil::dir_exist(&self.path) {
+            if self.optional {
+                return Ok(None);
+            }
+            return Err(XOneError::Config(format!(
+                "config source directory not found, path=[{}]",
+                self.path.display()
+            )));
+        }
+
+        let mut entries = std::fs::read_dir(&self.path)
+            .map_err(|e| {
+                XOneError::Config(format!(
+                    "read config source directory failed, path=[{}], err=[{e}]",
+                    self.path.display()
+                ))
+            })?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| !n.starts_with('.'))
+            })
+            .collect::<Vec<_>>();
+        entries.sort();
+
+        let mut result = serde_yaml::Value::Mapping(serde_yaml::Mapping::new());
+        let mut secret_keys = Vec::new();
+        for path in entries {
+            let (overlay, secret_key) = load_directory_entry(&path)?;
+            result = init::deep_merge_config(result, overlay);
+            secret_keys.extend(secret_key);
+        }
+        Ok(Some((result, secret_keys)))
+    }
+}
+
+/// 加载目录中的单个文件，非配置文件同时返回其点分 key
+fn load_directory_entry(path: &Path) -> Result<(serde_yaml::Value, Option<String>), XOneError> {
+    if ConfigFormat::is_config_file(path) {
+        return init::load_local_config(&path.to_string_lossy()).map(|value| (value, None));
+    }
+
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        XOneError::Config(format!(
+            "read config source file failed, path=[{}], err=[{e}]",
+            path.display()
+        ))
+    })?;
+    let key = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    let value = content.trim_end_matches(['\r', '\n']).to_string();
+    Ok((
+        nest_value(
+            &key.split('.').collect::<Vec<_>>(),
+            serde_yaml::Value::String(value),
+        ),
+        Some(key.to_string()),
+    ))
+}
+
+// ---------------------------------------------------------------------------
+// 环境变量配置源
+// ---------------------------------------------------------------------------
+
+/// 环境变量配置源
+///
+/// 读取以 `prefix` 开头的环境变量，去掉前缀后以 `__` 分隔层级，值为字符串。
+/// 例如 `APP_XOrm__DSN=...` → `XOrm.DSN`。
+/// 合并时 key 先精确匹配，找不到再忽略 ASCII 大小写匹配已有 key，
+/// 因此 `APP_XORM__DSN` 同样会覆盖 `XOrm.DSN`。
+pub struct EnvSource {
+    prefix: String,
+}
+
+impl EnvSource {
+    /// 创建环境变量配置源
+    pub fn new(prefix: &str) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+        }
+    }
+}
+
+impl ConfigSource for EnvSource {
+    fn name(&self) -> String {
+        format!("env:{}", self.prefix)
+    }
+
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    fn load(&self) -> Result<Option<serde_yaml::Value>, XOneError> {
+        let mut vars = std::env::vars()
+            .filter_map(|(k, v)| {
+                let path = k.strip_prefix(&self.prefix)?;
+                (!path.is_empty()).then(|| (path.to_string(), v))
+            })
+            .collect::<Vec<_>>();
+        // 按 key 排序，保证合并结果稳定
+        vars.sort();
+
+        let mut result = serde_yaml::Value::Mapping(serde_yaml::Mapping::new());
+        for (path, value) in vars {
+            let overlay = nest_value(
+                &path.split(ENV_PATH_SEPARATOR).collect::<Vec<_>>(),
+                serde_yaml::Value::String(value),
+            );
+            result = init::deep_merge_config(result, overlay);
+        }
+        Ok(Some(result))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTTP KV 配置源
+// ---------------------------------------------------------------------------
+
+/// HTTP KV 配置源
+///
+/// 对 `url` 发起 GET 请求，响应体按 YAML（兼容 JSON）解析。
+/// 响应中包含 `.` 的 key 会展开为层级结构，因此扁平的 KV 响应
+/// `{"XOrm.DSN": "..."}` 与嵌套结构等价。
+///
+/// 请求在独立线程的 tokio 运行时中执行，可在同步或异步上下文中调用。
+#[cfg(feature = "http")]
+pub struct HttpSource {
+    url: String,
+    headers: Vec<(String, String)>,
+    timeout: Duration,
+    optional: bool,
+}
+
+#[cfg(feature = "http")]
+impl HttpSource {
+    /// 创建 HTTP 配置源（默认超时 5 秒，失败时启动失败）
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            headers: Vec::new(),
+            timeout: DEFAULT_HTTP_TIMEOUT,
+            optional: false,
+        }
+    }
+
+    /// 添加请求头
+    pub fn header(mut self, key: &str, value: &str) -> Self {
+        self.headers.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// 设置请求超时
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// 设置请求失败时是否忽略
+    pub fn optional(mut self, optional: bool) -> Self {
+        self.optional = optional;
+        self
+    }
+
+    /// 发起请求并返回响应体
+    fn fetch(&self) -> Result<String, XOneError> {
+        let url = self.url.clone();
+        let headers = self.headers.clone();
+        let timeout = self.timeout;
+
+        let handle = std::thread::spawn(move || -> Result<String, String> {
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| e.to_string())?;
+            rt.block_on(async move {
+                let client = reqwest::Client::builder()
+                    .timeout(timeout)
+                    .build()
+                    .map_err(|e| e.to_string())?;
+                let mut req = client.get(&url);
+                for (k, v) in &headers {
+                    req = req.header(k, v);
+                }
+                let resp = req.send().await.map_err(|e| e.to_string())?;
+                let status = resp.status();
+                if !status.is_success() {
+                    return Err(format!("unexpected status [{status}]"));
+                }
+                resp.text().await.map_err(|e| e.to_string())
+            })
+        });
+
+        handle
+            .join()
+            .map_err(|_| "request thread panicked".to_string())
+            .and_then(|r| r)
+            .map_err(|e| {
+                XOneError::Config(format!(
+                    "fetch config source failed, url=[{}], err=[{e}]",
+                    self.url
+                ))
+            })
+    }
+}
+
+#[cfg(feature = "http")]
+impl ConfigSource for HttpSource {
+    fn name(&self) -> String {
+        format!("http:{}", self.url)
+    }
+
+    fn load(&self) -> Result<Option<serde_yaml::Value>, XOneError> {
+        let body = match self.fetch() {
+            Ok(body) => body,
+            Err(e) if self.optional => {
+                xutil::warn_if_enable_debug(&format!("{e}, optional source ignored"));
+                return Ok(None);
+            }
+            Err(e) => return Err(e),
+        };
+
+        let value: serde_yaml::Value = serde_yaml::from_str(&body).map_err(|e| {
+            XOneError::Config(format!(
+                "parse config source failed, url=[{}], err=[{e}]",
+                self.url
+            ))
+        })?;
+        if value.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(expand_dotted_keys(value)))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// 以下为私有实现
+// ---------------------------------------------------------------------------
+
+/// 相对路径基于主配置文件目录解析
+fn resolve_path(base_dir: &Path, path: &str) -> PathBuf {
+    let p = Path::new(path);
+    if p.is_absolute() {
+        p.to_path_buf()
+    } else {
+        base_dir.join(p)
+    }
+}
+
+/// 按合并后配置树中的实际 key 解析点分路径（key 精确匹配，找不到再忽略 ASCII 大小写）
+fn resolve_key_path(config: &serde_yaml::Value, key: &str) -> Option<String> {
+    let mut node = config;
+    let mut segments = Vec::new();
+    for segment in key.split('.') {
+        let map = node.as_mapping()?;
+        let (k, v) = map
+            .iter()
+            .find(|(k, _)| k.as_str() == Some(segment))
+            .or_else(|| {
+                map.iter()
+                    .find(|(k, _)| k.as_str().is_some_and(|s| s.eq_ignore_ascii_case(segment)))
+            })?;
+        segments.push(k.as_str()?.to_string());
+        node = v;
+    }
+    Some(segments.join("."))
+}
+
+/// 将 key 路径与值构造为嵌套 Mapping
+fn nest_value(path: &[&str], value: serde_yaml::Value) -> serde_yaml::Value {
+    path.iter().rev().fold(value, |acc, key| {
+        let mut map = serde_yaml::Mapping::new();
+        map.insert(serde_yaml::Value::String(key.to_string()), acc);
+        serde_yaml::Value::Mapping(map)
+    })
+}
+
+/// 将 Mapping 中包含 `.` 的 key 展开为层级结构
+#[cfg_attr(not(feature = "http"), allow(dead_code))]
+fn expand_dotted_keys(value: serde_yaml::Value) -> serde_yaml::Value {
+    let serde_yaml::Value::Mapping(map) = value else {
+        return value;
+    };
+
+    let mut result = serde_yaml::Value::Mapping(serde_yaml::Mapping::new());
+    for (k, v) in map {
+        let v = expand_dotted_keys(v);
+        let overlay = match k.as_str() {
+            Some(key) if key.contains('.') => nest_value(&key.split('.').collect::<Vec<_>>(), v),
+            _ => {
+                let mut m = serde_yaml::Mapping::new();
+                m.insert(k, v);
+                serde_yaml::Value::Mapping(m)
+            }
+        };
+        result = init::deep_merge_config(result, overlay);
+    }
+    result
+}
@@ -0,0 +1,338 @@
+//! 配置热更新
+//!
+//! 轮询监听基础配置文件、激活的 profile 文件及导入的文件，文件变更后重新执行
+//! 环境变量展开与 profile 合并，原子替换全局配置，并通知订阅了对应 key 的回调。
+//! 变更后的配置无法解析时拒绝本次更新，保留旧配置。
+//!
+//! ```yaml
+//! Server:
+//!   Reload:
+//!     Enable: true
+//!     Interval: "2s"
+//! ```
+//!
+//! ```ignore
+//! // 回调方式：XAxum 子树变化时触发
+//! x_one::xconfig::subscribe("XAxum", |change| {
+//!     println!("XAxum changed: {:?} -> {:?}", change.old, change.new);
+//! });
+//!
+//! // watch channel 方式
+//! let mut rx = x_one::xconfig::watch("XLog.Level");
+//! while rx.changed().await.is_ok() {
+//!     let level = rx.borrow().clone();
+//! }
+//! ```
+
+use super::config::ReloadConfig;
+use super::{accessor, config_store, import, init, profiles};
+use crate::error::XOneError;
+use crate::xutil;
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock, mpsc};
+use std::time::{Duration, SystemTime};
+
+/// 热更新配置路径
+const RELOAD_CONFIG_KEY: &str = "Server.Reload";
+
+/// 配置变更事件
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigChange {
+    /// 订阅的 key（点分路径，空字符串表示整棵配置树）
+    pub key: String,
+    /// 变更前的值（不存在时为 `None`）
+    pub old: Option<serde_yaml::Value>,
+    /// 变更后的值（不存在时为 `None`）
+    pub new: Option<serde_yaml::Value>,
+}
+
+/// 订阅 ID，用于取消订阅
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(u64);
+
+/// 变更回调类型
+type ChangeCallback = Arc<dyn Fn(&ConfigChange) + Send + Sync + 'static>;
+
+/// 变更通知方式
+#[derive(Clone)]
+enum Notifier {
+    Callback(ChangeCallback),
+    Channel(Arc<tokio::sync::watch::Sender<Option<serde_yaml::Value>>>),
+}
+
+/// 单个订阅
+#[derive(Clone)]
+struct Subscription {
+    id: SubscriptionId,
+    key: String,
+    notifier: Notifier,
+}
+
+/// 后台监听线程句柄
+struct WatcherHandle {
+    stop_tx: mpsc::Sender<()>,
+    thread: std::thread::JoinHandle<()>,
+}
+
+/// 订阅 ID 生成器
+static NEXT_SUBSCRIPTION_ID: AtomicU64 = AtomicU64::new(1);
+
+fn subscriptions() -> &'static Mutex<Vec<Subscription>> {
+    static INSTANCE: OnceLock<Mutex<Vec<Subscription>>> = OnceLock::new();
+    INSTANCE.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+fn location_store() -> &'static Mutex<Option<String>> {
+    static INSTANCE: OnceLock<Mutex<Option<String>>> = OnceLock::new();
+    INSTANCE.get_or_init(|| Mutex::new(None))
+}
+
+fn watcher_store() -> &'static Mutex<Option<WatcherHandle>> {
+    static INSTANCE: OnceLock<Mutex<Option<WatcherHandle>>> = OnceLock::new();
+    INSTANCE.get_or_init(|| Mutex::new(None))
+}
+
+/// 串行化 reload，保证通知顺序与配置替换顺序一致
+fn reload_lock() -> &'static Mutex<()> {
+    static INSTANCE: OnceLock<Mutex<()>> = OnceLock::new();
+    INSTANCE.get_or_init(|| Mutex::new(()))
+}
+
+/// 订阅指定 key 的配置变更
+///
+/// 热更新后该 key 对应子树发生变化（新增、删除或修改）时调用回调。
+/// 回调在监听线程中同步执行，panic 会被捕获并记录。
+pub fn subscribe<F>(key: &str, f: F) -> SubscriptionId
+where
+    F: Fn(&ConfigChange) + Send + Sync + 'static,
+{
+    add_subscription(key, Notifier::Callback(Arc::new(f)))
+}
+
+/// 取消订阅，返回订阅是否存在
+pub fn unsubscribe(id: SubscriptionId) -> bool {
+    let mut subs = subscriptions().lock();
+    let before = subs.len();
+    subs.retain(|s| s.id != id);
+    subs.len() != before
+}
+
+/// 以 watch channel 形式订阅指定 key 的配置值
+///
+/// Receiver 初始值为当前配置值，热更新后该 key 变化时推送新值。
+/// Receiver 全部 drop 后订阅自动清理。
+pub fn watch(key: &str) -> tokio::sync::watch::Receiver<Option<serde_yaml::Value>> {
+    let (tx, rx) = tokio::sync::watch::channel(accessor::get_value(key));
+    add_subscription(key, Notifier::Channel(Arc::new(tx)));
+    rx
+}
+
+/// 立即从配置文件重新加载配置
+///
+/// 返回配置是否发生变化。配置文件无法解析时返回错误并保留旧配置。
+pub fn reload() -> Result<bool, XOneError> {
+    let location = location_store().lock().clone().ok_or_else(|| {
+        XOneError::Config("reload config failed, config location unknown".to_string())
+    })?;
+
+    let _guard = reload_lock().lock();
+    let loaded = init::reload_xconfig(&location)?;
+    Ok(apply_config(loaded))
+}
+
+/// 启动配置文件监听
+///
+/// 每隔 `interval` 检查一次配置文件（基础文件、激活的 profile 文件及导入的文件）的修改时间和大小，
+/// 发生变化时触发 [`reload`]。重复调用会先停止已有的监听线程。
+pub fn start_watch(config_location: &str, interval: Duration) -> Result<(), XOneError> {
+    stop_watch()?;
+    *location_store().lock() = Some(config_location.to_string());
+
+    // 在启动线程前记录文件签名，保证调用返回后的修改一定能被检测到
+    let files = watched_files(config_location);
+    let signatures = file_signatures(&files);
+
+    let (stop_tx, stop_rx) = mpsc::channel();
+    let location = config_location.to_string();
+    let thread = std::thread::Builder::new()
+        .name("xconfig-watcher".to_string())
+        .spawn(move || watch_loop(&location, interval, &stop_rx, files, signatures))
+        .map_err(|e| XOneError::Config(format!("start config watcher failed, err=[{e}]")))?;
+
+    *watcher_store().lock() = Some(WatcherHandle { stop_tx, thread });
+
+    xutil::info_if_enable_debug(&format!(
+        "XOne config watcher started, location=[{config_location}], interval=[{interval:?}]"
+    ));
+    Ok(())
+}
+
+/// 停止配置文件监听
+pub fn stop_watch() -> Result<(), XOneError> {
+    let handle = watcher_store().lock().take();
+    if let Some(handle) = handle {
+        let _ = handle.stop_tx.send(());
+        handle
+            .thread
+            .join()
+            .map_err(|_| XOneError::Config("config watcher thread panicked".to_string()))?;
+        xutil::info_if_enable_debug("XOne config watcher stopped");
+    }
+    Ok(())
+}
+
+/// 记录配置文件路径，并在 `Server.Reload.Enable` 为 true 时启动监听
+pub(crate) fn start_watch_if_enabled(config_location: &str) -> Result<(), XOneError> {
+    *location_store().lock() = Some(config_location.to_string());
+
+    let c = accessor::try_get::<ReloadConfig>(RELOAD_CONFIG_KEY)?.unwrap_or_default();
+    if !c.enable {
+        return Ok(());
+    }
+
+    start_watch(config_location, c.interval)
+}
+
+// ---- 以下为私有实现 ----
+
+fn add_subscription(key: &str, notifier: Notifier) -> SubscriptionId {
+    let id = SubscriptionId(NEXT_SUBSCRIPTION_ID.fetch_add(1, Ordering::Relaxed));
+    subscriptions().lock().push(Subscription {
+        id,
+        key: key.to_string(),
+        notifier,
+    });
+    id
+}
+
+/// 提交加载元信息，原子替换全局配置并通知订阅者，返回配置是否发生变化
+fn apply_config(loaded: init::LoadedConfig) -> bool {
+    loaded.commit();
+    let config = loaded.config;
+    let old = {
+        let mut store = config_store().write();
+        if store.as_ref() == Some(&config) {
+            return false;
+        }
+        store.replace(config.clone())
+    };
+
+    let old = old.unwrap_or(serde_yaml::Value::Null);
+    notify_subscribers(&old, &config);
+    true
+}
+
+/// 逐个通知 key 子树发生变化的订阅者
+///
+/// 先在锁内复制订阅列表，回调在锁外执行，允许回调中再次订阅或取消订阅。
+fn notify_subscribers(old: &serde_yaml::Value, new: &serde_yaml::Value) {
+    let subs = {
+        let mut subs = subscriptions().lock();
+        subs.retain(|s| match &s.notifier {
+            Notifier::Channel(tx) => !tx.is_closed(),
+            Notifier::Callback(_) => true,
+        });
+        subs.clone()
+    };
+
+    for sub in subs {
+        let old_value = accessor::lookup(old, &sub.key);
+        let new_value = accessor::lookup(new, &sub.key);
+        if old_value == new_value {
+            continue;
+        }
+
+        match sub.notifier {
+            Notifier::Channel(tx) => {
+                tx.send_replace(new_value.cloned());
+            }
+            Notifier::Callback(f) => {
+                let change = ConfigChange {
+                    key: sub.key.clone(),
+                    old: old_value.cloned(),
+                    new: new_value.cloned(),
+                };
+                let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f(&change)));
+                if let Err(e) = result {
+                    xutil::error_if_enable_debug(&format!(
+                        "XOne config change callback panicked, key=[{}], err=[{}]",
+                        sub.key,
+                        xutil::extract_panic_message(e)
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// 监听线程主循环
+fn watch_loop(
+    location: &str,
+    interval: Duration,
+    stop_rx: &mpsc::Receiver<()>,
+    mut files: Vec<String>,
+    mut signatures: Vec<FileSignature>,
+) {
+    // 收到停止信号或 sender 被 drop 时退出
+    while let Err(mpsc::RecvTimeoutError::Timeout) = stop_rx.recv_timeout(interval) {
+        let current = file_signatures(&files);
+        if current == signatures {
+            continue;
+        }
+        // 无论 reload 成功与否都更新签名，避免对同一份错误内容反复重试
+        signatures = current;
+
+        match reload() {
+            Ok(true) => {
+                xutil::info_if_enable_debug(&format!(
+                    "XOne config reloaded, location=[{location}]"
+                ));
+                tracing::info!(location, "config reloaded");
+            }
+            Ok(false) => {}
+            Err(e) => {
+                xutil::error_if_enable_debug(&format!(
+                    "XOne config reload rejected, keep previous config, err=[{e}]"
+                ));
+                tracing::warn!(location, error = %e, "config reload rejected, keep previous config");
+            }
+        }
+
+        // 激活的 profile 可能随配置变化，重新计算监听文件
+        let latest = watched_files(location);
+        if latest != files {
+            signatures = file_signatures(&latest);
+            files = latest;
+        }
+    }
+}
+
+/// 需要监听的配置文件：基础文件 + 当前激活的各 profile 文件（可能尚不存在）+ 导入的文件
+fn watched_files(location: &str) -> Vec<String> {
+    let mut files = vec![location.to_string()];
+    let store = config_store().read();
+    let config = store.as_ref().unwrap_or(&serde_yaml::Value::Null);
+    files.extend(
+        profiles::detect_profiles_active(config)
+            .iter()
+            .filter_map(|pa| profiles::to_profiles_active_config_location(location, pa).ok()),
+    );
+    files.extend(import::imported_files());
+    files
+}
+
+/// 文件签名（修改时间 + 大小），文件不存在时为 None
+type FileSignature = Option<(SystemTime, u64)>;
+
+/// 逐个计算文件签名
+fn file_signatures(files: &[String]) -> Vec<FileSignature> {
+    files
+        .iter()
+        .map(|f| {
+            std::fs::metadata(f)
+                .ok()
+                .and_then(|m| m.modified().ok().map(|t| (t, m.len())))
+        })
+        .collect()
+}
@@ -12,11 +12,16 @@
 //! // 获取底层 client 做更复杂操作
 //! let client = x_one::xhttp::c();
 //! ```
+//!
+//! 启用 `log` 特性时，便捷方法在按请求开启 DEBUG 日志的请求内自动携带
+//! `XLog.RequestDebug.Header`，直接使用 `c()` 时可通过 [`with_request_debug`] 添加。
+//!
+//! 启用 `trace` 特性且 xtrace 已启用时，便捷方法按 `XTrace.Propagators` 注入当前
+//! trace 上下文与 baggage，直接使用 `c()` 时可通过 [`with_trace_context`] 添加。
 
 use super::config::XHttpConfig;
 use crate::xutil;
 use std::sync::OnceLock;
-use std::time::Duration;
 
 /// 全局 HTTP 客户端
 pub(crate) static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
@@ -38,53 +43,104 @@ pub fn c() -> &'static reqwest::Client {
 ///
 /// 内部初始化使用，支持 timeout、connect_timeout、keep_alive、连接池等参数。
 pub fn build_client(config: &XHttpConfig) -> Result<reqwest::Client, crate::error::XOneError> {
-    let timeout = duration_or(&config.timeout, Duration::from_secs(30));
-    let connect_timeout = duration_or(&config.dial_timeout, Duration::from_secs(10));
-    let keep_alive = duration_or(&config.dial_keep_alive, Duration::from_secs(30));
-
     let builder = reqwest::Client::builder()
-        .timeout(timeout)
-        .connect_timeout(connect_timeout)
+        .timeout(config.timeout)
+        .connect_timeout(config.dial_timeout)
         .pool_max_idle_per_host(config.pool_max_idle_per_host)
-        .tcp_keepalive(keep_alive);
+        .tcp_keepalive(config.dial_keep_alive);
 
     builder
         .build()
         .map_err(|e| crate::error::XOneError::Other(format!("XHttp build client failed: {e}")))
 }
 
-fn duration_or(value: &str, default: Duration) -> Duration {
-    xutil::to_duration(value).unwrap_or(default)
+/// 当前处于按请求开启 DEBUG 日志的请求内时，为出站请求添加同一 token 的请求头
+///
+/// 未启用 `log` 特性、`XLog.RequestDebug` 未启用或不在此类请求内时原样返回。
+pub fn with_request_debug(builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    #[cfg(feature = "log")]
+    {
+        use crate::xlog::request_debug;
+        if let Some(policy) = request_debug::policy()
+            && let Some(token) = request_debug::current_token()
+        {
+            return builder.header(policy.header(), token);
+        }
+    }
+    builder
+}
+
+/// 按全局 propagator（`XTrace.Propagators`）为出站请求注入当前 trace 上下文与 baggage
+///
+/// 未启用 `trace` 特性或 xtrace 未启用时原样返回。
+pub fn with_trace_context(builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    #[cfg(feature = "trace")]
+    {
+        if crate::xtrace::is_trace_enabled() {
+            let mut headers = reqwest::header::HeaderMap::new();
+            opentelemetry::global::get_text_map_propagator(|propagator| {
+                propagator.inject_context(
+                    &opentelemetry::Context::current(),
+                    &mut HeaderInjector(&mut headers),
+                );
+            });
+            if !headers.is_empty() {
+                return builder.headers(headers);
+            }
+        }
+    }
+    builder
+}
+
+/// 写入 reqwest 请求头的注入器，非法的头名或值直接忽略
+#[cfg(feature = "trace")]
+struct HeaderInjector<'a>(&'a mut reqwest::header::HeaderMap);
+
+#[cfg(feature = "trace")]
+impl opentelemetry::propagation::Injector for HeaderInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (
+            reqwest::header::HeaderName::from_bytes(key.as_bytes()),
+            reqwest::header::HeaderValue::from_str(&value),
+        ) {
+            self.0.insert(name, value);
+        }
+    }
+}
+
+/// 便捷方法统一附加的出站请求头
+fn decorate(builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    with_trace_context(with_request_debug(builder))
 }
 
 // ---- 便捷方法，直接使用全局 client 发起请求 ----
 
 /// 发起 GET 请求
 pub fn get(url: &str) -> reqwest::RequestBuilder {
-    c().get(url)
+    decorate(c().get(url))
 }
 
 /// 发起 POST 请求
 pub fn post(url: &str) -> reqwest::RequestBuilder {
-    c().post(url)
+    decorate(c().post(url))
 }
 
 /// 发起 PUT 请求
 pub fn put(url: &str) -> reqwest::RequestBuilder {
-    c().put(url)
+    decorate(c().put(url))
 }
 
 /// 发起 PATCH 请求
 pub fn patch(url: &str) -> reqwest::RequestBuilder {
-    c().patch(url)
+    decorate(c().patch(url))
 }
 
 /// 发起 DELETE 请求
 pub fn delete(url: &str) -> reqwest::RequestBuilder {
-    c().delete(url)
+    decorate(c().delete(url))
 }
 
 /// 发起 HEAD 请求
 pub fn head(url: &str) -> reqwest::RequestBuilder {
-    c().head(url)
+    decorate(c().head(url))
 }
@@ -9,7 +9,8 @@
 //!   PoolMaxIdlePerHost: 10
 //! ```
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// XHttp 配置 key
 pub const XHTTP_CONFIG_KEY: &str = "XHttp";
@@ -24,20 +25,20 @@ pub const XHTTP_CONFIG_KEY: &str = "XHttp";
 ///   DialKeepAlive: "30s"
 ///   PoolMaxIdlePerHost: 10
 /// ```
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(default)]
 pub struct XHttpConfig {
     /// 请求超时（duration 字符串，默认 "30s"）
-    #[serde(rename = "Timeout")]
-    pub timeout: String,
+    #[serde(rename = "Timeout", with = "crate::xutil::serde_duration")]
+    pub timeout: Duration,
 
     /// 连接超时（duration 字符串，默认 "10s"）
-    #[serde(rename = "DialTimeout")]
-    pub dial_timeout: String,
+    #[serde(rename = "DialTimeout", with = "crate::xutil::serde_duration")]
+    pub dial_timeout: Duration,
 
     /// Keep-alive 时间（duration 字符串，默认 "30s"）
-    #[serde(rename = "DialKeepAlive")]
-    pub dial_keep_alive: String,
+    #[serde(rename = "DialKeepAlive", with = "crate::xutil::serde_duration")]
+    pub dial_keep_alive: Duration,
 
     /// 连接池每个主机最大空闲数（默认 10）
     #[serde(rename = "PoolMaxIdlePerHost")]
@@ -47,15 +48,15 @@ pub struct XHttpConfig {
 impl Default for XHttpConfig {
     fn default() -> Self {
         Self {
-            timeout: "30s".into(),
-            dial_timeout: "10s".into(),
-            dial_keep_alive: "30s".into(),
+            timeout: Duration::from_secs(30),
+            dial_timeout: Duration::from_secs(10),
+            dial_keep_alive: Duration::from_secs(30),
             pool_max_idle_per_host: 10,
         }
     }
 }
 
-/// 加载 XHttp 配置
-pub(crate) fn load_config() -> XHttpConfig {
-    crate::xconfig::parse_config::<XHttpConfig>(XHTTP_CONFIG_KEY).unwrap_or_default()
+/// 加载 XHttp 配置（未配置时使用默认值，配置值非法时返回错误）
+pub(crate) fn load_config() -> Result<XHttpConfig, crate::error::XOneError> {
+    Ok(crate::xconfig::try_get::<XHttpConfig>(XHTTP_CONFIG_KEY)?.unwrap_or_default())
 }
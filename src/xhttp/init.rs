@@ -13,7 +13,7 @@ pub fn init_xhttp() -> Result<(), crate::error::XOneError> {
         return Ok(());
     }
 
-    let config = load_config();
+    let config = load_config()?;
     let http_client = build_client(&config)?;
 
     if HTTP_CLIENT.set(http_client).is_err() {
@@ -22,6 +22,9 @@ pub fn init_xhttp() -> Result<(), crate::error::XOneError> {
         );
     }
 
-    xutil::info_if_enable_debug(&format!("XHttp init success, timeout=[{}]", config.timeout));
+    xutil::info_if_enable_debug(&format!(
+        "XHttp init success, timeout=[{:?}]",
+        config.timeout
+    ));
     Ok(())
 }
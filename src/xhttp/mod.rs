@@ -6,7 +6,9 @@ pub mod client;
 pub mod config;
 pub mod init;
 
-pub use client::{build_client, c, delete, get, head, patch, post, put};
+pub use client::{
+    build_client, c, delete, get, head, patch, post, put, with_request_debug, with_trace_context,
+};
 pub use config::XHttpConfig;
 
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -5,12 +5,51 @@
 //! ```yaml
 //! XLog:
 //!   Level: "info"
+//!   Targets:
+//!     sqlx: "warn"
+//!     my_app::billing: "debug"
 //!   Name: "app"
 //!   Path: "./log"
+//!   Output: "file"
+//!   Format: "json"
+//!   ErrorFile: true
+//!   Sinks:
+//!     - Name: "audit"
+//!       Level: "info"
+//!       Targets: { my_app::audit: "info" }
+//!       Format: "text"
 //!   Console: true
+//!   MaxAge: "7d"
+//!   MaxFiles: 30
+//!   MaxTotalSize: "10GiB"
+//!   RotateTime: "1h"
+//!   RotateSize: "512MiB"
+//!   Compress: true
+//!   RateLimit:
+//!     Enable: true
+//!     MaxEvents: 100
+//!     Interval: "1s"
+//!   Sampling:
+//!     Debug: 0.1
+//!     Info: 0.5
+//!   Redact:
+//!     Keys: ["password", "*token*", "phone"]
+//!     Values:
+//!       - Pattern: "\\b\\d{16}\\b"
+//!         Mask: "partial"
+//!   Otlp:
+//!     Enable: true
+//!     Endpoint: "http://otel-collector:4318"
+//!     Protocol: "http/protobuf"
+//!   RequestDebug:
+//!     Enable: true
+//!     Secret: "${XLOG_DEBUG_SECRET}"
 //! ```
 
+use crate::error::XOneError;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
 
 /// 日志配置 key
 pub const XLOG_CONFIG_KEY: &str = "XLog";
@@ -48,6 +87,12 @@ pub struct XLogConfig {
     #[serde(rename = "Level")]
     pub level: LogLevel,
 
+    /// 按 target 设置的日志级别（默认为空），覆盖 `Level`
+    ///
+    /// key 为 target 前缀（通常是 crate 名或模块路径，如 `sqlx`、`my_app::billing`）。
+    #[serde(rename = "Targets")]
+    pub targets: BTreeMap<String, LogLevel>,
+
     /// 日志文件名称（默认 "app"）
     #[serde(rename = "Name")]
     pub name: String,
@@ -56,6 +101,22 @@ pub struct XLogConfig {
     #[serde(rename = "Path")]
     pub path: String,
 
+    /// 日志输出目标（默认 file）：file / stdout / stderr / both
+    #[serde(rename = "Output")]
+    pub output: LogOutput,
+
+    /// 日志格式（默认 json）：json / text / ecs / gcp / logfmt，作用于日志文件与 stdout / stderr 输出
+    #[serde(rename = "Format")]
+    pub format: LogFormat,
+
+    /// 是否额外将 WARN / ERROR 日志写入 `{Name}.error.log`（默认 false）
+    #[serde(rename = "ErrorFile")]
+    pub error_file: bool,
+
+    /// 额外的文件 sink（默认为空），各自设置级别与格式
+    #[serde(rename = "Sinks")]
+    pub sinks: Vec<LogSinkConfig>,
+
     /// 日志内容是否在控制台打印（默认 false）
     #[serde(rename = "Console")]
     pub console: bool,
@@ -64,41 +125,604 @@ pub struct XLogConfig {
     #[serde(rename = "ConsoleFormatIsRaw")]
     pub console_format_is_raw: bool,
 
-    /// 日志保存最大时间（默认 "7d"）
+    /// 日志保存最大时间（默认 "7d"，设为 "0s" 不按时间清理）
     ///
-    /// **注意**：当前版本未实现自动清理，保留此字段仅为配置兼容。
-    #[serde(rename = "MaxAge")]
-    pub max_age: String,
+    /// 修改时间早于该时长的切割文件会被后台任务删除。
+    #[serde(rename = "MaxAge", with = "crate::xutil::serde_duration")]
+    pub max_age: Duration,
 
-    /// 日志切割时长（默认 "1d"）
+    /// 最多保留的日志文件数，含当前写入的文件（默认 0，不限制）
+    #[serde(rename = "MaxFiles")]
+    pub max_files: usize,
+
+    /// 日志文件总大小上限（整数字节或 "10GiB" 形式，默认 0，不限制）
+    ///
+    /// 超出时从最旧的切割文件开始删除，当前写入的文件不会被删除。
+    #[serde(rename = "MaxTotalSize", with = "crate::xutil::serde_byte_size")]
+    pub max_total_size: u64,
+
+    /// 日志切割周期（默认 "1d"，支持 "1h"、"15m" 等，"0s" 不按时间切割）
     ///
-    /// **注意**：当前版本固定按天切割，此字段仅为配置兼容。
-    #[serde(rename = "RotateTime")]
-    pub rotate_time: String,
+    /// 周期边界按 `Timezone` 时区对齐。
+    #[serde(rename = "RotateTime", with = "crate::xutil::serde_duration")]
+    pub rotate_time: Duration,
+
+    /// 单个日志文件大小上限（整数字节或 "512MiB" 形式，默认 0，不按大小切割）
+    #[serde(rename = "RotateSize", with = "crate::xutil::serde_byte_size")]
+    pub rotate_size: u64,
+
+    /// 是否在后台 gzip 压缩切割下来的文件（默认 false）
+    #[serde(rename = "Compress")]
+    pub compress: bool,
 
     /// 日志时间的时区（默认 "Asia/Shanghai"）
     ///
-    /// **注意**：当前版本使用 UTC 时间戳，此字段仅为配置兼容。
+    /// 支持 IANA 时区名、`+08:00` 形式的固定偏移、`UTC` 与 `Local`，
+    /// 同时作用于日志时间戳和文件切割周期边界。
     #[serde(rename = "Timezone")]
     pub timezone: String,
+
+    /// 按调用点限流
+    #[serde(rename = "RateLimit")]
+    pub rate_limit: RateLimitConfig,
+
+    /// trace / debug / info 级别日志的采样率
+    #[serde(rename = "Sampling")]
+    pub sampling: SamplingConfig,
+
+    /// 敏感字段脱敏策略
+    #[serde(rename = "Redact")]
+    pub redact: RedactConfig,
+
+    /// 通过 OTLP 导出日志（需启用 `log-otlp` 特性）
+    #[serde(rename = "Otlp")]
+    pub otlp: OtlpConfig,
+
+    /// 按请求开启 DEBUG 日志
+    #[serde(rename = "RequestDebug")]
+    pub request_debug: RequestDebugConfig,
+}
+
+/// 日志限流配置
+///
+/// 每个调用点（源码中的一处日志宏）在 `Interval` 内最多输出 `MaxEvents` 条日志，
+/// 超出的日志被丢弃，并在周期结束后输出一条包含丢弃数量的汇总日志。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    /// 是否启用限流（默认 false）
+    #[serde(rename = "Enable")]
+    pub enable: bool,
+
+    /// 每个周期每个调用点最多输出的日志条数（默认 100）
+    #[serde(rename = "MaxEvents")]
+    pub max_events: u64,
+
+    /// 限流周期（默认 "1s"）
+    #[serde(rename = "Interval", with = "crate::xutil::serde_duration")]
+    pub interval: Duration,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            max_events: 100,
+            interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// 日志采样配置（取值 0.0 ~ 1.0，默认 1.0 全部输出）
+///
+/// warn / error 级别日志不参与采样。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SamplingConfig {
+    /// trace 级别采样率
+    #[serde(rename = "Trace")]
+    pub trace: f64,
+
+    /// debug 级别采样率
+    #[serde(rename = "Debug")]
+    pub debug: f64,
+
+    /// info 级别采样率
+    #[serde(rename = "Info")]
+    pub info: f64,
+}
+
+impl Default for SamplingConfig {
+    fn default() -> Self {
+        Self {
+            trace: 1.0,
+            debug: 1.0,
+            info: 1.0,
+        }
+    }
+}
+
+/// 脱敏方式
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MaskMode {
+    /// 整体替换为 `***`
+    #[default]
+    Full,
+    /// 保留首尾少量字符，如 `138****8000`
+    Partial,
+}
+
+/// 按值匹配的脱敏规则
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct RedactValueRule {
+    /// 匹配敏感内容的正则（如银行卡号 `\b\d{16}\b`）
+    #[serde(rename = "Pattern")]
+    pub pattern: String,
+
+    /// 匹配内容的脱敏方式（默认 full）
+    #[serde(rename = "Mask")]
+    pub mask: MaskMode,
+}
+
+/// 日志脱敏配置
+///
+/// 作用于 JSON / 控制台日志字段（含 `xlog_kv!` 注入的字段）以及 XAxum 访问日志的 header 与 body。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedactConfig {
+    /// 是否启用脱敏（默认 true）
+    #[serde(rename = "Enable")]
+    pub enable: bool,
+
+    /// 敏感字段名模式（忽略大小写，`*` 为通配符），配置后替换默认列表
+    ///
+    /// 字段名为点分形式（如 `user.password`）时，完整名或最后一段匹配即视为敏感。
+    #[serde(rename = "Keys")]
+    pub keys: Vec<String>,
+
+    /// 敏感字段值的脱敏方式（默认 full）
+    #[serde(rename = "Mask")]
+    pub mask: MaskMode,
+
+    /// 按值匹配的脱敏规则（默认为空），作用于所有字符串字段和消息内容
+    #[serde(rename = "Values")]
+    pub values: Vec<RedactValueRule>,
+}
+
+impl Default for RedactConfig {
+    fn default() -> Self {
+        Self {
+            enable: true,
+            keys: [
+                "password",
+                "passwd",
+                "*secret*",
+                "*token*",
+                "*api_key*",
+                "*apikey*",
+                "authorization",
+                "cookie",
+                "phone",
+                "mobile",
+            ]
+            .iter()
+            .map(|k| k.to_string())
+            .collect(),
+            mask: MaskMode::Full,
+            values: Vec::new(),
+        }
+    }
+}
+
+impl XLogConfig {
+    /// 将 `Level` 与 `Targets` 编译为过滤指令（语法同 `RUST_LOG`）
+    ///
+    /// ```
+    /// use x_one::xlog::config::{LogLevel, XLogConfig};
+    ///
+    /// let mut c = XLogConfig::default();
+    /// c.targets.insert("sqlx".to_string(), LogLevel::Warn);
+    /// assert_eq!(c.filter_directives().unwrap(), "info,sqlx=warn");
+    /// ```
+    ///
+    /// target 为空或包含空白、`,`、`=`、`[`、`]`、`{`、`}` 时返回错误。
+    pub fn filter_directives(&self) -> Result<String, XOneError> {
+        compile_directives(
+            self.level,
+            &self.targets,
+            &format!("{XLOG_CONFIG_KEY}.Targets"),
+        )
+    }
+
+    /// 额外的文件 sink 列表（`ErrorFile` 开启时包含 `{Name}.error` sink，格式同主日志文件）
+    ///
+    /// sink 名称为空、包含路径分隔符、与主日志文件或其他 sink 重名时返回错误。
+    pub fn file_sinks(&self) -> Result<Vec<LogSinkConfig>, XOneError> {
+        let mut sinks = Vec::with_capacity(self.sinks.len() + 1);
+        if self.error_file {
+            sinks.push(LogSinkConfig {
+                name: format!("{}.error", self.name),
+                level: LogLevel::Warn,
+                format: self.format,
+                ..Default::default()
+            });
+        }
+        sinks.extend(self.sinks.iter().cloned());
+
+        let mut names = vec![self.name.as_str()];
+        for sink in &sinks {
+            let name = sink.name.trim();
+            if name.is_empty() || name.contains(['/', '\\']) {
+                return Err(XOneError::Log(format!(
+                    "invalid {XLOG_CONFIG_KEY}.Sinks name [{name}]"
+                )));
+            }
+            if names.contains(&name) {
+                return Err(XOneError::Log(format!(
+                    "duplicate {XLOG_CONFIG_KEY}.Sinks name [{name}]"
+                )));
+            }
+            names.push(name);
+        }
+        Ok(sinks)
+    }
+}
+
+/// 文件日志格式
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// 内置 JSON 格式（`timestamp` / `level` / `fields` / `trace_id` 等字段）
+    #[default]
+    Json,
+    /// 不含颜色的文本格式（同控制台输出）
+    Text,
+    /// Elastic Common Schema JSON（`@timestamp` / `log.level` / `trace.id` 等字段）
+    Ecs,
+    /// Google Cloud Logging 结构化 JSON（`severity` / `logging.googleapis.com/trace` 等字段）
+    Gcp,
+    /// logfmt 文本（`key=value` 形式）
+    Logfmt,
+}
+
+/// 日志输出目标
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LogOutput {
+    /// 写入 `{Path}/{Name}.log` 及各文件 sink，`Console` 控制是否同时输出到控制台
+    #[default]
+    File,
+    /// 仅输出到 stdout，不创建日志目录与文件（适用于只读文件系统的容器）
+    Stdout,
+    /// 仅输出到 stderr，不创建日志目录与文件
+    Stderr,
+    /// 同时写入日志文件与 stdout
+    Both,
+}
+
+impl LogOutput {
+    /// 是否写入日志文件
+    pub fn writes_file(self) -> bool {
+        matches!(self, LogOutput::File | LogOutput::Both)
+    }
+}
+
+/// 额外的文件 sink 配置
+///
+/// 写入 `{Path}/{Name}.log`，切割与清理设置与主日志文件相同。
+/// 级别过滤独立于 `XLog.Level`，sink 可输出比主日志更详细的日志。
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct LogSinkConfig {
+    /// 日志文件名称（不含 `.log` 后缀）
+    #[serde(rename = "Name")]
+    pub name: String,
+
+    /// 日志级别（默认 "info"）
+    #[serde(rename = "Level")]
+    pub level: LogLevel,
+
+    /// 按 target 设置的日志级别（默认为空）
+    #[serde(rename = "Targets")]
+    pub targets: BTreeMap<String, LogLevel>,
+
+    /// 日志格式（默认 json），可选值同 `XLog.Format`
+    #[serde(rename = "Format")]
+    pub format: LogFormat,
+}
+
+impl LogSinkConfig {
+    /// 将 `Level` 与 `Targets` 编译为过滤指令
+    pub fn filter_directives(&self) -> Result<String, XOneError> {
+        compile_directives(
+            self.level,
+            &self.targets,
+            &format!("{XLOG_CONFIG_KEY}.Sinks[{}].Targets", self.name),
+        )
+    }
+}
+
+/// OTLP 传输协议
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum OtlpProtocol {
+    /// HTTP + protobuf，请求 `{Endpoint}/v1/logs`
+    #[default]
+    #[serde(rename = "http/protobuf")]
+    HttpProtobuf,
+    /// HTTP + JSON，请求 `{Endpoint}/v1/logs`
+    #[serde(rename = "http/json")]
+    HttpJson,
+    /// gRPC（HTTP/2），调用 `LogsService/Export`
+    #[serde(rename = "grpc")]
+    Grpc,
+}
+
+impl OtlpProtocol {
+    /// 协议的默认端点（http 为 4318 端口，grpc 为 4317 端口）
+    pub fn default_endpoint(self) -> &'static str {
+        match self {
+            OtlpProtocol::HttpProtobuf | OtlpProtocol::HttpJson => "http://localhost:4318",
+            OtlpProtocol::Grpc => "http://localhost:4317",
+        }
+    }
+}
+
+/// OTLP 日志导出配置
+///
+/// 日志事件经 OpenTelemetry logs SDK 转为 LogRecord，写入有界队列后由后台线程批量发送，
+/// 队列满或发送失败时丢弃并计数。级别过滤独立于 `XLog.Level`。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OtlpConfig {
+    /// 是否启用（默认 false）
+    #[serde(rename = "Enable")]
+    pub enable: bool,
+
+    /// Collector 地址（默认按协议为 `http://localhost:4318` 或 `http://localhost:4317`）
+    ///
+    /// HTTP 协议下未以 `/v1/logs` 结尾时自动追加。
+    #[serde(rename = "Endpoint")]
+    pub endpoint: String,
+
+    /// 传输协议（默认 "http/protobuf"）：http/protobuf / http/json / grpc
+    #[serde(rename = "Protocol")]
+    pub protocol: OtlpProtocol,
+
+    /// 附加的请求头（如鉴权 token）
+    #[serde(rename = "Headers")]
+    pub headers: BTreeMap<String, String>,
+
+    /// 单次发送超时（默认 "10s"）
+    #[serde(rename = "Timeout", with = "crate::xutil::serde_duration")]
+    pub timeout: Duration,
+
+    /// 导出的日志级别（默认 "info"）
+    #[serde(rename = "Level")]
+    pub level: LogLevel,
+
+    /// 按 target 设置的导出级别（默认为空）
+    #[serde(rename = "Targets")]
+    pub targets: BTreeMap<String, LogLevel>,
+
+    /// 待发送队列容量（默认 2048），队列满时丢弃新日志
+    #[serde(rename = "MaxQueueSize")]
+    pub max_queue_size: usize,
+
+    /// 单批最多发送的条数（默认 512）
+    #[serde(rename = "MaxBatchSize")]
+    pub max_batch_size: usize,
+
+    /// 批量发送间隔（默认 "1s"），队列中积累满一批时立即发送
+    #[serde(rename = "ExportInterval", with = "crate::xutil::serde_duration")]
+    pub export_interval: Duration,
+}
+
+impl Default for OtlpConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            endpoint: String::new(),
+            protocol: OtlpProtocol::HttpProtobuf,
+            headers: BTreeMap::new(),
+            timeout: Duration::from_secs(10),
+            level: LogLevel::Info,
+            targets: BTreeMap::new(),
+            max_queue_size: 2048,
+            max_batch_size: 512,
+            export_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+impl OtlpConfig {
+    /// 将 `Level` 与 `Targets` 编译为过滤指令
+    pub fn filter_directives(&self) -> Result<String, XOneError> {
+        compile_directives(
+            self.level,
+            &self.targets,
+            &format!("{XLOG_CONFIG_KEY}.Otlp.Targets"),
+        )
+    }
+
+    /// Collector 地址，未配置时为协议的默认端点
+    pub(crate) fn collector_endpoint(&self) -> &str {
+        let endpoint = self.endpoint.trim();
+        if endpoint.is_empty() {
+            self.protocol.default_endpoint()
+        } else {
+            endpoint.trim_end_matches('/')
+        }
+    }
+
+    /// 实际请求的 URL
+    ///
+    /// ```
+    /// use x_one::xlog::config::{OtlpConfig, OtlpProtocol};
+    ///
+    /// let mut c = OtlpConfig::default();
+    /// assert_eq!(c.export_url(), "http://localhost:4318/v1/logs");
+    ///
+    /// c.endpoint = "http://collector:4317/".to_string();
+    /// c.protocol = OtlpProtocol::Grpc;
+    /// assert_eq!(
+    ///     c.export_url(),
+    ///     "http://collector:4317/opentelemetry.proto.collector.logs.v1.LogsService/Export"
+    /// );
+    /// ```
+    pub fn export_url(&self) -> String {
+        let endpoint = self.collector_endpoint();
+        match self.protocol {
+            OtlpProtocol::Grpc => {
+                format!("{endpoint}/opentelemetry.proto.collector.logs.v1.LogsService/Export")
+            }
+            _ if endpoint.ends_with("/v1/logs") => endpoint.to_string(),
+            _ => format!("{endpoint}/v1/logs"),
+        }
+    }
+
+    /// 校验参数，队列容量、批大小、发送间隔或超时为 0 时返回错误
+    pub fn validate(&self) -> Result<(), XOneError> {
+        if self.max_queue_size == 0
+            || self.max_batch_size == 0
+            || self.export_interval.is_zero()
+            || self.timeout.is_zero()
+        {
+            return Err(XOneError::Log(format!(
+                "invalid {XLOG_CONFIG_KEY}.Otlp, MaxQueueSize, MaxBatchSize, ExportInterval and Timeout must be greater than 0"
+            )));
+        }
+        let url = self.export_url();
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(XOneError::Log(format!(
+                "invalid {XLOG_CONFIG_KEY}.Otlp.Endpoint [{}], expect http:// or https:// url",
+                self.endpoint
+            )));
+        }
+        self.filter_directives().map(|_| ())
+    }
+}
+
+/// 按请求开启 DEBUG 日志的配置
+///
+/// 请求携带签名有效的 `Header` 时，该请求 span 内的 DEBUG 日志不受 `XLog.Level` 限制，
+/// 签名 token 通过 [`sign_token`](super::request_debug::sign_token) 生成。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RequestDebugConfig {
+    /// 是否启用（默认 false）
+    #[serde(rename = "Enable")]
+    pub enable: bool,
+
+    /// HMAC-SHA256 签名密钥，启用时不能为空
+    #[serde(rename = "Secret")]
+    pub secret: String,
+
+    /// 携带签名 token 的请求头（默认 "X-Debug-Log"），xhttp 出站请求沿用同名请求头
+    #[serde(rename = "Header")]
+    pub header: String,
+
+    /// token 最长有效期（默认 "1h"），过期时间超出当前时间加该值的 token 视为无效
+    #[serde(rename = "MaxTTL", with = "crate::xutil::serde_duration")]
+    pub max_ttl: Duration,
+}
+
+impl Default for RequestDebugConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            secret: String::new(),
+            header: "X-Debug-Log".to_string(),
+            max_ttl: Duration::from_secs(3600),
+        }
+    }
+}
+
+impl RequestDebugConfig {
+    /// 校验参数，启用时密钥为空、请求头名非法或有效期为 0 返回错误
+    pub fn validate(&self) -> Result<(), XOneError> {
+        if !self.enable {
+            return Ok(());
+        }
+        if self.secret.is_empty() {
+            return Err(XOneError::Log(format!(
+                "invalid {XLOG_CONFIG_KEY}.RequestDebug, Secret must not be empty"
+            )));
+        }
+        if self.header.is_empty()
+            || !self
+                .header
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+        {
+            return Err(XOneError::Log(format!(
+                "invalid {XLOG_CONFIG_KEY}.RequestDebug.Header [{}]",
+                self.header
+            )));
+        }
+        if self.max_ttl.is_zero() {
+            return Err(XOneError::Log(format!(
+                "invalid {XLOG_CONFIG_KEY}.RequestDebug.MaxTTL, must be greater than 0"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// 将级别与 target 级别编译为过滤指令，`path` 用于错误信息
+fn compile_directives(
+    level: LogLevel,
+    targets: &BTreeMap<String, LogLevel>,
+    path: &str,
+) -> Result<String, XOneError> {
+    let mut directives = level.as_str().to_string();
+    for (target, level) in targets {
+        let target = target.trim();
+        if target.is_empty()
+            || target
+                .chars()
+                .any(|ch| ch.is_whitespace() || ",=[]{}".contains(ch))
+        {
+            return Err(XOneError::Log(format!(
+                "invalid log target [{target}] in {path}"
+            )));
+        }
+        directives.push_str(&format!(",{target}={}", level.as_str()));
+    }
+    Ok(directives)
 }
 
-/// 从配置中获取日志配置
-pub(crate) fn load_config() -> XLogConfig {
-    crate::xconfig::parse_config::<XLogConfig>(XLOG_CONFIG_KEY).unwrap_or_default()
+/// 从配置中获取日志配置（未配置时使用默认值，配置值非法时返回错误）
+pub(crate) fn load_config() -> Result<XLogConfig, crate::error::XOneError> {
+    Ok(crate::xconfig::try_get::<XLogConfig>(XLOG_CONFIG_KEY)?.unwrap_or_default())
 }
 
 impl Default for XLogConfig {
     fn default() -> Self {
         Self {
             level: LogLevel::Info,
+            targets: BTreeMap::new(),
             name: "app".to_string(),
             path: "./log".to_string(),
+            output: LogOutput::File,
+            format: LogFormat::Json,
+            error_file: false,
+            sinks: Vec::new(),
             console: false,
             console_format_is_raw: false,
-            max_age: "7d".to_string(),
-            rotate_time: "1d".to_string(),
+            max_age: Duration::from_secs(7 * 24 * 3600),
+            max_files: 0,
+            max_total_size: 0,
+            rotate_time: Duration::from_secs(24 * 3600),
+            rotate_size: 0,
+            compress: false,
             timezone: "Asia/Shanghai".to_string(),
+            rate_limit: RateLimitConfig::default(),
+            sampling: SamplingConfig::default(),
+            redact: RedactConfig::default(),
+            otlp: OtlpConfig::default(),
+            request_debug: RequestDebugConfig::default(),
         }
     }
 }
@@ -49,3 +49,26 @@ pub fn format_console_line(
         )
     }
 }
+
+/// 格式化不含颜色的文本日志行（用于文本格式的文件输出）
+#[doc(hidden)]
+pub fn format_plain_line(
+    level: &tracing::Level,
+    timestamp: &str,
+    message: &str,
+    trace_id: &str,
+    caller: &str,
+) -> String {
+    let level_text = level.as_str();
+    let caller_part = if caller.is_empty() {
+        String::new()
+    } else {
+        format!(" ({caller})")
+    };
+
+    if trace_id.is_empty() {
+        format!("{level_text}[{timestamp}] {message}{caller_part}\n")
+    } else {
+        format!("{level_text}[{timestamp}] {trace_id} {message}{caller_part}\n")
+    }
+}
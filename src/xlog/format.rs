@@ -0,0 +1,340 @@
+//! 可选的结构化日志格式
+//!
+//! 按 `XLog.Format` / `XLog.Sinks[].Format` 选择文件日志的编码方式：
+//!
+//! - `json`：内置 JSON 格式，见 [`OtelJsonFormat`]
+//! - `text`：不含颜色的文本格式，见 [`OtelConsoleFormat`]
+//! - `ecs`：Elastic Common Schema，见 [`EcsFormat`]
+//! - `gcp`：Google Cloud Logging 结构化日志，见 [`GcpFormat`]
+//! - `logfmt`：`key=value` 文本，见 [`LogfmtFormat`]
+//!
+//! 所有格式均注入 OpenTelemetry `trace_id` / `span_id` 与 `xlog_kv!` 注入的 Span 字段，并按脱敏策略处理字段。
+
+use std::fmt;
+
+use super::config::LogFormat;
+use super::otel_fmt::{OtelConsoleFormat, OtelJsonFormat, collect_fields, get_otel_trace_ids};
+use super::timezone::LogTimezone;
+use serde_json::{Map, Value};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::Layer;
+use tracing_subscriber::fmt::format::{self, FormatFields};
+use tracing_subscriber::fmt::writer::MakeWriter;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent};
+use tracing_subscriber::registry::LookupSpan;
+
+/// 输出的 ECS 版本号
+pub const ECS_VERSION: &str = "8.11.0";
+
+/// GCP 项目 ID 环境变量，设置后 trace 字段输出为 `projects/{id}/traces/{trace_id}`
+pub const GCP_PROJECT_ENV: &str = "GOOGLE_CLOUD_PROJECT";
+
+/// 按格式创建文件输出 Layer
+pub fn fmt_layer<S, W>(
+    format: LogFormat,
+    timezone: LogTimezone,
+    writer: W,
+) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let layer = tracing_subscriber::fmt::layer().with_writer(writer);
+    match format {
+        LogFormat::Json => layer
+            .event_format(OtelJsonFormat.with_timezone(timezone))
+            .boxed(),
+        LogFormat::Text => layer
+            .event_format(OtelConsoleFormat.with_timezone(timezone).with_ansi(false))
+            .boxed(),
+        LogFormat::Ecs => layer.event_format(EcsFormat::new(timezone)).boxed(),
+        LogFormat::Gcp => layer.event_format(GcpFormat::new(timezone)).boxed(),
+        LogFormat::Logfmt => layer.event_format(LogfmtFormat::new(timezone)).boxed(),
+    }
+}
+
+/// Elastic Common Schema 格式化器
+///
+/// 输出 `@timestamp`、`log.level`、`message`、`log.logger`、`log.origin.*`、
+/// `process.thread.name`、`trace.id`、`span.id`、`ecs.version` 等字段，
+/// 自定义字段作为顶层字段输出（与上述字段重名时忽略）。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EcsFormat {
+    timezone: LogTimezone,
+}
+
+impl EcsFormat {
+    /// 使用指定时区输出时间戳
+    pub fn new(timezone: LogTimezone) -> Self {
+        Self { timezone }
+    }
+}
+
+impl<S, N> FormatEvent<S, N> for EcsFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: format::Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let meta = event.metadata();
+        let mut fields = collect_fields(ctx, event);
+        let message = take_message(&mut fields);
+        let (trace_id, span_id) = get_otel_trace_ids();
+
+        let mut entry = Map::new();
+        entry.insert(
+            "@timestamp".into(),
+            self.timezone.format_rfc3339(chrono::Utc::now()).into(),
+        );
+        entry.insert("log.level".into(), meta.level().as_str().into());
+        entry.insert("message".into(), message.into());
+        entry.insert("ecs.version".into(), ECS_VERSION.into());
+        entry.insert("log.logger".into(), meta.target().into());
+        if let Some(file) = meta.file() {
+            entry.insert("log.origin.file.name".into(), file.into());
+        }
+        if let Some(line) = meta.line() {
+            entry.insert("log.origin.file.line".into(), line.into());
+        }
+        let thread = std::thread::current();
+        entry.insert(
+            "process.thread.name".into(),
+            thread.name().unwrap_or("unknown").into(),
+        );
+        if !trace_id.is_empty() {
+            entry.insert("trace.id".into(), trace_id.into());
+            entry.insert("span.id".into(), span_id.into());
+        }
+        for (k, v) in fields {
+            entry.entry(k).or_insert(v);
+        }
+
+        write_json_line(&mut writer, &entry)
+    }
+}
+
+/// Google Cloud Logging 结构化日志格式化器
+///
+/// 输出 `timestamp`、`severity`、`message`、`logging.googleapis.com/sourceLocation`、
+/// `logging.googleapis.com/trace`、`logging.googleapis.com/spanId` 等字段，
+/// 自定义字段作为顶层字段输出（与上述字段重名时忽略）。
+///
+/// 设置 `GOOGLE_CLOUD_PROJECT` 环境变量时 trace 字段为 `projects/{id}/traces/{trace_id}`，
+/// 以便 Cloud Logging 与 Cloud Trace 关联，否则为原始 trace_id。
+#[derive(Debug, Clone, Default)]
+pub struct GcpFormat {
+    timezone: LogTimezone,
+    project_id: Option<String>,
+}
+
+impl GcpFormat {
+    /// 使用指定时区输出时间戳，项目 ID 从 `GOOGLE_CLOUD_PROJECT` 环境变量读取
+    pub fn new(timezone: LogTimezone) -> Self {
+        let project_id = std::env::var(GCP_PROJECT_ENV)
+            .ok()
+            .filter(|id| !id.trim().is_empty());
+        Self {
+            timezone,
+            project_id,
+        }
+    }
+
+    /// 设置 trace 字段使用的 GCP 项目 ID
+    pub fn with_project_id(mut self, project_id: impl Into<String>) -> Self {
+        self.project_id = Some(project_id.into());
+        self
+    }
+}
+
+impl<S, N> FormatEvent<S, N> for GcpFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: format::Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let meta = event.metadata();
+        let mut fields = collect_fields(ctx, event);
+        let message = take_message(&mut fields);
+        let (trace_id, span_id) = get_otel_trace_ids();
+
+        let mut entry = Map::new();
+        entry.insert(
+            "timestamp".into(),
+            self.timezone.format_rfc3339(chrono::Utc::now()).into(),
+        );
+        entry.insert("severity".into(), gcp_severity(meta.level()).into());
+        entry.insert("message".into(), message.into());
+        entry.insert("target".into(), meta.target().into());
+
+        let mut location = Map::new();
+        if let Some(file) = meta.file() {
+            location.insert("file".into(), file.into());
+        }
+        if let Some(line) = meta.line() {
+            // GCP 约定 line 为字符串（int64 的 JSON 表示）
+            location.insert("line".into(), line.to_string().into());
+        }
+        location.insert("function".into(), meta.target().into());
+        entry.insert(
+            "logging.googleapis.com/sourceLocation".into(),
+            Value::Object(location),
+        );
+
+        if !trace_id.is_empty() {
+            let trace = match &self.project_id {
+                Some(project) => format!("projects/{project}/traces/{trace_id}"),
+                None => trace_id,
+            };
+            entry.insert("logging.googleapis.com/trace".into(), trace.into());
+            entry.insert("logging.googleapis.com/spanId".into(), span_id.into());
+        }
+        for (k, v) in fields {
+            entry.entry(k).or_insert(v);
+        }
+
+        write_json_line(&mut writer, &entry)
+    }
+}
+
+/// logfmt 格式化器
+///
+/// 输出 `time=... level=... msg=... target=... caller=... trace_id=... span_id=...`，其后为自定义字段。
+/// 值为空或包含空格、`=`、`"`、控制字符时加双引号并转义。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogfmtFormat {
+    timezone: LogTimezone,
+}
+
+impl LogfmtFormat {
+    /// 使用指定时区输出时间戳
+    pub fn new(timezone: LogTimezone) -> Self {
+        Self { timezone }
+    }
+}
+
+impl<S, N> FormatEvent<S, N> for LogfmtFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: format::Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let meta = event.metadata();
+        let mut fields = collect_fields(ctx, event);
+        let message = take_message(&mut fields);
+        let (trace_id, span_id) = get_otel_trace_ids();
+
+        let mut line = String::with_capacity(256);
+        push_logfmt_pair(
+            &mut line,
+            "time",
+            &self.timezone.format_rfc3339(chrono::Utc::now()),
+        );
+        push_logfmt_pair(&mut line, "level", &meta.level().as_str().to_lowercase());
+        push_logfmt_pair(&mut line, "msg", &message);
+        push_logfmt_pair(&mut line, "target", meta.target());
+        if let (Some(file), Some(line_no)) = (meta.file(), meta.line()) {
+            push_logfmt_pair(&mut line, "caller", &format!("{file}:{line_no}"));
+        }
+        if !trace_id.is_empty() {
+            push_logfmt_pair(&mut line, "trace_id", &trace_id);
+            push_logfmt_pair(&mut line, "span_id", &span_id);
+        }
+        for (k, v) in &fields {
+            match v {
+                Value::String(s) => push_logfmt_pair(&mut line, k, s),
+                other => push_logfmt_pair(&mut line, k, &other.to_string()),
+            }
+        }
+
+        writeln!(writer, "{line}")
+    }
+}
+
+/// 按 logfmt 规则转义单个值
+///
+/// ```
+/// use x_one::xlog::format::logfmt_value;
+///
+/// assert_eq!(logfmt_value("ok"), "ok");
+/// assert_eq!(logfmt_value("hello world"), "\"hello world\"");
+/// assert_eq!(logfmt_value(""), "\"\"");
+/// assert_eq!(logfmt_value("a\"b"), "\"a\\\"b\"");
+/// ```
+pub fn logfmt_value(value: &str) -> String {
+    let needs_quote = value.is_empty()
+        || value
+            .chars()
+            .any(|c| c == ' ' || c == '=' || c == '"' || c.is_control());
+    if !needs_quote {
+        return value.to_string();
+    }
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// ---- 以下为私有实现 ----
+
+/// 取出 message 字段（非字符串时转为 JSON 文本）
+fn take_message(fields: &mut Map<String, Value>) -> String {
+    match fields.remove("message") {
+        Some(Value::String(s)) => s,
+        Some(other) => other.to_string(),
+        None => String::new(),
+    }
+}
+
+/// trace 级别在 GCP 中无对应值，归为 DEBUG
+fn gcp_severity(level: &Level) -> &'static str {
+    match *level {
+        Level::ERROR => "ERROR",
+        Level::WARN => "WARNING",
+        Level::INFO => "INFO",
+        _ => "DEBUG",
+    }
+}
+
+fn push_logfmt_pair(line: &mut String, key: &str, value: &str) {
+    if !line.is_empty() {
+        line.push(' ');
+    }
+    // key 中的空格、`=`、`"` 替换为 `_`，保证可被解析
+    line.extend(key.chars().map(|c| match c {
+        ' ' | '=' | '"' => '_',
+        c => c,
+    }));
+    line.push('=');
+    line.push_str(&logfmt_value(value));
+}
+
+fn write_json_line(writer: &mut format::Writer<'_>, entry: &Map<String, Value>) -> fmt::Result {
+    let json = serde_json::to_string(entry).map_err(|_| fmt::Error)?;
+    writeln!(writer, "{json}")
+}
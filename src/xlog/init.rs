@@ -1,18 +1,18 @@
 //! 日志系统初始化与关闭
 
-use super::config::{XLOG_CONFIG_KEY, XLogConfig};
+use super::config::{LogOutput, XLOG_CONFIG_KEY, XLogConfig};
 use super::otel_fmt::{OtelConsoleFormat, OtelJsonFormat};
+use super::timezone::LogTimezone;
 use crate::{xconfig, xutil};
 use parking_lot::Mutex;
-use tracing_subscriber::EnvFilter;
 use tracing_subscriber::prelude::*;
 
-/// 全局日志写入器 guard（drop 时刷新并关闭日志）
-static LOG_GUARD: std::sync::OnceLock<Mutex<Option<tracing_appender::non_blocking::WorkerGuard>>> =
+/// 全局日志写入器 guard（主日志文件及各文件 sink，drop 时刷新并关闭日志）
+static LOG_GUARD: std::sync::OnceLock<Mutex<Vec<tracing_appender::non_blocking::WorkerGuard>>> =
     std::sync::OnceLock::new();
 
-fn guard_store() -> &'static Mutex<Option<tracing_appender::non_blocking::WorkerGuard>> {
-    LOG_GUARD.get_or_init(|| Mutex::new(None))
+fn guard_store() -> &'static Mutex<Vec<tracing_appender::non_blocking::WorkerGuard>> {
+    LOG_GUARD.get_or_init(|| Mutex::new(Vec::new()))
 }
 
 /// 初始化日志系统
@@ -22,7 +22,7 @@ pub fn init_xlog() -> Result<(), crate::error::XOneError> {
         return Ok(());
     }
 
-    let c = super::config::load_config();
+    let c = super::config::load_config()?;
     xutil::info_if_enable_debug(&format!(
         "XOne initXLog got config: {}",
         xutil::to_json_string(&c)
@@ -33,42 +33,26 @@ pub fn init_xlog() -> Result<(), crate::error::XOneError> {
 
 /// 根据配置初始化日志
 fn init_xlog_by_config(c: &XLogConfig) -> Result<(), crate::error::XOneError> {
-    // 确保日志目录存在
-    if !xutil::dir_exist(&c.path) {
-        std::fs::create_dir_all(&c.path).map_err(|e| {
-            crate::error::XOneError::Log(format!(
-                "create log dir failed, path=[{}], err=[{e}]",
-                c.path
-            ))
-        })?;
-    }
-
-    // 创建文件 appender
-    let log_filename = format!("{}.log", c.name);
-    let file_appender = tracing_appender::rolling::daily(&c.path, &log_filename);
+    // 限流与采样策略（参数非法时直接返回错误）
+    let throttle_policy = super::throttle::ThrottlePolicy::from_config(c)?;
 
-    // 创建异步文件写入器
-    let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
+    // 编译脱敏策略（正则非法时直接返回错误）
+    let redact_policy = super::redact::RedactPolicy::from_config(&c.redact)?;
 
-    // 将 guard 存入全局状态，由 shutdown_xlog 统一管理生命周期
-    let mut store = guard_store().lock();
-    *store = Some(guard);
+    // 按请求开启 DEBUG 的签名策略（未启用时为 None，参数非法时直接返回错误）
+    let request_debug_policy =
+        super::request_debug::RequestDebugPolicy::from_config(&c.request_debug)?;
 
-    // 解析日志级别
-    let level_filter = c.level.as_str();
+    // 日志时间戳与切割周期使用配置的时区
+    let timezone = LogTimezone::parse(&c.timezone)?;
 
-    // 构建 env filter
-    let env_filter = EnvFilter::try_new(level_filter).unwrap_or_else(|e| {
-        xutil::warn_if_enable_debug(&format!(
-            "XLog invalid level filter [{level_filter}], fallback to info, err=[{e}]"
-        ));
-        EnvFilter::new("info")
-    });
+    // 按 XLog.Output 创建日志文件与 stdout / stderr 输出，日志目录无法创建时回退为 stdout。
+    // 主输出各自附加可重载的 XLog.Level 过滤（请求携带签名有效的 DEBUG 头时放行该请求的 DEBUG 日志），
+    // 文件 sink 与 OTLP 只使用各自的级别过滤
+    let mut outputs = super::output::build_outputs(c, timezone)?;
 
-    // 文件输出：JSON 格式（自动注入 trace_id）
-    let file_layer = tracing_subscriber::fmt::layer()
-        .event_format(OtelJsonFormat)
-        .with_writer(non_blocking);
+    // 将 guard 存入全局状态，由 shutdown_xlog 统一管理生命周期
+    *guard_store().lock() = outputs.guards;
 
     fn init_err(e: impl std::fmt::Display) -> crate::error::XOneError {
         crate::error::XOneError::Log(format!("init tracing subscriber failed, err=[{e}]"))
@@ -77,22 +61,24 @@ fn init_xlog_by_config(c: &XLogConfig) -> Result<(), crate::error::XOneError> {
     // Span KV 字段收集 Layer
     let kv_layer = super::kv_layer::KvLayer;
 
-    // 控制台输出（Option<Layer> 支持条件启用，None 等同于无 Layer）
+    // 控制台输出（仅 Output 为 file 时生效，Option<Layer> 支持条件启用，None 等同于无 Layer）
     use tracing_subscriber::Layer as _;
     let console_layer: Option<Box<dyn tracing_subscriber::Layer<_> + Send + Sync>> =
-        if c.console {
+        if c.console && outputs.output == LogOutput::File {
             if c.console_format_is_raw {
                 Some(
                     tracing_subscriber::fmt::layer()
-                        .event_format(OtelJsonFormat)
+                        .event_format(OtelJsonFormat.with_timezone(timezone))
                         .with_writer(std::io::stdout as fn() -> std::io::Stdout)
+                        .with_filter(outputs.level_filters.build())
                         .boxed(),
                 )
             } else {
                 Some(
                     tracing_subscriber::fmt::layer()
-                        .event_format(OtelConsoleFormat)
+                        .event_format(OtelConsoleFormat.with_timezone(timezone))
                         .with_writer(std::io::stdout as fn() -> std::io::Stdout)
+                        .with_filter(outputs.level_filters.build())
                         .boxed(),
                 )
             }
@@ -100,17 +86,56 @@ fn init_xlog_by_config(c: &XLogConfig) -> Result<(), crate::error::XOneError> {
             None
         };
 
+    // OTLP 日志导出（未启用时为 None）
+    #[cfg(feature = "log-otlp")]
+    let otlp_layer = super::otlp::build_layer(&c.otlp)?;
+    #[cfg(not(feature = "log-otlp"))]
+    let otlp_layer: Option<Box<dyn tracing_subscriber::Layer<_> + Send + Sync>> = if c.otlp.enable {
+        return Err(crate::error::XOneError::Log(format!(
+            "{XLOG_CONFIG_KEY}.Otlp requires the log-otlp feature"
+        )));
+    } else {
+        None
+    };
+
+    super::redact::set_policy(redact_policy);
+    super::request_debug::set_policy(request_debug_policy);
+
+    // 限流与采样（未启用时不加入 Layer，避免额外开销）
+    let throttle_layer = throttle_policy
+        .is_enabled()
+        .then(|| super::throttle::ThrottleLayer::new(throttle_policy));
+
     tracing_subscriber::registry()
-        .with(env_filter)
+        .with(throttle_layer.clone())
         .with(kv_layer)
-        .with(file_layer)
+        .with(outputs.layers)
         .with(console_layer)
+        .with(otlp_layer)
         .try_init()
         .map_err(init_err)?;
 
+    // 记录重载句柄，并在配置热更新修改 XLog.Level / XLog.Targets 时重新应用
+    outputs.level_filters.install();
+    xconfig::subscribe(XLOG_CONFIG_KEY, super::level::on_config_changed);
+
+    // 定期输出限流丢弃汇总
+    if let Some(layer) = &throttle_layer {
+        super::throttle::start_summary(layer)?;
+    }
+
+    // 按 MaxAge / MaxFiles / MaxTotalSize 定期清理切割文件（不写文件时无需清理）
+    if !outputs.filenames.is_empty() {
+        super::retention::start_retention(
+            &c.path,
+            outputs.filenames.clone(),
+            super::retention::RetentionPolicy::from_config(c),
+        )?;
+    }
+
     xutil::info_if_enable_debug(&format!(
-        "XOne initXLog success, log file: {}/{}",
-        c.path, log_filename
+        "XOne initXLog success, output: {:?}, log dir: {}, files: {:?}",
+        outputs.output, c.path, outputs.filenames
     ));
 
     Ok(())
@@ -118,18 +143,22 @@ fn init_xlog_by_config(c: &XLogConfig) -> Result<(), crate::error::XOneError> {
 
 /// 关闭日志系统
 ///
+/// 发送 OTLP 队列中剩余的日志，停止限流汇总与过期日志清理线程，
 /// 释放异步写入器 guard，确保缓冲区中的日志全部刷写到文件。
 /// order 设为 `i32::MAX`，保证在所有其他 hook 之后执行，
 /// 使其他模块（xtrace、xcache、xorm 等）的 shutdown 过程仍可使用日志。
 pub fn shutdown_xlog() -> Result<(), crate::error::XOneError> {
-    let guard = {
-        let mut store = guard_store().lock();
-        store.take()
-    };
+    #[cfg(feature = "log-otlp")]
+    super::otlp::shutdown()?;
+    super::throttle::stop_summary()?;
+    super::retention::stop_retention()?;
+    super::level::stop_revert_worker()?;
+
+    let guards = std::mem::take(&mut *guard_store().lock());
 
-    if let Some(guard) = guard {
+    if !guards.is_empty() {
         xutil::info_if_enable_debug("XLog shutdown: flushing and closing log writer");
-        drop(guard);
+        drop(guards);
     }
 
     xutil::info_if_enable_debug("XLog shutdown complete");
@@ -44,6 +44,10 @@ where
             let mut fields = Map::new();
             let mut visitor = JsonFieldVisitor::new(&mut fields);
             attrs.record(&mut visitor);
+            // 请求 DEBUG 的签名 token 不写入日志
+            if let Some(value) = fields.get_mut(super::request_debug::REQUEST_DEBUG_FIELD) {
+                *value = Value::Bool(true);
+            }
             span.extensions_mut().insert(SpanKvFields(fields));
         }
     }
@@ -0,0 +1,293 @@
+//! 运行时日志级别调整
+//!
+//! 日志系统初始化时为主日志文件、控制台及 stdout / stderr 输出各附加一个可重载的
+//! `EnvFilter`（per-layer 过滤，文件 sink 与 OTLP 使用各自的级别，不受影响），运行期间可通过
+//! [`set_level`] 统一修改这些过滤器的指令（语法同 `RUST_LOG`，如 `"info,sqlx=debug"`），
+//! 或通过 [`set_level_for`] 临时调整并在 TTL 到期后自动恢复。
+//! 配置热更新导致 `XLog.Level` 或 `XLog.Targets` 变化时自动重新应用。
+//!
+//! ```ignore
+//! use std::time::Duration;
+//!
+//! x_one::xlog::set_level("info,sqlx=debug")?;
+//! // 临时开启 debug，10 分钟后恢复为之前的级别
+//! x_one::xlog::set_level_for("debug", Duration::from_secs(600))?;
+//! assert_eq!(x_one::xlog::get_level().as_deref(), Some("debug"));
+//! ```
+
+use super::config::XLogConfig;
+use super::request_debug::RequestDebugLayer;
+use crate::error::XOneError;
+use crate::xutil;
+use parking_lot::{Condvar, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tracing_subscriber::{EnvFilter, reload};
+
+/// 过滤器重载句柄
+///
+/// 擦除 `reload::Handle<EnvFilter, S>` 的 Subscriber 类型，使挂在不同位置的过滤器可统一重载。
+pub struct FilterHandle(Box<dyn Fn(EnvFilter) -> Result<(), reload::Error> + Send + Sync>);
+
+impl<S: 'static> From<reload::Handle<EnvFilter, S>> for FilterHandle {
+    fn from(handle: reload::Handle<EnvFilter, S>) -> Self {
+        Self(Box::new(move |filter| handle.reload(filter)))
+    }
+}
+
+/// 主输出的级别过滤器：可重载的 `EnvFilter`，按请求 DEBUG 包装
+pub(crate) type OutputFilter<S> = RequestDebugLayer<reload::Layer<EnvFilter, S>>;
+
+/// 主输出级别过滤器的构建器
+///
+/// 为每个主输出 Layer 创建独立的 [`OutputFilter`]，并收集重载句柄供 [`set_level`] 统一调整。
+pub(crate) struct OutputFilters {
+    directives: String,
+    request_debug: bool,
+    handles: Vec<FilterHandle>,
+}
+
+impl OutputFilters {
+    /// 校验过滤指令，非法时返回错误
+    pub(crate) fn new(directives: &str, request_debug: bool) -> Result<Self, XOneError> {
+        parse_filter(directives)?;
+        Ok(Self {
+            directives: directives.to_string(),
+            request_debug,
+            handles: Vec::new(),
+        })
+    }
+
+    /// 为一个输出 Layer 创建级别过滤器
+    pub(crate) fn build<S: 'static>(&mut self) -> OutputFilter<S> {
+        // 指令已在 new 中校验
+        let (filter, handle) = reload::Layer::new(EnvFilter::new(&self.directives));
+        self.handles.push(handle.into());
+        RequestDebugLayer::new(filter, self.request_debug)
+    }
+
+    /// 记录所有重载句柄，开启运行时调级
+    pub(crate) fn install(self) {
+        install_all(self.handles, &self.directives);
+    }
+}
+
+/// 级别状态
+struct LevelState {
+    handles: Vec<FilterHandle>,
+    /// 持久级别（临时调整到期后恢复为该级别）
+    base: String,
+    /// 当前生效的级别
+    current: String,
+    /// 临时调整的恢复时间，没有进行中的临时调整时为 `None`
+    revert_at: Option<Instant>,
+}
+
+fn level_store() -> &'static Mutex<Option<LevelState>> {
+    static INSTANCE: OnceLock<Mutex<Option<LevelState>>> = OnceLock::new();
+    INSTANCE.get_or_init(|| Mutex::new(None))
+}
+
+/// 恢复时间变化时唤醒恢复线程
+fn revert_signal() -> &'static Condvar {
+    static INSTANCE: OnceLock<Condvar> = OnceLock::new();
+    INSTANCE.get_or_init(Condvar::new)
+}
+
+/// 恢复线程句柄
+struct RevertWorker {
+    stop: Arc<AtomicBool>,
+    thread: std::thread::JoinHandle<()>,
+}
+
+fn revert_worker_store() -> &'static Mutex<Option<RevertWorker>> {
+    static INSTANCE: OnceLock<Mutex<Option<RevertWorker>>> = OnceLock::new();
+    INSTANCE.get_or_init(|| Mutex::new(None))
+}
+
+/// 修改日志级别（持久生效，覆盖进行中的临时调整）
+///
+/// 指令无法解析或日志系统未初始化时返回错误，原级别保持不变。
+pub fn set_level(directives: &str) -> Result<(), XOneError> {
+    let directives = directives.trim();
+    parse_filter(directives)?;
+    let mut store = level_store().lock();
+    let state = initialized(&mut store)?;
+    reload_filter(state, directives)?;
+    state.base = directives.to_string();
+    state.current = directives.to_string();
+    state.revert_at = None;
+    drop(store);
+    revert_signal().notify_all();
+    xutil::info_if_enable_debug(&format!("XLog level changed to [{directives}]"));
+    Ok(())
+}
+
+/// 临时修改日志级别，`ttl` 到期后恢复为调整前的持久级别
+///
+/// 到期前再次调用 [`set_level`] 或 [`set_level_for`] 时，本次的自动恢复被取消或替换为新的到期时间。
+pub fn set_level_for(directives: &str, ttl: Duration) -> Result<(), XOneError> {
+    let directives = directives.trim();
+    parse_filter(directives)?;
+    start_revert_worker()?;
+    let mut store = level_store().lock();
+    let state = initialized(&mut store)?;
+    reload_filter(state, directives)?;
+    state.current = directives.to_string();
+    state.revert_at = Some(Instant::now() + ttl);
+    drop(store);
+    revert_signal().notify_all();
+    xutil::info_if_enable_debug(&format!(
+        "XLog level temporarily changed to [{directives}], revert after [{ttl:?}]"
+    ));
+    Ok(())
+}
+
+/// 当前生效的日志级别，日志系统未初始化时为 `None`
+pub fn get_level() -> Option<String> {
+    level_store().lock().as_ref().map(|s| s.current.clone())
+}
+
+/// 记录单个过滤器重载句柄（测试中可传入自建句柄）
+#[doc(hidden)]
+pub fn install(handle: impl Into<FilterHandle>, level: &str) {
+    install_all(vec![handle.into()], level);
+}
+
+/// 记录日志系统各输出的过滤器重载句柄
+fn install_all(handles: Vec<FilterHandle>, level: &str) {
+    *level_store().lock() = Some(LevelState {
+        handles,
+        base: level.to_string(),
+        current: level.to_string(),
+        revert_at: None,
+    });
+    revert_signal().notify_all();
+}
+
+/// 配置中的 `XLog.Level` 或 `XLog.Targets` 变化时重新应用
+///
+/// 编译出的过滤指令与当前持久级别相同时（如仅修改了其他 XLog 配置）不做处理。
+pub(crate) fn on_config_changed(change: &crate::xconfig::ConfigChange) {
+    let config = match change.new.clone().map(serde_yaml::from_value::<XLogConfig>) {
+        Some(Ok(c)) => c,
+        Some(Err(e)) => {
+            xutil::warn_if_enable_debug(&format!(
+                "XLog parse reloaded config failed, keep level, err=[{e}]"
+            ));
+            return;
+        }
+        None => XLogConfig::default(),
+    };
+    let directives = match config.filter_directives() {
+        Ok(d) => d,
+        Err(e) => {
+            xutil::warn_if_enable_debug(&format!("XLog apply level from config failed, err=[{e}]"));
+            return;
+        }
+    };
+    let unchanged = level_store()
+        .lock()
+        .as_ref()
+        .is_some_and(|s| s.base == directives);
+    if unchanged {
+        return;
+    }
+    if let Err(e) = set_level(&directives) {
+        xutil::warn_if_enable_debug(&format!(
+            "XLog apply level [{directives}] from config failed, err=[{e}]"
+        ));
+    }
+}
+
+/// 停止恢复线程（进行中的临时调整不再自动恢复）
+pub(crate) fn stop_revert_worker() -> Result<(), XOneError> {
+    let worker = revert_worker_store().lock().take();
+    if let Some(worker) = worker {
+        // 持有状态锁时设置标记，避免恢复线程在检查标记与等待之间错过唤醒
+        {
+            let _store = level_store().lock();
+            worker.stop.store(true, Ordering::Relaxed);
+            revert_signal().notify_all();
+        }
+        worker
+            .thread
+            .join()
+            .map_err(|_| XOneError::Log("log level revert thread panicked".to_string()))?;
+        xutil::info_if_enable_debug("XLog level revert worker stopped");
+    }
+    Ok(())
+}
+
+// ---- 以下为私有实现 ----
+
+fn parse_filter(directives: &str) -> Result<EnvFilter, XOneError> {
+    EnvFilter::try_new(directives)
+        .map_err(|e| XOneError::Log(format!("invalid log level [{directives}], err=[{e}]")))
+}
+
+fn initialized(store: &mut Option<LevelState>) -> Result<&mut LevelState, XOneError> {
+    store
+        .as_mut()
+        .ok_or_else(|| XOneError::Log("set log level failed, xlog not initialized".to_string()))
+}
+
+/// 使用同一指令重载所有输出的过滤器（指令需已校验）
+fn reload_filter(state: &LevelState, directives: &str) -> Result<(), XOneError> {
+    for handle in &state.handles {
+        (handle.0)(parse_filter(directives)?)
+            .map_err(|e| XOneError::Log(format!("reload log filter failed, err=[{e}]")))?;
+    }
+    Ok(())
+}
+
+/// 启动唯一的恢复线程（幂等）
+fn start_revert_worker() -> Result<(), XOneError> {
+    let mut worker = revert_worker_store().lock();
+    if worker.is_none() {
+        let stop = Arc::new(AtomicBool::new(false));
+        let loop_stop = stop.clone();
+        let thread = std::thread::Builder::new()
+            .name("xlog-level-revert".to_string())
+            .spawn(move || revert_loop(&loop_stop))
+            .map_err(|e| XOneError::Log(format!("start level revert worker failed, err=[{e}]")))?;
+        *worker = Some(RevertWorker { stop, thread });
+    }
+    Ok(())
+}
+
+/// 恢复线程主循环：等待最近的恢复时间，到期时在持有状态锁的情况下恢复持久级别，
+/// 期间的新调整会替换或清除恢复时间，不会被旧的到期恢复覆盖
+fn revert_loop(stop: &AtomicBool) {
+    let mut store = level_store().lock();
+    while !stop.load(Ordering::Relaxed) {
+        match store.as_ref().and_then(|s| s.revert_at) {
+            None => revert_signal().wait(&mut store),
+            Some(at) if Instant::now() < at => {
+                revert_signal().wait_until(&mut store, at);
+            }
+            Some(_) => {
+                if let Some(state) = store.as_mut() {
+                    revert(state);
+                }
+            }
+        }
+    }
+}
+
+/// 恢复为持久级别
+fn revert(state: &mut LevelState) {
+    state.revert_at = None;
+    let base = state.base.clone();
+    let result = reload_filter(state, &base);
+    match result {
+        Ok(()) => {
+            state.current = base.clone();
+            xutil::info_if_enable_debug(&format!("XLog level reverted to [{base}]"));
+        }
+        Err(e) => {
+            xutil::warn_if_enable_debug(&format!("XLog revert level to [{base}] failed, err=[{e}]"))
+        }
+    }
+}
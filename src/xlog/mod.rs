@@ -1,16 +1,33 @@
 //! xlog - 日志模块
 //!
 //! 基于 `tracing` + `tracing-subscriber` 实现，
-//! 提供 JSON 格式文件日志、控制台彩色输出、异步写入等功能。
+//! 提供 JSON / ECS / GCP / logfmt 等格式的文件或 stdout / stderr 日志、按级别分流的额外文件 sink、控制台彩色输出、异步写入、文件切割与压缩、过期日志清理、运行时级别调整、敏感字段脱敏、限流采样、OTLP 日志导出、按请求开启 DEBUG 日志等功能。
 
 pub mod config;
 pub mod console;
+pub mod format;
 pub mod init;
 pub mod kv_layer;
+pub mod level;
 pub mod otel_fmt;
+#[cfg(feature = "log-otlp")]
+pub mod otlp;
+pub mod output;
+pub mod redact;
+pub mod request_debug;
+pub mod retention;
+pub mod rolling;
+pub mod sink;
+pub mod throttle;
+pub mod timezone;
 
-pub use config::{LogLevel, XLOG_CONFIG_KEY, XLogConfig};
+pub use config::{
+    LogFormat, LogLevel, LogOutput, LogSinkConfig, MaskMode, OtlpConfig, OtlpProtocol,
+    RedactConfig, RequestDebugConfig, XLOG_CONFIG_KEY, XLogConfig,
+};
 pub use kv_layer::SpanKvFields;
+pub use level::{get_level, set_level, set_level_for};
+pub use timezone::LogTimezone;
 
 use std::sync::atomic::{AtomicBool, Ordering};
 
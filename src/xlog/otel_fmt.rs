@@ -2,9 +2,11 @@
 //!
 //! 为 JSON 和控制台日志输出自动注入 `trace_id` 和 `span_id`。
 //! 当存在活跃的 OpenTelemetry Span 时自动提取，否则省略。
+//! 输出前按 [`redact`](super::redact) 策略对字段脱敏。
 
 use std::fmt;
 
+use super::timezone::LogTimezone;
 use opentelemetry::trace::TraceContextExt;
 use serde_json::{Map, Value};
 use tracing::field::{Field, Visit};
@@ -85,8 +87,11 @@ impl<'a> Visit for JsonFieldVisitor<'a> {
 
 /// 从 span extensions 和 event 中收集所有字段
 ///
-/// 先从外层到内层 span 的 KV 字段合并，再覆盖 event 字段。
-fn collect_fields<S, N>(ctx: &FmtContext<'_, S, N>, event: &Event<'_>) -> Map<String, Value>
+/// 先从外层到内层 span 的 KV 字段合并，再覆盖 event 字段，最后按全局脱敏策略脱敏。
+pub(crate) fn collect_fields<S, N>(
+    ctx: &FmtContext<'_, S, N>,
+    event: &Event<'_>,
+) -> Map<String, Value>
 where
     S: Subscriber + for<'a> LookupSpan<'a>,
     N: for<'a> FormatFields<'a> + 'static,
@@ -104,6 +109,7 @@ where
     }
     let mut visitor = JsonFieldVisitor::new(&mut fields);
     event.record(&mut visitor);
+    super::redact::policy().redact_fields(&mut fields);
     fields
 }
 
@@ -112,7 +118,27 @@ where
 /// 在标准 JSON 日志字段基础上，自动注入 `trace_id` 和 `span_id`。
 /// 输出格式与 `tracing-subscriber` 内置 JSON 格式兼容，
 /// 额外增加 `trace_id`、`span_id` 顶层字段。
-pub struct OtelJsonFormat;
+/// `timestamp` 为所配置时区的 RFC3339 时间（默认 UTC）。
+///
+/// 仍可按单元结构体的写法使用：`.event_format(OtelJsonFormat)` 等同于默认配置。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OtelJsonFormat {
+    timezone: LogTimezone,
+}
+
+/// 默认配置的 [`OtelJsonFormat`]，兼容 `.event_format(OtelJsonFormat)` 写法
+#[allow(non_upper_case_globals)]
+pub const OtelJsonFormat: OtelJsonFormat = OtelJsonFormat {
+    timezone: LogTimezone::Utc,
+};
+
+impl OtelJsonFormat {
+    /// 使用指定时区输出时间戳
+    pub fn with_timezone(mut self, timezone: LogTimezone) -> Self {
+        self.timezone = timezone;
+        self
+    }
+}
 
 impl<S, N> FormatEvent<S, N> for OtelJsonFormat
 where
@@ -130,11 +156,7 @@ where
         // 直接写 JSON，避免中间 Map 分配和 serde_json::to_string
         writer.write_str("{\"timestamp\":\"")?;
         let now = chrono::Utc::now();
-        write!(
-            writer,
-            "{}",
-            now.to_rfc3339_opts(chrono::SecondsFormat::Micros, true)
-        )?;
+        writer.write_str(&self.timezone.format_rfc3339(now))?;
 
         writer.write_str("\",\"level\":\"")?;
         writer.write_str(meta.level().as_str())?;
@@ -197,7 +219,42 @@ where
 /// 带 OpenTelemetry trace 上下文的控制台格式化器
 ///
 /// 以彩色文本格式输出日志，自动在时间戳和消息之间插入 `trace_id`。
-pub struct OtelConsoleFormat;
+/// 时间戳为所配置时区的本地时间（默认系统本地时区）。
+/// 关闭 ANSI 颜色后可用于文本格式的文件输出。
+///
+/// 仍可按单元结构体的写法使用：`.event_format(OtelConsoleFormat)` 等同于默认配置。
+#[derive(Debug, Clone, Copy)]
+pub struct OtelConsoleFormat {
+    timezone: LogTimezone,
+    ansi: bool,
+}
+
+/// 默认配置的 [`OtelConsoleFormat`]，兼容 `.event_format(OtelConsoleFormat)` 写法
+#[allow(non_upper_case_globals)]
+pub const OtelConsoleFormat: OtelConsoleFormat = OtelConsoleFormat {
+    timezone: LogTimezone::Local,
+    ansi: true,
+};
+
+impl OtelConsoleFormat {
+    /// 使用指定时区输出时间戳
+    pub fn with_timezone(mut self, timezone: LogTimezone) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// 设置是否输出 ANSI 颜色（默认 true）
+    pub fn with_ansi(mut self, ansi: bool) -> Self {
+        self.ansi = ansi;
+        self
+    }
+}
+
+impl Default for OtelConsoleFormat {
+    fn default() -> Self {
+        OtelConsoleFormat
+    }
+}
 
 impl<S, N> FormatEvent<S, N> for OtelConsoleFormat
 where
@@ -239,7 +296,7 @@ where
             format!("{message} {}", extras.join(" "))
         };
 
-        let now = chrono::Local::now();
+        let now = self.timezone.to_local(chrono::Utc::now());
         let timestamp = now.format("%Y-%m-%d %H:%M:%S%.3f").to_string();
 
         let (trace_id, _) = get_otel_trace_ids();
@@ -249,13 +306,12 @@ where
             _ => String::new(),
         };
 
-        let line = super::console::format_console_line(
-            meta.level(),
-            &timestamp,
-            &full_message,
-            &trace_id,
-            &caller,
-        );
+        let format_line = if self.ansi {
+            super::console::format_console_line
+        } else {
+            super::console::format_plain_line
+        };
+        let line = format_line(meta.level(), &timestamp, &full_message, &trace_id, &caller);
         write!(writer, "{line}")
     }
 }
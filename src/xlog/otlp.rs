@@ -0,0 +1,594 @@
+//! OTLP 日志导出
+//!
+//! [`OtlpLogLayer`] 通过 `opentelemetry-appender-tracing` 将 tracing 事件桥接为
+//! OpenTelemetry logs SDK 的 LogRecord，并补充 `xlog_kv!` 注入的 Span 字段、按脱敏策略处理
+//! 字段与消息；trace 上下文由 SDK 从当前 OpenTelemetry Context 注入。
+//!
+//! [`OtlpLogProcessor`] 使用 SDK 的 `BatchLogProcessor` 按 `MaxBatchSize` / `ExportInterval`
+//! 批量调用 `opentelemetry-otlp` 的 `LogExporter`（http/protobuf、http/json 或 gRPC）。
+//!
+//! 队列满或发送失败时丢弃并计数，见 [`OtlpStats`]；启用 `metric` 特性时同时计入
+//! `xlog_otlp_exported_records_total` 与 `xlog_otlp_dropped_records_total{reason}` 计数器。
+
+use super::config::{OtlpConfig, OtlpProtocol, XLOG_CONFIG_KEY};
+use super::kv_layer::SpanKvFields;
+use super::redact::RedactPolicy;
+use crate::error::XOneError;
+use crate::{xconfig, xutil};
+use opentelemetry::logs::{AnyValue, LogRecord, Logger, LoggerProvider, Severity};
+use opentelemetry::trace::{SpanId, TraceFlags, TraceId};
+use opentelemetry::{InstrumentationScope, Key, KeyValue};
+use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
+use opentelemetry_otlp::{WithExportConfig, WithHttpConfig, WithTonicConfig};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::error::OTelSdkResult;
+use opentelemetry_sdk::logs::{
+    BatchConfigBuilder, BatchLogProcessor, LogBatch, LogExporter, LogProcessor, SdkLogRecord,
+    SdkLogger, SdkLoggerProvider,
+};
+use parking_lot::Mutex;
+use serde_json::{Map, Value};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::SystemTime;
+use tonic::metadata::{MetadataKey, MetadataMap, MetadataValue};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// 日志 Logger 的 instrumentation scope 名
+pub const SCOPE_NAME: &str = "x_one::xlog";
+
+/// 发送成功条数指标名
+pub const EXPORTED_METRIC: &str = "xlog_otlp_exported_records_total";
+
+/// 丢弃条数指标名（`reason` 为 `queue_full` 或 `export_failed`）
+pub const DROPPED_METRIC: &str = "xlog_otlp_dropped_records_total";
+
+/// 导出链路自身（HTTP / gRPC 客户端与 OpenTelemetry SDK）的日志不再导出，避免反馈循环
+const EXPORTER_DIRECTIVES: &str =
+    "hyper=off,h2=off,tonic=off,tower=off,reqwest=off,opentelemetry=off";
+
+/// 导出统计
+#[derive(Debug, Default)]
+pub struct OtlpStats {
+    exported: AtomicU64,
+    dropped_queue_full: AtomicU64,
+    dropped_export_failed: AtomicU64,
+}
+
+impl OtlpStats {
+    /// 发送成功的条数
+    pub fn exported(&self) -> u64 {
+        self.exported.load(Ordering::Relaxed)
+    }
+
+    /// 因队列满丢弃的条数
+    pub fn dropped_queue_full(&self) -> u64 {
+        self.dropped_queue_full.load(Ordering::Relaxed)
+    }
+
+    /// 因发送失败丢弃的条数
+    pub fn dropped_export_failed(&self) -> u64 {
+        self.dropped_export_failed.load(Ordering::Relaxed)
+    }
+
+    /// 丢弃的总条数
+    pub fn dropped(&self) -> u64 {
+        self.dropped_queue_full() + self.dropped_export_failed()
+    }
+}
+
+/// tracing 事件到 OpenTelemetry logs SDK 的桥接 Layer
+///
+/// 消息作为 body，事件字段与 Span KV 字段（按脱敏策略处理后）作为 attributes，
+/// 代码位置记为 `code.filepath` / `code.lineno`。
+pub struct OtlpLogLayer {
+    bridge: OpenTelemetryTracingBridge<XLogLoggerProvider, XLogLogger>,
+}
+
+impl OtlpLogLayer {
+    /// 从 LoggerProvider 创建 Layer
+    pub fn new(provider: &SdkLoggerProvider) -> Self {
+        Self {
+            bridge: OpenTelemetryTracingBridge::new(&XLogLoggerProvider(provider.clone())),
+        }
+    }
+}
+
+impl<S> Layer<S> for OtlpLogLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut fields = Map::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                if let Some(kv) = span.extensions().get::<SpanKvFields>() {
+                    for (k, v) in &kv.0 {
+                        fields.insert(k.clone(), v.clone());
+                    }
+                }
+            }
+        }
+        // 桥接 Layer 无法访问 Span，KV 字段经线程局部变量交给 XLogLogger::emit
+        EVENT_KV.with(|kv| *kv.borrow_mut() = fields);
+        self.bridge.on_event(event, ctx);
+    }
+}
+
+/// 批量发送的 LogProcessor
+///
+/// 在 SDK `BatchLogProcessor` 之上统计发送结果；待发送的日志达到 `MaxQueueSize` 时
+/// 新日志直接丢弃并计入 `queue_full`。
+///
+/// 待发送计数在日志进入 SDK 队列前增加、在导出器取出批次时减少，始终不小于 SDK 队列中的
+/// 日志数，因此 SDK 队列不会先于本处理器满而静默丢弃。关闭后 SDK 不再接收日志，
+/// 此时直接拒绝新日志并清零计数，避免计数残留。
+#[derive(Debug)]
+pub struct OtlpLogProcessor {
+    inner: BatchLogProcessor,
+    pending: Arc<AtomicUsize>,
+    max_queue_size: usize,
+    is_shutdown: AtomicBool,
+    stats: Arc<OtlpStats>,
+}
+
+impl OtlpLogProcessor {
+    /// 按配置创建处理器并启动发送线程，参数非法时返回错误
+    pub fn new(c: &OtlpConfig) -> Result<Self, XOneError> {
+        c.validate()?;
+        let pending = Arc::new(AtomicUsize::new(0));
+        let stats = Arc::new(OtlpStats::default());
+        let exporter = CountingExporter {
+            inner: build_exporter(c)?,
+            runtime: match c.protocol {
+                OtlpProtocol::Grpc => Some(grpc_runtime()?),
+                _ => None,
+            },
+            pending: pending.clone(),
+            stats: stats.clone(),
+        };
+        let batch_config = BatchConfigBuilder::default()
+            .with_max_queue_size(c.max_queue_size)
+            .with_max_export_batch_size(c.max_batch_size)
+            .with_scheduled_delay(c.export_interval)
+            .build();
+        let inner = BatchLogProcessor::builder(exporter)
+            .with_batch_config(batch_config)
+            .build();
+
+        Ok(Self {
+            inner,
+            pending,
+            max_queue_size: c.max_queue_size,
+            is_shutdown: AtomicBool::new(false),
+            stats,
+        })
+    }
+
+    /// 导出统计
+    pub fn stats(&self) -> Arc<OtlpStats> {
+        self.stats.clone()
+    }
+}
+
+impl LogProcessor for OtlpLogProcessor {
+    fn emit(&self, data: &mut SdkLogRecord, instrumentation: &InstrumentationScope) {
+        if self.is_shutdown.load(Ordering::Acquire) {
+            return;
+        }
+        let max = self.max_queue_size;
+        let accepted = self
+            .pending
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| {
+                (n < max).then_some(n + 1)
+            })
+            .is_ok();
+        if !accepted {
+            self.stats
+                .dropped_queue_full
+                .fetch_add(1, Ordering::Relaxed);
+            record_dropped("queue_full", 1);
+            return;
+        }
+        self.inner.emit(data, instrumentation);
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    fn shutdown(&self) -> OTelSdkResult {
+        self.is_shutdown.store(true, Ordering::Release);
+        let result = self.inner.shutdown();
+        // 关闭后未被导出器取出的日志随 SDK 发送线程一起丢弃
+        self.pending.store(0, Ordering::Relaxed);
+        result
+    }
+
+    fn set_resource(&self, resource: &Resource) {
+        self.inner.set_resource(resource);
+    }
+}
+
+/// 按配置初始化 OTLP 导出并返回带级别过滤的 Layer（未启用时返回 `None`）
+pub(crate) fn build_layer<S>(
+    c: &OtlpConfig,
+) -> Result<Option<Box<dyn Layer<S> + Send + Sync>>, XOneError>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    if !c.enable {
+        return Ok(None);
+    }
+    let directives = format!("{},{EXPORTER_DIRECTIVES}", c.filter_directives()?);
+    let filter = tracing_subscriber::EnvFilter::try_new(&directives).map_err(|e| {
+        XOneError::Log(format!(
+            "invalid {XLOG_CONFIG_KEY}.Otlp level filter [{directives}], err=[{e}]"
+        ))
+    })?;
+
+    let processor = OtlpLogProcessor::new(c)?;
+    let stats = processor.stats();
+    let resource = Resource::builder()
+        .with_service_name(xconfig::get_server_name())
+        .with_attribute(KeyValue::new(
+            "service.version",
+            xconfig::get_server_version(),
+        ))
+        .build();
+    let provider = SdkLoggerProvider::builder()
+        .with_log_processor(processor)
+        .with_resource(resource)
+        .build();
+    let layer = OtlpLogLayer::new(&provider).with_filter(filter).boxed();
+
+    shutdown()?;
+    *provider_store().lock() = Some(Installed { provider, stats });
+    xutil::info_if_enable_debug(&format!(
+        "XLog otlp exporter started, url=[{}], protocol=[{:?}]",
+        c.export_url(),
+        c.protocol
+    ));
+    Ok(Some(layer))
+}
+
+/// 当前 OTLP 导出的统计（未启用时返回 `None`）
+pub fn stats() -> Option<Arc<OtlpStats>> {
+    provider_store()
+        .lock()
+        .as_ref()
+        .map(|installed| installed.stats.clone())
+}
+
+/// 发送队列中剩余日志并停止导出
+pub(crate) fn shutdown() -> Result<(), XOneError> {
+    let installed = provider_store().lock().take();
+    if let Some(installed) = installed {
+        installed
+            .provider
+            .shutdown()
+            .map_err(|e| XOneError::Log(format!("shutdown otlp log exporter failed, err=[{e}]")))?;
+        xutil::info_if_enable_debug("XLog otlp exporter stopped");
+    }
+    Ok(())
+}
+
+// ---- 以下为私有实现 ----
+
+thread_local! {
+    /// 当前事件所在 Span 链上的 KV 字段
+    static EVENT_KV: RefCell<Map<String, Value>> = RefCell::new(Map::new());
+}
+
+/// 已安装的导出
+struct Installed {
+    provider: SdkLoggerProvider,
+    stats: Arc<OtlpStats>,
+}
+
+fn provider_store() -> &'static Mutex<Option<Installed>> {
+    static INSTANCE: OnceLock<Mutex<Option<Installed>>> = OnceLock::new();
+    INSTANCE.get_or_init(|| Mutex::new(None))
+}
+
+/// gRPC 导出使用的运行时，tonic 通道的后台任务与请求都在其上执行，进程内只创建一次
+fn grpc_runtime() -> Result<&'static tokio::runtime::Runtime, XOneError> {
+    static INSTANCE: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+    if let Some(runtime) = INSTANCE.get() {
+        return Ok(runtime);
+    }
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(1)
+        .thread_name("xlog-otlp-grpc")
+        .enable_all()
+        .build()
+        .map_err(|e| XOneError::Log(format!("build otlp grpc runtime failed, err=[{e}]")))?;
+    Ok(INSTANCE.get_or_init(|| runtime))
+}
+
+fn build_exporter(c: &OtlpConfig) -> Result<opentelemetry_otlp::LogExporter, XOneError> {
+    let invalid_header = |name: &str| {
+        XOneError::Log(format!(
+            "invalid {XLOG_CONFIG_KEY}.Otlp.Headers entry [{name}]"
+        ))
+    };
+    let exporter = match c.protocol {
+        OtlpProtocol::Grpc => {
+            let mut metadata = MetadataMap::new();
+            for (name, value) in &c.headers {
+                let key = MetadataKey::from_bytes(name.as_bytes());
+                let value = MetadataValue::try_from(value.as_str());
+                let (Ok(key), Ok(value)) = (key, value) else {
+                    return Err(invalid_header(name));
+                };
+                metadata.insert(key, value);
+            }
+            // tonic 在创建通道时需要 tokio 运行时上下文
+            let _guard = grpc_runtime()?.enter();
+            opentelemetry_otlp::LogExporter::builder()
+                .with_tonic()
+                .with_endpoint(c.collector_endpoint())
+                .with_timeout(c.timeout)
+                .with_metadata(metadata)
+                .build()
+        }
+        OtlpProtocol::HttpProtobuf | OtlpProtocol::HttpJson => {
+            let mut headers = HashMap::new();
+            for (name, value) in &c.headers {
+                if MetadataKey::<tonic::metadata::Ascii>::from_bytes(name.as_bytes()).is_err()
+                    || MetadataValue::<tonic::metadata::Ascii>::try_from(value.as_str()).is_err()
+                {
+                    return Err(invalid_header(name));
+                }
+                headers.insert(name.clone(), value.clone());
+            }
+            let protocol = match c.protocol {
+                OtlpProtocol::HttpJson => opentelemetry_otlp::Protocol::HttpJson,
+                _ => opentelemetry_otlp::Protocol::HttpBinary,
+            };
+            opentelemetry_otlp::LogExporter::builder()
+                .with_http()
+                .with_protocol(protocol)
+                .with_endpoint(c.export_url())
+                .with_timeout(c.timeout)
+                .with_headers(headers)
+                .build()
+        }
+    };
+    exporter.map_err(|e| XOneError::Log(format!("build otlp log exporter failed, err=[{e}]")))
+}
+
+/// 统计发送结果的 LogExporter
+#[derive(Debug)]
+struct CountingExporter {
+    inner: opentelemetry_otlp::LogExporter,
+    runtime: Option<&'static tokio::runtime::Runtime>,
+    pending: Arc<AtomicUsize>,
+    stats: Arc<OtlpStats>,
+}
+
+impl LogExporter for CountingExporter {
+    async fn export(&self, batch: LogBatch<'_>) -> OTelSdkResult {
+        let count = batch.iter().count();
+        // 日志已离开 BatchLogProcessor 的队列（关闭时计数可能已清零）
+        let _ = self
+            .pending
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| {
+                Some(n.saturating_sub(count))
+            });
+        let result = match self.runtime {
+            Some(runtime) => runtime.block_on(self.inner.export(batch)),
+            None => self.inner.export(batch).await,
+        };
+        let count = count as u64;
+        match &result {
+            Ok(()) => {
+                self.stats.exported.fetch_add(count, Ordering::Relaxed);
+                #[cfg(feature = "metric")]
+                crate::xmetric::counter_add(EXPORTED_METRIC, count, &[]);
+            }
+            Err(e) => {
+                self.stats
+                    .dropped_export_failed
+                    .fetch_add(count, Ordering::Relaxed);
+                record_dropped("export_failed", count);
+                xutil::warn_if_enable_debug(&format!(
+                    "XLog otlp export failed, dropped=[{count}], err=[{e}]"
+                ));
+            }
+        }
+        result
+    }
+
+    fn shutdown(&mut self) -> OTelSdkResult {
+        self.inner.shutdown()
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.inner.set_resource(resource);
+    }
+}
+
+/// 为桥接 Layer 提供 [`XLogLogger`] 的 LoggerProvider
+struct XLogLoggerProvider(SdkLoggerProvider);
+
+impl LoggerProvider for XLogLoggerProvider {
+    type Logger = XLogLogger;
+
+    fn logger_with_scope(&self, _scope: InstrumentationScope) -> XLogLogger {
+        XLogLogger(self.0.logger(SCOPE_NAME))
+    }
+}
+
+/// 补充 Span KV 字段并脱敏的 Logger
+struct XLogLogger(SdkLogger);
+
+impl Logger for XLogLogger {
+    type LogRecord = XLogRecord;
+
+    fn create_log_record(&self) -> XLogRecord {
+        let mut record = self.0.create_log_record();
+        record.set_timestamp(SystemTime::now());
+        XLogRecord {
+            record,
+            policy: super::redact::policy(),
+        }
+    }
+
+    fn emit(&self, mut record: XLogRecord) {
+        let fields = EVENT_KV.with(|kv| std::mem::take(&mut *kv.borrow_mut()));
+        for (k, v) in fields {
+            // 同名事件字段优先
+            let exists = record
+                .record
+                .attributes_iter()
+                .any(|(key, _)| key.as_str() == k);
+            if exists {
+                continue;
+            }
+            if let Some(v) = json_to_any_value(v) {
+                record.add_attribute(k, v);
+            }
+        }
+        self.0.emit(record.record);
+    }
+
+    fn event_enabled(&self, level: Severity, target: &str) -> bool {
+        self.0.event_enabled(level, target)
+    }
+}
+
+/// 写入时按脱敏策略处理 body 与 attributes 的 LogRecord
+struct XLogRecord {
+    record: SdkLogRecord,
+    policy: Arc<RedactPolicy>,
+}
+
+impl LogRecord for XLogRecord {
+    fn set_event_name(&mut self, name: &'static str) {
+        self.record.set_event_name(name);
+    }
+
+    fn set_target<T>(&mut self, target: T)
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.record.set_target(target);
+    }
+
+    fn set_timestamp(&mut self, timestamp: SystemTime) {
+        self.record.set_timestamp(timestamp);
+    }
+
+    fn set_observed_timestamp(&mut self, timestamp: SystemTime) {
+        self.record.set_observed_timestamp(timestamp);
+    }
+
+    fn set_severity_text(&mut self, text: &'static str) {
+        self.record.set_severity_text(text);
+    }
+
+    fn set_severity_number(&mut self, number: Severity) {
+        self.record.set_severity_number(number);
+    }
+
+    fn set_body(&mut self, body: AnyValue) {
+        let body = match body {
+            AnyValue::String(s) => match self.policy.redact_text(s.as_str()) {
+                Cow::Owned(redacted) => AnyValue::from(redacted),
+                Cow::Borrowed(_) => AnyValue::String(s),
+            },
+            other => other,
+        };
+        self.record.set_body(body);
+    }
+
+    fn add_attributes<I, K, V>(&mut self, attributes: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<Key>,
+        V: Into<AnyValue>,
+    {
+        for (k, v) in attributes {
+            self.add_attribute(k, v);
+        }
+    }
+
+    fn add_attribute<K, V>(&mut self, key: K, value: V)
+    where
+        K: Into<Key>,
+        V: Into<AnyValue>,
+    {
+        let key = key.into();
+        let mut fields = Map::new();
+        fields.insert(key.to_string(), any_value_to_json(value.into()));
+        self.policy.redact_fields(&mut fields);
+        if let Some(value) = fields
+            .into_iter()
+            .next()
+            .and_then(|(_, v)| json_to_any_value(v))
+        {
+            self.record.add_attribute(key, value);
+        }
+    }
+
+    fn set_trace_context(
+        &mut self,
+        trace_id: TraceId,
+        span_id: SpanId,
+        trace_flags: Option<TraceFlags>,
+    ) {
+        self.record
+            .set_trace_context(trace_id, span_id, trace_flags);
+    }
+}
+
+fn json_to_any_value(value: Value) -> Option<AnyValue> {
+    Some(match value {
+        Value::Null => return None,
+        Value::Bool(b) => AnyValue::Boolean(b),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => AnyValue::Int(i),
+            None => AnyValue::Double(n.as_f64().unwrap_or_default()),
+        },
+        Value::String(s) => AnyValue::from(s),
+        Value::Array(items) => AnyValue::ListAny(Box::new(
+            items.into_iter().filter_map(json_to_any_value).collect(),
+        )),
+        Value::Object(map) => AnyValue::Map(Box::new(
+            map.into_iter()
+                .filter_map(|(k, v)| json_to_any_value(v).map(|v| (Key::from(k), v)))
+                .collect(),
+        )),
+    })
+}
+
+fn any_value_to_json(value: AnyValue) -> Value {
+    match value {
+        AnyValue::Int(i) => Value::from(i),
+        AnyValue::Double(f) => Value::from(f),
+        AnyValue::String(s) => Value::String(s.to_string()),
+        AnyValue::Boolean(b) => Value::Bool(b),
+        AnyValue::Bytes(bytes) => Value::String(String::from_utf8_lossy(&bytes).into_owned()),
+        AnyValue::ListAny(items) => {
+            Value::Array(items.into_iter().map(any_value_to_json).collect())
+        }
+        AnyValue::Map(map) => Value::Object(
+            map.into_iter()
+                .map(|(k, v)| (k.to_string(), any_value_to_json(v)))
+                .collect(),
+        ),
+        _ => Value::Null,
+    }
+}
+
+#[cfg_attr(not(feature = "metric"), allow(unused_variables))]
+fn record_dropped(reason: &str, count: u64) {
+    #[cfg(feature = "metric")]
+    crate::xmetric::counter_add(DROPPED_METRIC, count, &[("reason", reason)]);
+}
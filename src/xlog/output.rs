@@ -0,0 +1,132 @@
+//! 日志输出目标
+//!
+//! 按 `XLog.Output` 创建日志文件（主日志文件与各文件 sink）和 stdout / stderr 输出 Layer。
+//! 主日志文件与 stdout / stderr 各自附加 `XLog.Level` 级别过滤，文件 sink 仅使用自身的级别过滤。
+//! 需要写文件但日志目录或文件无法创建时（如容器只读文件系统），输出警告并回退为仅输出到 stdout，
+//! 不中断启动。
+
+use super::config::{LogOutput, XLogConfig};
+use super::level::OutputFilters;
+use super::rolling::{RollingFileWriter, RotationPolicy};
+use super::timezone::LogTimezone;
+use crate::error::XOneError;
+use crate::xutil;
+use tracing::Subscriber;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::Layer;
+use tracing_subscriber::registry::LookupSpan;
+
+/// 日志输出 Layer 及其资源
+pub struct LogOutputs<S> {
+    /// 各输出的 Layer
+    pub layers: Vec<Box<dyn Layer<S> + Send + Sync>>,
+    /// 异步写入器 guard，drop 时刷新并关闭输出
+    pub guards: Vec<WorkerGuard>,
+    /// 日志文件名（主日志文件在前），不写文件时为空
+    pub filenames: Vec<String>,
+    /// 实际生效的输出目标（回退后为 `Stdout`）
+    pub output: LogOutput,
+    /// 回退为 stdout 的原因
+    pub fallback: Option<String>,
+    /// `XLog.Level` 级别过滤器构建器（控制台输出复用，初始化完成后开启运行时调级）
+    pub(crate) level_filters: OutputFilters,
+}
+
+/// 按配置创建日志输出
+///
+/// 配置非法（级别过滤、sink 名称、切割参数）时返回错误；
+/// 日志目录或文件无法创建时回退为 stdout，原因记录在 [`LogOutputs::fallback`]。
+pub fn build_outputs<S>(c: &XLogConfig, timezone: LogTimezone) -> Result<LogOutputs<S>, XOneError>
+where
+    S: Subscriber + for<'a> LookupSpan<'a> + 'static,
+{
+    let mut outputs = LogOutputs {
+        layers: Vec::new(),
+        guards: Vec::new(),
+        filenames: Vec::new(),
+        output: c.output,
+        fallback: None,
+        level_filters: OutputFilters::new(&c.filter_directives()?, c.request_debug.enable)?,
+    };
+
+    if c.output.writes_file() {
+        // 先校验配置，配置错误不回退
+        let rotation = RotationPolicy::from_config(c)?;
+        for sink in c.file_sinks()? {
+            sink.filter_directives()?;
+        }
+
+        match build_files(c, timezone, rotation, &mut outputs.level_filters) {
+            Ok(files) => {
+                outputs.layers.extend(files.layers);
+                outputs.guards.extend(files.guards);
+                outputs.filenames = files.filenames;
+            }
+            Err(e) => {
+                xutil::warn_if_enable_debug(&format!(
+                    "XLog write log files failed, fallback to stdout, path=[{}], err=[{e}]",
+                    c.path
+                ));
+                outputs.output = LogOutput::Stdout;
+                outputs.fallback = Some(e.to_string());
+            }
+        }
+    }
+
+    let stream = match outputs.output {
+        LogOutput::File => None,
+        LogOutput::Stdout | LogOutput::Both => {
+            Some(tracing_appender::non_blocking(std::io::stdout()))
+        }
+        LogOutput::Stderr => Some(tracing_appender::non_blocking(std::io::stderr())),
+    };
+    if let Some((writer, guard)) = stream {
+        let layer = super::format::fmt_layer(c.format, timezone, writer)
+            .with_filter(outputs.level_filters.build());
+        outputs.layers.push(layer.boxed());
+        outputs.guards.push(guard);
+    }
+    Ok(outputs)
+}
+
+// ---- 以下为私有实现 ----
+
+/// 创建日志目录、主日志文件与各文件 sink
+fn build_files<S>(
+    c: &XLogConfig,
+    timezone: LogTimezone,
+    rotation: RotationPolicy,
+    level_filters: &mut OutputFilters,
+) -> Result<super::sink::FileSinks<S>, XOneError>
+where
+    S: Subscriber + for<'a> LookupSpan<'a> + 'static,
+{
+    if !xutil::dir_exist(&c.path) {
+        std::fs::create_dir_all(&c.path).map_err(|e| {
+            XOneError::Log(format!(
+                "create log dir failed, path=[{}], err=[{e}]",
+                c.path
+            ))
+        })?;
+    }
+
+    // 主日志文件：按周期 / 大小切割，异步写入
+    let log_filename = format!("{}.log", c.name);
+    let appender = RollingFileWriter::new(&c.path, &log_filename, rotation)?;
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+    let main_layer =
+        super::format::fmt_layer(c.format, timezone, writer).with_filter(level_filters.build());
+
+    // 额外文件 sink：各自的级别过滤与格式，切割设置与主日志文件相同
+    let sinks = super::sink::build_file_sinks(c, timezone)?;
+
+    let mut files = super::sink::FileSinks {
+        layers: vec![main_layer.boxed()],
+        guards: vec![guard],
+        filenames: vec![log_filename],
+    };
+    files.layers.extend(sinks.layers);
+    files.guards.extend(sinks.guards);
+    files.filenames.extend(sinks.filenames);
+    Ok(files)
+}
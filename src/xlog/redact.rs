@@ -0,0 +1,199 @@
+//! 日志敏感字段脱敏
+//!
+//! 按 `XLog.Redact` 配置编译脱敏策略，统一作用于：
+//!
+//! - `OtelJsonFormat` / `OtelConsoleFormat` 输出的事件字段与 `xlog_kv!` 注入的 Span 字段
+//! - XAxum 访问日志中间件记录的 header 与请求 body
+//!
+//! 字段名匹配 `Keys` 时整个值被脱敏；所有字符串值（含消息内容）中匹配 `Values` 正则的片段被脱敏。
+//!
+//! ```
+//! use serde_json::json;
+//! use x_one::xlog::config::RedactConfig;
+//! use x_one::xlog::redact::RedactPolicy;
+//!
+//! let policy = RedactPolicy::from_config(&RedactConfig::default()).unwrap();
+//! let mut fields = json!({"user": "alice", "access_token": "abc"});
+//! policy.redact_value(&mut fields);
+//! assert_eq!(fields, json!({"user": "alice", "access_token": "***"}));
+//! ```
+
+use super::config::{MaskMode, RedactConfig, XLOG_CONFIG_KEY};
+use crate::error::XOneError;
+use crate::xutil::glob_match;
+use parking_lot::RwLock;
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::borrow::Cow;
+use std::sync::{Arc, OnceLock};
+
+/// 整体脱敏后的显示值
+pub const REDACTED: &str = "***";
+
+/// 编译后的脱敏策略
+#[derive(Debug, Clone)]
+pub struct RedactPolicy {
+    enable: bool,
+    /// 小写的字段名模式
+    keys: Vec<String>,
+    mask: MaskMode,
+    values: Vec<(Regex, MaskMode)>,
+}
+
+impl RedactPolicy {
+    /// 从配置编译脱敏策略，`Values` 中的正则非法时返回错误
+    pub fn from_config(c: &RedactConfig) -> Result<Self, XOneError> {
+        let values = c
+            .values
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                Regex::new(&rule.pattern)
+                    .map(|re| (re, rule.mask))
+                    .map_err(|e| {
+                        XOneError::Log(format!(
+                            "invalid {XLOG_CONFIG_KEY}.Redact.Values[{i}].Pattern [{}], err=[{e}]",
+                            rule.pattern
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            enable: c.enable,
+            keys: c.keys.iter().map(|k| k.trim().to_lowercase()).collect(),
+            mask: c.mask,
+            values,
+        })
+    }
+
+    /// 字段名是否敏感（完整名或点分的最后一段匹配任一模式）
+    pub fn is_sensitive_key(&self, key: &str) -> bool {
+        if !self.enable {
+            return false;
+        }
+        let key = key.to_lowercase();
+        let last = key.rsplit('.').next().unwrap_or(&key);
+        self.keys
+            .iter()
+            .any(|p| glob_match(p, &key) || glob_match(p, last))
+    }
+
+    /// 对文本中匹配 `Values` 正则的片段脱敏，无匹配时不分配
+    pub fn redact_text<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if !self.enable {
+            return Cow::Borrowed(text);
+        }
+        let mut result = Cow::Borrowed(text);
+        for (re, mode) in &self.values {
+            if let Cow::Owned(replaced) = re.replace_all(&result, |caps: &regex::Captures| {
+                mask_value(&caps[0], *mode)
+            }) {
+                result = Cow::Owned(replaced);
+            }
+        }
+        result
+    }
+
+    /// 递归脱敏 JSON 值：敏感 key 的值整体脱敏，字符串按 `Values` 规则脱敏
+    pub fn redact_value(&self, value: &mut Value) {
+        if !self.enable {
+            return;
+        }
+        match value {
+            Value::Object(map) => self.redact_fields(map),
+            Value::Array(items) => items.iter_mut().for_each(|v| self.redact_value(v)),
+            Value::String(s) => {
+                if let Cow::Owned(redacted) = self.redact_text(s) {
+                    *s = redacted;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// 脱敏日志字段集合
+    pub fn redact_fields(&self, fields: &mut Map<String, Value>) {
+        if !self.enable {
+            return;
+        }
+        for (key, value) in fields.iter_mut() {
+            if self.is_sensitive_key(key) {
+                let masked = match &*value {
+                    Value::Null => continue,
+                    Value::String(s) => mask_value(s, self.mask),
+                    other => mask_value(&other.to_string(), self.mask),
+                };
+                *value = Value::String(masked);
+            } else {
+                self.redact_value(value);
+            }
+        }
+    }
+
+    /// 脱敏请求 / 响应 body：JSON 按字段脱敏，其他文本按 `Values` 规则脱敏
+    pub fn redact_body<'a>(&self, body: &'a str) -> Cow<'a, str> {
+        if !self.enable || body.is_empty() {
+            return Cow::Borrowed(body);
+        }
+        let trimmed = body.trim_start();
+        if (trimmed.starts_with('{') || trimmed.starts_with('['))
+            && let Ok(mut json) = serde_json::from_str::<Value>(body)
+        {
+            let original = json.clone();
+            self.redact_value(&mut json);
+            if json != original {
+                return Cow::Owned(json.to_string());
+            }
+            return Cow::Borrowed(body);
+        }
+        self.redact_text(body)
+    }
+}
+
+impl Default for RedactPolicy {
+    fn default() -> Self {
+        Self::from_config(&RedactConfig::default()).expect("default redact config is invalid")
+    }
+}
+
+fn policy_store() -> &'static RwLock<Arc<RedactPolicy>> {
+    static INSTANCE: OnceLock<RwLock<Arc<RedactPolicy>>> = OnceLock::new();
+    INSTANCE.get_or_init(|| RwLock::new(Arc::new(RedactPolicy::default())))
+}
+
+/// 当前生效的脱敏策略（日志系统初始化前为默认策略）
+pub fn policy() -> Arc<RedactPolicy> {
+    policy_store().read().clone()
+}
+
+/// 替换全局脱敏策略（`init_xlog` 按配置调用）
+pub fn set_policy(policy: RedactPolicy) {
+    *policy_store().write() = Arc::new(policy);
+}
+
+/// 按 [`MaskMode`] 脱敏单个值
+///
+/// ```
+/// use x_one::xlog::config::MaskMode;
+/// use x_one::xlog::redact::mask_value;
+///
+/// assert_eq!(mask_value("13800138000", MaskMode::Partial), "138****8000");
+/// assert_eq!(mask_value("secret", MaskMode::Full), "***");
+/// ```
+pub fn mask_value(value: &str, mode: MaskMode) -> String {
+    match mode {
+        MaskMode::Full => REDACTED.to_string(),
+        MaskMode::Partial => {
+            // 首部最多保留 3 个字符（约 30%），尾部最多保留 4 个字符（约 40%）
+            let chars: Vec<char> = value.chars().collect();
+            let n = chars.len();
+            let (head, tail) = ((n * 3 / 10).min(3), (n * 4 / 10).min(4));
+            if head == 0 || tail == 0 {
+                return REDACTED.to_string();
+            }
+            let head: String = chars[..head].iter().collect();
+            let tail: String = chars[n - tail..].iter().collect();
+            format!("{head}****{tail}")
+        }
+    }
+}
@@ -0,0 +1,390 @@
+//! 按请求开启 DEBUG 日志
+//!
+//! 请求携带签名有效的 `X-Debug-Log` 头时，xaxum 中间件为该请求创建带 [`REQUEST_DEBUG_FIELD`]
+//! 字段的 span；[`RequestDebugLayer`] 包装 `XLog.Level` 级别过滤，使该 span 及其子 span 内的
+//! DEBUG 日志不受 `XLog.Level` 限制，其他请求的日志级别不变。
+//! xhttp 出站请求通过 [`current_token`] 取得 token 并沿用同名请求头向下游传递。
+//!
+//! token 格式为 `{过期时间 unix 秒}.{base64url(HMAC-SHA256(Secret, 过期时间))}`，
+//! 由 [`sign_token`] 生成。
+
+use super::config::RequestDebugConfig;
+use crate::error::XOneError;
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use parking_lot::RwLock;
+use ring::hmac;
+use std::any::TypeId;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::Interest;
+use tracing::{Dispatch, Event, Level, Metadata, Subscriber};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, Filter, Layer};
+use tracing_subscriber::registry::{LookupSpan, Registry};
+
+/// 标记请求 DEBUG 的 span 字段名，字段值为签名 token
+///
+/// ```
+/// use x_one::xlog::request_debug::REQUEST_DEBUG_FIELD;
+///
+/// assert_eq!(REQUEST_DEBUG_FIELD, "xlog.debug");
+/// ```
+pub const REQUEST_DEBUG_FIELD: &str = "xlog.debug";
+
+/// 按请求开启 DEBUG 的校验策略
+pub struct RequestDebugPolicy {
+    key: hmac::Key,
+    header: String,
+    max_ttl: Duration,
+}
+
+impl RequestDebugPolicy {
+    /// 从配置创建策略，未启用时返回 `None`，参数非法时返回错误
+    pub fn from_config(c: &RequestDebugConfig) -> Result<Option<Self>, XOneError> {
+        if !c.enable {
+            return Ok(None);
+        }
+        c.validate()?;
+        Ok(Some(Self {
+            key: hmac::Key::new(hmac::HMAC_SHA256, c.secret.as_bytes()),
+            header: c.header.clone(),
+            max_ttl: c.max_ttl,
+        }))
+    }
+
+    /// 携带 token 的请求头名
+    pub fn header(&self) -> &str {
+        &self.header
+    }
+
+    /// 校验 token 签名与有效期
+    pub fn verify(&self, token: &str) -> bool {
+        let Some((expires, signature)) = token.trim().split_once('.') else {
+            return false;
+        };
+        let Ok(expires_at) = expires.parse::<u64>() else {
+            return false;
+        };
+        let now = unix_now();
+        if expires_at < now || expires_at > now.saturating_add(self.max_ttl.as_secs()) {
+            return false;
+        }
+        let Ok(signature) = URL_SAFE_NO_PAD.decode(signature) else {
+            return false;
+        };
+        hmac::verify(&self.key, expires.as_bytes(), &signature).is_ok()
+    }
+}
+
+impl std::fmt::Debug for RequestDebugPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestDebugPolicy")
+            .field("header", &self.header)
+            .field("max_ttl", &self.max_ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+/// 生成有效期为 `ttl` 的签名 token
+///
+/// ```
+/// use std::time::Duration;
+/// use x_one::xlog::config::RequestDebugConfig;
+/// use x_one::xlog::request_debug::{RequestDebugPolicy, sign_token};
+///
+/// let c = RequestDebugConfig {
+///     enable: true,
+///     secret: "s3cret".to_string(),
+///     ..Default::default()
+/// };
+/// let policy = RequestDebugPolicy::from_config(&c).unwrap().unwrap();
+/// assert!(policy.verify(&sign_token("s3cret", Duration::from_secs(600))));
+/// assert!(!policy.verify(&sign_token("other", Duration::from_secs(600))));
+/// ```
+pub fn sign_token(secret: &str, ttl: Duration) -> String {
+    let expires = unix_now().saturating_add(ttl.as_secs()).to_string();
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let signature = hmac::sign(&key, expires.as_bytes());
+    format!("{expires}.{}", URL_SAFE_NO_PAD.encode(signature.as_ref()))
+}
+
+fn policy_store() -> &'static RwLock<Option<Arc<RequestDebugPolicy>>> {
+    static INSTANCE: OnceLock<RwLock<Option<Arc<RequestDebugPolicy>>>> = OnceLock::new();
+    INSTANCE.get_or_init(|| RwLock::new(None))
+}
+
+/// 当前生效的策略，未启用时返回 `None`
+pub fn policy() -> Option<Arc<RequestDebugPolicy>> {
+    policy_store().read().clone()
+}
+
+/// 替换全局策略（`init_xlog` 按配置调用）
+pub fn set_policy(policy: Option<RequestDebugPolicy>) {
+    *policy_store().write() = policy.map(Arc::new);
+}
+
+/// 当前 span 所属请求的 token，不在按请求 DEBUG 的 span 内时返回 `None`
+///
+/// 仅在 [`RequestDebugLayer`] 所在的全局 subscriber（或 `with_default` 设置的 subscriber）下生效。
+pub fn current_token() -> Option<String> {
+    tracing::dispatcher::get_default(|dispatch| {
+        current_mark(dispatch).and_then(|mark| mark.token.as_deref().map(str::to_string))
+    })
+}
+
+/// 当前 span 是否属于按请求开启 DEBUG 的请求
+pub fn is_active() -> bool {
+    tracing::dispatcher::get_default(|dispatch| current_mark(dispatch).is_some())
+}
+
+/// 按请求开启 DEBUG 的过滤 Layer
+///
+/// 包装级别过滤（如 `EnvFilter`），既可包装全局过滤 Layer，也可包装 per-layer [`Filter`]，
+/// 其余行为原样委托给内部过滤：
+/// - 带 [`REQUEST_DEBUG_FIELD`] 字段的 span 始终创建，并标记该 span
+/// - 标记会继承到子 span，标记 span 内 DEBUG 及以上级别的日志与 span 始终放行
+///
+/// `enable` 为 false 时完全等同于内部过滤。
+pub struct RequestDebugLayer<L> {
+    inner: L,
+    enable: bool,
+}
+
+impl<L> RequestDebugLayer<L> {
+    /// 包装级别过滤 Layer 或 [`Filter`]
+    pub fn new(inner: L, enable: bool) -> Self {
+        Self { inner, enable }
+    }
+
+    /// 内部过滤未放行时，判断是否因请求 DEBUG 放行
+    fn debug_enabled<S>(&self, metadata: &Metadata<'_>, ctx: &Context<'_, S>) -> bool
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        self.enable
+            && (is_marker_span(metadata)
+                || (*metadata.level() <= Level::DEBUG
+                    && ctx
+                        .lookup_current()
+                        .is_some_and(|span| span.extensions().get::<RequestDebugMark>().is_some())))
+    }
+
+    /// 调整内部过滤的 callsite interest：标记 span 始终放行，DEBUG 及以上级别需逐次判断
+    fn debug_interest(&self, metadata: &Metadata<'_>, interest: Interest) -> Interest {
+        if !self.enable || interest.is_always() {
+            return interest;
+        }
+        if is_marker_span(metadata) {
+            return Interest::always();
+        }
+        if *metadata.level() <= Level::DEBUG {
+            // 是否放行取决于日志所在的请求，需逐次判断
+            return Interest::sometimes();
+        }
+        interest
+    }
+
+    /// 调整内部过滤的最大级别提示
+    fn debug_level_hint(&self, hint: Option<LevelFilter>) -> Option<LevelFilter> {
+        if self.enable {
+            hint.map(|h| h.max(LevelFilter::DEBUG))
+        } else {
+            hint
+        }
+    }
+
+    /// 标记带 [`REQUEST_DEBUG_FIELD`] 字段的 span，或继承父 span 的标记
+    ///
+    /// 同一 span 可能被多个输出的过滤器重复标记，因此使用 `replace`。
+    fn mark_span<S>(&self, attrs: &Attributes<'_>, id: &Id, ctx: &Context<'_, S>)
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        if !self.enable {
+            return;
+        }
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut visitor = MarkVisitor(None);
+        attrs.record(&mut visitor);
+        let mark = visitor.0.or_else(|| {
+            span.parent()
+                .and_then(|parent| parent.extensions().get::<RequestDebugMark>().cloned())
+        });
+        if let Some(mark) = mark {
+            span.extensions_mut().replace(mark);
+        }
+    }
+}
+
+impl<L, S> Layer<S> for RequestDebugLayer<L>
+where
+    L: Layer<S>,
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_register_dispatch(&self, subscriber: &Dispatch) {
+        self.inner.on_register_dispatch(subscriber);
+    }
+
+    fn on_layer(&mut self, subscriber: &mut S) {
+        self.inner.on_layer(subscriber);
+    }
+
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        self.debug_interest(metadata, self.inner.register_callsite(metadata))
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, ctx: Context<'_, S>) -> bool {
+        self.inner.enabled(metadata, ctx.clone()) || self.debug_enabled(metadata, &ctx)
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        self.debug_level_hint(self.inner.max_level_hint())
+    }
+
+    fn event_enabled(&self, event: &Event<'_>, ctx: Context<'_, S>) -> bool {
+        self.inner.event_enabled(event, ctx)
+    }
+
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        self.mark_span(attrs, id, &ctx);
+        self.inner.on_new_span(attrs, id, ctx);
+    }
+
+    fn on_record(&self, span: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        self.inner.on_record(span, values, ctx);
+    }
+
+    fn on_follows_from(&self, span: &Id, follows: &Id, ctx: Context<'_, S>) {
+        self.inner.on_follows_from(span, follows, ctx);
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        self.inner.on_event(event, ctx);
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        self.inner.on_enter(id, ctx);
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        self.inner.on_exit(id, ctx);
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        self.inner.on_close(id, ctx);
+    }
+
+    fn on_id_change(&self, old: &Id, new: &Id, ctx: Context<'_, S>) {
+        self.inner.on_id_change(old, new, ctx);
+    }
+
+    unsafe fn downcast_raw(&self, id: TypeId) -> Option<*const ()> {
+        if id == TypeId::of::<Self>() {
+            Some(self as *const Self as *const ())
+        } else {
+            // SAFETY: 由内部 Layer 保证返回的指针与 id 类型一致
+            unsafe { self.inner.downcast_raw(id) }
+        }
+    }
+}
+
+impl<F, S> Filter<S> for RequestDebugLayer<F>
+where
+    F: Filter<S>,
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn enabled(&self, metadata: &Metadata<'_>, ctx: &Context<'_, S>) -> bool {
+        self.inner.enabled(metadata, ctx) || self.debug_enabled(metadata, ctx)
+    }
+
+    fn callsite_enabled(&self, metadata: &'static Metadata<'static>) -> Interest {
+        self.debug_interest(metadata, self.inner.callsite_enabled(metadata))
+    }
+
+    fn event_enabled(&self, event: &Event<'_>, ctx: &Context<'_, S>) -> bool {
+        self.inner.event_enabled(event, ctx)
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        self.debug_level_hint(self.inner.max_level_hint())
+    }
+
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        self.mark_span(attrs, id, &ctx);
+        self.inner.on_new_span(attrs, id, ctx);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        self.inner.on_record(id, values, ctx);
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        self.inner.on_enter(id, ctx);
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        self.inner.on_exit(id, ctx);
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        self.inner.on_close(id, ctx);
+    }
+}
+
+// ---- 以下为私有实现 ----
+
+/// span extensions 中的请求 DEBUG 标记
+#[derive(Clone)]
+struct RequestDebugMark {
+    /// 向下游传递的 token（字段值为 bool 时为空）
+    token: Option<Arc<str>>,
+}
+
+/// 读取 [`REQUEST_DEBUG_FIELD`] 字段
+struct MarkVisitor(Option<RequestDebugMark>);
+
+impl Visit for MarkVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == REQUEST_DEBUG_FIELD && !value.is_empty() {
+            self.0 = Some(RequestDebugMark {
+                token: Some(Arc::from(value)),
+            });
+        }
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        if field.name() == REQUEST_DEBUG_FIELD && value {
+            self.0 = Some(RequestDebugMark { token: None });
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == REQUEST_DEBUG_FIELD {
+            self.record_str(field, &format!("{value:?}"));
+        }
+    }
+}
+
+fn is_marker_span(metadata: &Metadata<'_>) -> bool {
+    metadata.is_span() && metadata.fields().field(REQUEST_DEBUG_FIELD).is_some()
+}
+
+/// 从当前 subscriber 的 Registry 中读取当前 span 的标记
+fn current_mark(dispatch: &Dispatch) -> Option<RequestDebugMark> {
+    let registry = dispatch.downcast_ref::<Registry>()?;
+    let id = dispatch.current_span().id()?.clone();
+    let span = registry.span(&id)?;
+    span.extensions().get::<RequestDebugMark>().cloned()
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
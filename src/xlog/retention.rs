@@ -0,0 +1,212 @@
+//! 日志文件保留策略
+//!
+//! 切割生成的 `{Name}.log[.*]` 文件（含压缩后的 `*.gz`）不会自动删除，
+//! 后台线程定期按 `MaxAge` / `MaxFiles` / `MaxTotalSize` 清理过期的切割文件。
+//! 文件的新旧按文件名中的周期与序号判断，最新的文件（当前写入的文件）始终保留。
+//!
+//! ```yaml
+//! XLog:
+//!   MaxAge: "7d"          # 删除修改时间早于 7 天的文件
+//!   MaxFiles: 30          # 最多保留 30 个文件
+//!   MaxTotalSize: "10GiB" # 总大小超出时从最旧的文件开始删除
+//! ```
+
+use super::config::XLogConfig;
+use super::rolling::rotated_file_key;
+use crate::error::XOneError;
+use crate::xutil;
+use parking_lot::Mutex;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, mpsc};
+use std::time::{Duration, SystemTime};
+
+/// 清理检查间隔
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// 日志保留策略（各项为 0 时表示不限制）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RetentionPolicy {
+    /// 文件最大保留时长
+    pub max_age: Duration,
+    /// 最多保留的文件数（含当前文件）
+    pub max_files: usize,
+    /// 文件总大小上限（字节）
+    pub max_total_size: u64,
+}
+
+impl RetentionPolicy {
+    /// 从日志配置构建保留策略
+    pub fn from_config(c: &XLogConfig) -> Self {
+        Self {
+            max_age: c.max_age,
+            max_files: c.max_files,
+            max_total_size: c.max_total_size,
+        }
+    }
+
+    /// 是否设置了任一限制
+    pub fn is_enabled(&self) -> bool {
+        !self.max_age.is_zero() || self.max_files > 0 || self.max_total_size > 0
+    }
+}
+
+/// 后台清理线程句柄
+struct RetentionHandle {
+    stop_tx: mpsc::Sender<()>,
+    thread: std::thread::JoinHandle<()>,
+}
+
+fn retention_store() -> &'static Mutex<Option<RetentionHandle>> {
+    static INSTANCE: OnceLock<Mutex<Option<RetentionHandle>>> = OnceLock::new();
+    INSTANCE.get_or_init(|| Mutex::new(None))
+}
+
+/// 按保留策略清理 `dir` 下 `{log_filename}` 的切割文件，返回被删除的文件
+///
+/// 文件按文件名中的周期与序号从新到旧排列（压缩会更新修改时间，不作为排序依据），
+/// 最新的文件始终保留；其余文件满足任一条件即删除：
+/// 修改时间早于 `now - max_age`、超出 `max_files` 个、累计大小超出 `max_total_size`。
+pub fn cleanup_rotated_files(
+    dir: &Path,
+    log_filename: &str,
+    policy: &RetentionPolicy,
+    now: SystemTime,
+) -> Result<Vec<PathBuf>, XOneError> {
+    let mut files = rotated_files(dir, log_filename)?;
+    files.sort_by(|a, b| b.key.cmp(&a.key));
+
+    let mut removed = Vec::new();
+    let mut kept_count = 0usize;
+    let mut kept_size = 0u64;
+    for (i, f) in files.into_iter().enumerate() {
+        let expired = !policy.max_age.is_zero()
+            && now
+                .duration_since(f.modified)
+                .is_ok_and(|age| age > policy.max_age);
+        let over_count = policy.max_files > 0 && kept_count >= policy.max_files;
+        let over_size =
+            policy.max_total_size > 0 && kept_size.saturating_add(f.size) > policy.max_total_size;
+
+        if i == 0 || !(expired || over_count || over_size) {
+            kept_count += 1;
+            kept_size = kept_size.saturating_add(f.size);
+            continue;
+        }
+
+        match std::fs::remove_file(&f.path) {
+            Ok(()) => removed.push(f.path),
+            Err(e) => xutil::warn_if_enable_debug(&format!(
+                "XLog remove expired log file failed, path=[{}], err=[{e}]",
+                f.path.display()
+            )),
+        }
+    }
+    Ok(removed)
+}
+
+/// 启动后台清理线程（未设置任何限制时不启动），重复调用会先停止已有线程
+///
+/// `log_filenames` 中的每个日志文件（主日志文件及各文件 sink）按同一策略分别清理。
+pub(crate) fn start_retention(
+    dir: &str,
+    log_filenames: Vec<String>,
+    policy: RetentionPolicy,
+) -> Result<(), XOneError> {
+    stop_retention()?;
+    if !policy.is_enabled() {
+        return Ok(());
+    }
+
+    let (stop_tx, stop_rx) = mpsc::channel();
+    let dir = PathBuf::from(dir);
+    let thread = std::thread::Builder::new()
+        .name("xlog-retention".to_string())
+        .spawn(move || retention_loop(&dir, &log_filenames, &policy, &stop_rx))
+        .map_err(|e| XOneError::Log(format!("start log retention failed, err=[{e}]")))?;
+
+    *retention_store().lock() = Some(RetentionHandle { stop_tx, thread });
+    xutil::info_if_enable_debug(&format!("XLog retention started, policy=[{policy:?}]"));
+    Ok(())
+}
+
+/// 停止后台清理线程
+pub(crate) fn stop_retention() -> Result<(), XOneError> {
+    let handle = retention_store().lock().take();
+    if let Some(handle) = handle {
+        let _ = handle.stop_tx.send(());
+        handle
+            .thread
+            .join()
+            .map_err(|_| XOneError::Log("log retention thread panicked".to_string()))?;
+        xutil::info_if_enable_debug("XLog retention stopped");
+    }
+    Ok(())
+}
+
+// ---- 以下为私有实现 ----
+
+/// 切割文件信息
+struct RotatedFile {
+    path: PathBuf,
+    /// 文件名中的（时间后缀, 序号）
+    key: (String, u32),
+    modified: SystemTime,
+    size: u64,
+}
+
+/// 列出 `{log_filename}` 的切割文件
+fn rotated_files(dir: &Path, log_filename: &str) -> Result<Vec<RotatedFile>, XOneError> {
+    let entries = std::fs::read_dir(dir).map_err(|e| {
+        XOneError::Log(format!(
+            "read log dir failed, path=[{}], err=[{e}]",
+            dir.display()
+        ))
+    })?;
+
+    Ok(entries
+        .filter_map(Result::ok)
+        .filter_map(|e| {
+            let key = rotated_file_key(&e.file_name().to_string_lossy(), log_filename)?;
+            let meta = e.metadata().ok().filter(|m| m.is_file())?;
+            Some(RotatedFile {
+                path: e.path(),
+                key,
+                modified: meta.modified().ok()?,
+                size: meta.len(),
+            })
+        })
+        .collect())
+}
+
+/// 清理线程主循环：启动时立即清理一次，之后每隔 [`CLEANUP_INTERVAL`] 清理
+fn retention_loop(
+    dir: &Path,
+    log_filenames: &[String],
+    policy: &RetentionPolicy,
+    stop_rx: &mpsc::Receiver<()>,
+) {
+    loop {
+        for log_filename in log_filenames {
+            match cleanup_rotated_files(dir, log_filename, policy, SystemTime::now()) {
+                Ok(removed) if !removed.is_empty() => {
+                    xutil::info_if_enable_debug(&format!(
+                        "XLog retention removed {} file(s): {removed:?}",
+                        removed.len()
+                    ));
+                }
+                Ok(_) => {}
+                Err(e) => xutil::warn_if_enable_debug(&format!(
+                    "XLog retention [{log_filename}] failed, err=[{e}]"
+                )),
+            }
+        }
+
+        // 收到停止信号或 sender 被 drop 时退出
+        if !matches!(
+            stop_rx.recv_timeout(CLEANUP_INTERVAL),
+            Err(mpsc::RecvTimeoutError::Timeout)
+        ) {
+            return;
+        }
+    }
+}
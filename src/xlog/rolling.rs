@@ -0,0 +1,429 @@
+//! 日志文件切割与压缩
+//!
+//! 按 `RotateTime` 周期及 `RotateSize` 大小切割日志文件，切割后的文件可在后台 gzip 压缩。
+//! 周期边界按 `Timezone` 时区的本地时间对齐，文件名后缀随周期精度变化：
+//!
+//! | RotateTime | 文件名 |
+//! |---|---|
+//! | `1d` | `app.log.2026-10-17` |
+//! | `1h` / `6h` | `app.log.2026-10-17-09` |
+//! | `1m` / `15m` | `app.log.2026-10-17-09-15` |
+//! | `0s` | `app.log`（不按时间切割） |
+//!
+//! 同一周期内超出 `RotateSize` 时依次写入 `app.log.2026-10-17.1`、`app.log.2026-10-17.2` ……
+//! 开启 `Compress` 后，切割下来的文件压缩为 `*.gz` 并删除原文件。
+//!
+//! ```yaml
+//! XLog:
+//!   RotateTime: "1h"
+//!   RotateSize: "512MiB"
+//!   Compress: true
+//! ```
+
+use super::config::XLogConfig;
+use super::timezone::LogTimezone;
+use crate::error::XOneError;
+use crate::xutil;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// 压缩文件扩展名
+const GZIP_EXTENSION: &str = "gz";
+
+/// 日志切割策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RotationPolicy {
+    /// 切割周期（为 0 时不按时间切割，否则至少 1s）
+    pub period: Duration,
+    /// 单个文件大小上限（字节，为 0 时不按大小切割）
+    pub max_size: u64,
+    /// 是否压缩切割下来的文件
+    pub compress: bool,
+    /// 周期边界及文件名使用的时区
+    pub timezone: LogTimezone,
+}
+
+impl RotationPolicy {
+    /// 从日志配置构建切割策略，`RotateTime` 非 0 且小于 1s 或时区无法识别时返回错误
+    pub fn from_config(c: &XLogConfig) -> Result<Self, XOneError> {
+        if !c.rotate_time.is_zero() && c.rotate_time < Duration::from_secs(1) {
+            return Err(XOneError::Log(format!(
+                "invalid RotateTime [{:?}], expect 0s or at least 1s",
+                c.rotate_time
+            )));
+        }
+        Ok(Self {
+            period: c.rotate_time,
+            max_size: c.rotate_size,
+            compress: c.compress,
+            timezone: LogTimezone::parse(&c.timezone)?,
+        })
+    }
+}
+
+/// 按周期和大小切割的日志文件写入器
+///
+/// 实现 [`std::io::Write`]，配合 `tracing_appender::non_blocking` 在写入线程中使用。
+/// drop 时等待后台压缩任务完成。
+pub struct RollingFileWriter {
+    dir: PathBuf,
+    log_filename: String,
+    policy: RotationPolicy,
+    file: File,
+    path: PathBuf,
+    period_index: u64,
+    file_index: u32,
+    written: u64,
+    compressor: Option<Compressor>,
+}
+
+impl RollingFileWriter {
+    /// 创建写入器，打开（追加）当前周期的日志文件
+    ///
+    /// 开启压缩时，目录中遗留的未压缩切割文件也会在后台压缩。
+    pub fn new(dir: &str, log_filename: &str, policy: RotationPolicy) -> Result<Self, XOneError> {
+        let dir = PathBuf::from(dir);
+        let now = SystemTime::now();
+        let period_index = period_index(policy.period, policy.timezone, now);
+
+        // 续写当前周期最后一个文件，已超出大小上限或已被压缩时切到下一个
+        let mut file_index = last_file_index(&dir, log_filename, &policy, now);
+        let mut path = dir.join(rotated_file_name(
+            log_filename,
+            policy.period,
+            policy.timezone,
+            now,
+            file_index,
+        ));
+        let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        if (policy.max_size > 0 && size >= policy.max_size) || gzip_path(&path).exists() {
+            file_index += 1;
+            path = dir.join(rotated_file_name(
+                log_filename,
+                policy.period,
+                policy.timezone,
+                now,
+                file_index,
+            ));
+        }
+
+        let file = open_append(&path)?;
+        let written = file.metadata().map(|m| m.len()).unwrap_or(0);
+
+        let compressor = policy.compress.then(Compressor::start).transpose()?;
+        if let Some(c) = &compressor {
+            let current_base =
+                rotated_file_name(log_filename, policy.period, policy.timezone, now, 0);
+            for leftover in uncompressed_files(&dir, log_filename, &current_base, file_index) {
+                c.submit(leftover);
+            }
+        }
+
+        Ok(Self {
+            dir,
+            log_filename: log_filename.to_string(),
+            policy,
+            file,
+            path,
+            period_index,
+            file_index,
+            written,
+            compressor,
+        })
+    }
+
+    /// 当前写入的文件路径
+    pub fn current_path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 按需切割：周期变化时切到新周期的首个文件，超出大小上限时切到同周期的下一个文件
+    fn rotate_if_needed(&mut self, incoming: usize) -> std::io::Result<()> {
+        let now = SystemTime::now();
+        let period_index = period_index(self.policy.period, self.policy.timezone, now);
+        if period_index != self.period_index {
+            self.period_index = period_index;
+            self.file_index = 0;
+        } else if self.policy.max_size > 0
+            && self.written > 0
+            && self.written + incoming as u64 > self.policy.max_size
+        {
+            self.file_index += 1;
+        } else {
+            return Ok(());
+        }
+
+        let path = self.dir.join(rotated_file_name(
+            &self.log_filename,
+            self.policy.period,
+            self.policy.timezone,
+            now,
+            self.file_index,
+        ));
+        // 不按时间切割且未设置大小上限时不会走到这里；路径相同说明无需切换
+        if path == self.path {
+            return Ok(());
+        }
+
+        self.file.flush()?;
+        let file = open_append(&path).map_err(std::io::Error::other)?;
+        let previous = std::mem::replace(&mut self.path, path);
+        self.file = file;
+        self.written = self.file.metadata().map(|m| m.len()).unwrap_or(0);
+
+        if let Some(c) = &self.compressor {
+            c.submit(previous);
+        }
+        Ok(())
+    }
+}
+
+impl Write for RollingFileWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.rotate_if_needed(buf.len())?;
+        let n = self.file.write(buf)?;
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// 生成切割文件名
+///
+/// `period` 为 0 时不带时间后缀；`index` 为 0 时不带序号后缀。
+/// 时间后缀为 `at` 所在周期在 `timezone` 时区下的起始时间。
+pub fn rotated_file_name(
+    log_filename: &str,
+    period: Duration,
+    timezone: LogTimezone,
+    at: SystemTime,
+    index: u32,
+) -> String {
+    let mut name = log_filename.to_string();
+    if !period.is_zero() {
+        // 周期序号基于本地时间计算，起始时间按本地时间格式化（不再换算时区）
+        let start = period_index(period, timezone, at) * period.as_secs();
+        let start = chrono::DateTime::from_timestamp(start as i64, 0).unwrap_or_default();
+        name.push('.');
+        name.push_str(&start.format(time_suffix_format(period)).to_string());
+    }
+    if index > 0 {
+        name.push_str(&format!(".{index}"));
+    }
+    name
+}
+
+/// gzip 压缩文件为 `{path}.gz` 并删除原文件，返回压缩文件路径
+pub fn compress_file(path: &Path) -> Result<PathBuf, XOneError> {
+    let target = gzip_path(path);
+    let tmp = PathBuf::from(format!("{}.tmp", target.display()));
+    let err = |e: std::io::Error| {
+        XOneError::Log(format!(
+            "compress log file failed, path=[{}], err=[{e}]",
+            path.display()
+        ))
+    };
+
+    let mut input = File::open(path).map_err(err)?;
+    let output = File::create(&tmp).map_err(err)?;
+    let mut encoder = flate2::write::GzEncoder::new(output, flate2::Compression::default());
+    let result = std::io::copy(&mut input, &mut encoder)
+        .and_then(|_| encoder.finish())
+        .and_then(|f| f.sync_all());
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(err(e));
+    }
+
+    std::fs::rename(&tmp, &target).map_err(err)?;
+    std::fs::remove_file(path).map_err(err)?;
+    Ok(target)
+}
+
+/// 解析 `{log_filename}[.{时间后缀}][.{序号}][.gz]` 形式的切割文件名，返回（时间后缀, 序号）
+///
+/// 同一周期的时间后缀格式固定且补零，按（时间后缀, 序号）排序即为切割先后顺序；
+/// 不是该日志文件的切割文件（如其他 sink 的文件）时返回 `None`。
+pub(crate) fn rotated_file_key(name: &str, log_filename: &str) -> Option<(String, u32)> {
+    let rest = name.strip_prefix(log_filename)?;
+    let rest = rest
+        .strip_suffix(&format!(".{GZIP_EXTENSION}"))
+        .unwrap_or(rest);
+    if rest.is_empty() {
+        return Some((String::new(), 0));
+    }
+    let rest = rest.strip_prefix('.')?;
+    let (time, index) = match rest.split_once('.') {
+        Some((time, index)) => (time, index.parse::<u32>().ok()?),
+        // 不按时间切割时仅有序号后缀
+        None => match rest.parse::<u32>() {
+            Ok(index) => return Some((String::new(), index)),
+            Err(_) => (rest, 0),
+        },
+    };
+    let is_time = !time.is_empty() && time.bytes().all(|b| b.is_ascii_digit() || b == b'-');
+    is_time.then(|| (time.to_string(), index))
+}
+
+// ---- 以下为私有实现 ----
+
+/// 后台压缩线程
+struct Compressor {
+    tx: Option<mpsc::Sender<PathBuf>>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl Compressor {
+    fn start() -> Result<Self, XOneError> {
+        let (tx, rx) = mpsc::channel::<PathBuf>();
+        let thread = std::thread::Builder::new()
+            .name("xlog-compress".to_string())
+            .spawn(move || {
+                for path in rx {
+                    match compress_file(&path) {
+                        Ok(target) => xutil::info_if_enable_debug(&format!(
+                            "XLog compressed rotated file [{}]",
+                            target.display()
+                        )),
+                        Err(e) => xutil::warn_if_enable_debug(&e.to_string()),
+                    }
+                }
+            })
+            .map_err(|e| XOneError::Log(format!("start log compressor failed, err=[{e}]")))?;
+        Ok(Self {
+            tx: Some(tx),
+            thread: Some(thread),
+        })
+    }
+
+    fn submit(&self, path: PathBuf) {
+        if let Some(tx) = &self.tx {
+            let _ = tx.send(path);
+        }
+    }
+}
+
+impl Drop for Compressor {
+    fn drop(&mut self) {
+        // 关闭 channel 后等待已提交的压缩任务完成
+        self.tx.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// 按 `timezone` 本地时间计算的周期序号（不按时间切割时恒为 0）
+fn period_index(period: Duration, timezone: LogTimezone, at: SystemTime) -> u64 {
+    let utc = chrono::DateTime::<chrono::Utc>::from(at.max(UNIX_EPOCH));
+    let offset = timezone.offset_at(utc).local_minus_utc() as i64;
+    let local_secs = (utc.timestamp() + offset).max(0) as u64;
+    local_secs.checked_div(period.as_secs()).unwrap_or(0)
+}
+
+/// 按周期精度选择时间后缀格式
+fn time_suffix_format(period: Duration) -> &'static str {
+    let secs = period.as_secs();
+    if secs.is_multiple_of(86400) {
+        "%Y-%m-%d"
+    } else if secs.is_multiple_of(3600) {
+        "%Y-%m-%d-%H"
+    } else if secs.is_multiple_of(60) {
+        "%Y-%m-%d-%H-%M"
+    } else {
+        "%Y-%m-%d-%H-%M-%S"
+    }
+}
+
+/// 当前周期已存在的最大文件序号（含已压缩文件）
+fn last_file_index(
+    dir: &Path,
+    log_filename: &str,
+    policy: &RotationPolicy,
+    now: SystemTime,
+) -> u32 {
+    let base = rotated_file_name(log_filename, policy.period, policy.timezone, now, 0);
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            let rest = name.strip_prefix(&base)?.strip_prefix('.')?;
+            let rest = rest
+                .strip_suffix(&format!(".{GZIP_EXTENSION}"))
+                .unwrap_or(rest);
+            rest.parse::<u32>().ok()
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// 目录中遗留的未压缩切割文件
+///
+/// 只返回早于当前文件切割出的文件（当前周期内序号更小，或属于其他周期），
+/// 不按时间切割时当前写入的 `{log_filename}` 及其他写入器的文件不会被选中。
+fn uncompressed_files(
+    dir: &Path,
+    log_filename: &str,
+    current_base: &str,
+    current_index: u32,
+) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(Result::ok)
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .filter(|p| {
+            let name = p.file_name().unwrap_or_default().to_string_lossy();
+            !name.ends_with(&format!(".{GZIP_EXTENSION}"))
+                && !name.ends_with(".tmp")
+                && is_rotated_before(&name, log_filename, current_base, current_index)
+        })
+        .collect()
+}
+
+/// 文件名是否为早于当前文件切割出的文件
+///
+/// `current_base` 为当前周期序号 0 的文件名，`current_index` 为当前写入文件的序号。
+fn is_rotated_before(
+    name: &str,
+    log_filename: &str,
+    current_base: &str,
+    current_index: u32,
+) -> bool {
+    let (Some((time, index)), Some((current_time, _))) = (
+        rotated_file_key(name, log_filename),
+        rotated_file_key(current_base, log_filename),
+    ) else {
+        return false;
+    };
+    time != current_time || index < current_index
+}
+
+/// 压缩文件路径（`{path}.gz`）
+fn gzip_path(path: &Path) -> PathBuf {
+    PathBuf::from(format!("{}.{GZIP_EXTENSION}", path.display()))
+}
+
+fn open_append(path: &Path) -> Result<File, XOneError> {
+    File::options()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| {
+            XOneError::Log(format!(
+                "open log file failed, path=[{}], err=[{e}]",
+                path.display()
+            ))
+        })
+}
@@ -0,0 +1,65 @@
+//! 额外的文件 sink
+//!
+//! 按 `XLog.ErrorFile` 与 `XLog.Sinks` 为每个 sink 创建独立的切割文件写入器，
+//! 各自使用 per-layer 级别过滤与输出格式，切割设置与主日志文件相同。
+//! sink 的级别过滤独立于 `XLog.Level`，可输出比主日志更详细的日志。
+
+use super::config::XLogConfig;
+use super::rolling::{RollingFileWriter, RotationPolicy};
+use super::timezone::LogTimezone;
+use crate::error::XOneError;
+use tracing::Subscriber;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{EnvFilter, Layer};
+
+/// 文件 sink 的 Layer 及其资源
+pub struct FileSinks<S> {
+    /// 各 sink 的 Layer（已附加级别过滤）
+    pub layers: Vec<Box<dyn Layer<S> + Send + Sync>>,
+    /// 异步写入器 guard，drop 时刷新并关闭对应文件
+    pub guards: Vec<WorkerGuard>,
+    /// 各 sink 的日志文件名（如 `app.error.log`）
+    pub filenames: Vec<String>,
+}
+
+/// 按配置创建文件 sink
+///
+/// 先校验所有 sink 的名称与级别过滤，全部合法后才创建文件。
+pub fn build_file_sinks<S>(c: &XLogConfig, timezone: LogTimezone) -> Result<FileSinks<S>, XOneError>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let sinks = c
+        .file_sinks()?
+        .into_iter()
+        .map(|sink| {
+            let directives = sink.filter_directives()?;
+            let filter = EnvFilter::try_new(&directives).map_err(|e| {
+                XOneError::Log(format!(
+                    "invalid log sink [{}] level filter [{directives}], err=[{e}]",
+                    sink.name
+                ))
+            })?;
+            Ok((sink, filter))
+        })
+        .collect::<Result<Vec<_>, XOneError>>()?;
+
+    let rotation = RotationPolicy::from_config(c)?;
+    let mut result = FileSinks {
+        layers: Vec::with_capacity(sinks.len()),
+        guards: Vec::with_capacity(sinks.len()),
+        filenames: Vec::with_capacity(sinks.len()),
+    };
+    for (sink, filter) in sinks {
+        let filename = format!("{}.log", sink.name.trim());
+        let appender = RollingFileWriter::new(&c.path, &filename, rotation)?;
+        let (writer, guard) = tracing_appender::non_blocking(appender);
+
+        let layer = super::format::fmt_layer(sink.format, timezone, writer).with_filter(filter);
+        result.layers.push(layer.boxed());
+        result.guards.push(guard);
+        result.filenames.push(filename);
+    }
+    Ok(result)
+}
@@ -0,0 +1,281 @@
+//! 日志限流与采样
+//!
+//! [`ThrottleLayer`] 在事件分发前决定是否丢弃日志：
+//!
+//! - **采样**：trace / debug / info 级别日志按 `XLog.Sampling` 中的概率保留，warn / error 不参与采样
+//! - **限流**：每个调用点在 `XLog.RateLimit.Interval` 内最多保留 `MaxEvents` 条，
+//!   被丢弃的数量由后台线程在每个周期结束后以一条 warn 汇总日志输出
+//!
+//! 启用 `metric` 特性时，丢弃数量同时计入 `xlog_suppressed_events_total{reason, level}` 计数器
+//! （`reason` 为 `rate_limit` 或 `sampling`）。
+
+use super::config::{RateLimitConfig, SamplingConfig, XLOG_CONFIG_KEY, XLogConfig};
+use crate::error::XOneError;
+use crate::xutil;
+use parking_lot::Mutex;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::mpsc;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tracing::callsite::Identifier;
+use tracing::{Event, Level, Metadata, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// 汇总日志的 target（不参与限流与采样）
+pub const SUMMARY_TARGET: &str = "x_one::xlog::throttle";
+
+/// 丢弃计数指标名
+pub const SUPPRESSED_METRIC: &str = "xlog_suppressed_events_total";
+
+/// 限流与采样策略
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThrottlePolicy {
+    /// 每个周期每个调用点最多保留的条数，`None` 表示不限流
+    pub rate_limit: Option<(u64, Duration)>,
+    /// trace / debug / info 级别的采样率
+    pub sample_rates: [f64; 3],
+}
+
+impl ThrottlePolicy {
+    /// 从日志配置构建策略，采样率不在 0.0 ~ 1.0 之间或限流参数为 0 时返回错误
+    pub fn from_config(c: &XLogConfig) -> Result<Self, XOneError> {
+        Ok(Self {
+            rate_limit: rate_limit_from_config(&c.rate_limit)?,
+            sample_rates: sample_rates_from_config(&c.sampling)?,
+        })
+    }
+
+    /// 是否启用了限流或采样
+    pub fn is_enabled(&self) -> bool {
+        self.rate_limit.is_some() || self.sample_rates.iter().any(|r| *r < 1.0)
+    }
+}
+
+impl Default for ThrottlePolicy {
+    fn default() -> Self {
+        Self {
+            rate_limit: None,
+            sample_rates: [1.0; 3],
+        }
+    }
+}
+
+/// 限流与采样 Layer
+///
+/// 通过 `event_enabled` 丢弃事件，对同一 subscriber 中的所有 Layer 生效。
+#[derive(Clone)]
+pub struct ThrottleLayer {
+    state: Arc<ThrottleState>,
+}
+
+impl ThrottleLayer {
+    /// 按策略创建 Layer
+    pub fn new(policy: ThrottlePolicy) -> Self {
+        Self {
+            state: Arc::new(ThrottleState {
+                policy,
+                callsites: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    /// 输出各调用点自上次汇总以来被限流丢弃的数量，返回汇总的调用点数
+    ///
+    /// 后台线程每个限流周期调用一次，需在事件分发之外调用。
+    pub fn flush_summaries(&self) -> usize {
+        let pending: Vec<(&'static Metadata<'static>, u64)> = {
+            let mut callsites = self.state.callsites.lock();
+            callsites
+                .values_mut()
+                .filter(|c| c.suppressed > 0)
+                .map(|c| (c.meta, std::mem::take(&mut c.suppressed)))
+                .collect()
+        };
+        for (meta, suppressed) in &pending {
+            let caller = match (meta.file(), meta.line()) {
+                (Some(file), Some(line)) => format!("{file}:{line}"),
+                _ => meta.name().to_string(),
+            };
+            tracing::warn!(
+                target: SUMMARY_TARGET,
+                suppressed = *suppressed,
+                suppressed_target = meta.target(),
+                suppressed_caller = %caller,
+                "log events suppressed by rate limit"
+            );
+        }
+        pending.len()
+    }
+}
+
+impl<S: Subscriber> Layer<S> for ThrottleLayer {
+    fn event_enabled(&self, event: &Event<'_>, _ctx: Context<'_, S>) -> bool {
+        let meta = event.metadata();
+        if meta.target() == SUMMARY_TARGET {
+            return true;
+        }
+        let policy = &self.state.policy;
+
+        // 采样：仅 trace / debug / info
+        let rate = match *meta.level() {
+            Level::TRACE => policy.sample_rates[0],
+            Level::DEBUG => policy.sample_rates[1],
+            Level::INFO => policy.sample_rates[2],
+            _ => 1.0,
+        };
+        if rate < 1.0 && next_random() >= rate {
+            record_suppressed("sampling", meta.level());
+            return false;
+        }
+
+        // 限流：按调用点计数
+        let Some((max_events, interval)) = policy.rate_limit else {
+            return true;
+        };
+        let now = Instant::now();
+        let mut callsites = self.state.callsites.lock();
+        let counter = callsites
+            .entry(meta.callsite())
+            .or_insert_with(|| CallsiteCounter {
+                meta,
+                window_start: now,
+                count: 0,
+                suppressed: 0,
+            });
+        if now.duration_since(counter.window_start) >= interval {
+            counter.window_start = now;
+            counter.count = 0;
+        }
+        counter.count += 1;
+        if counter.count <= max_events {
+            return true;
+        }
+        counter.suppressed += 1;
+        drop(callsites);
+        record_suppressed("rate_limit", meta.level());
+        false
+    }
+}
+
+/// 启动限流汇总线程（未启用限流时不启动）
+pub(crate) fn start_summary(layer: &ThrottleLayer) -> Result<(), XOneError> {
+    stop_summary()?;
+    let Some((_, interval)) = layer.state.policy.rate_limit else {
+        return Ok(());
+    };
+
+    let (stop_tx, stop_rx) = mpsc::channel();
+    let layer = layer.clone();
+    let thread = std::thread::Builder::new()
+        .name("xlog-throttle".to_string())
+        .spawn(move || {
+            // 收到停止信号或 sender 被 drop 时输出最后一次汇总并退出
+            while matches!(
+                stop_rx.recv_timeout(interval),
+                Err(mpsc::RecvTimeoutError::Timeout)
+            ) {
+                layer.flush_summaries();
+            }
+            layer.flush_summaries();
+        })
+        .map_err(|e| XOneError::Log(format!("start log throttle summary failed, err=[{e}]")))?;
+
+    *summary_store().lock() = Some(SummaryHandle { stop_tx, thread });
+    xutil::info_if_enable_debug(&format!(
+        "XLog rate limit summary started, interval=[{interval:?}]"
+    ));
+    Ok(())
+}
+
+/// 停止限流汇总线程
+pub(crate) fn stop_summary() -> Result<(), XOneError> {
+    let handle = summary_store().lock().take();
+    if let Some(handle) = handle {
+        let _ = handle.stop_tx.send(());
+        handle
+            .thread
+            .join()
+            .map_err(|_| XOneError::Log("log throttle summary thread panicked".to_string()))?;
+        xutil::info_if_enable_debug("XLog rate limit summary stopped");
+    }
+    Ok(())
+}
+
+// ---- 以下为私有实现 ----
+
+struct ThrottleState {
+    policy: ThrottlePolicy,
+    callsites: Mutex<HashMap<Identifier, CallsiteCounter>>,
+}
+
+/// 单个调用点的限流计数
+struct CallsiteCounter {
+    meta: &'static Metadata<'static>,
+    window_start: Instant,
+    count: u64,
+    /// 自上次汇总以来被丢弃的数量
+    suppressed: u64,
+}
+
+/// 汇总线程句柄
+struct SummaryHandle {
+    stop_tx: mpsc::Sender<()>,
+    thread: std::thread::JoinHandle<()>,
+}
+
+fn summary_store() -> &'static Mutex<Option<SummaryHandle>> {
+    static INSTANCE: OnceLock<Mutex<Option<SummaryHandle>>> = OnceLock::new();
+    INSTANCE.get_or_init(|| Mutex::new(None))
+}
+
+fn rate_limit_from_config(c: &RateLimitConfig) -> Result<Option<(u64, Duration)>, XOneError> {
+    if !c.enable {
+        return Ok(None);
+    }
+    if c.max_events == 0 || c.interval.is_zero() {
+        return Err(XOneError::Log(format!(
+            "invalid {XLOG_CONFIG_KEY}.RateLimit, MaxEvents and Interval must be greater than 0"
+        )));
+    }
+    Ok(Some((c.max_events, c.interval)))
+}
+
+fn sample_rates_from_config(c: &SamplingConfig) -> Result<[f64; 3], XOneError> {
+    let rates = [("Trace", c.trace), ("Debug", c.debug), ("Info", c.info)];
+    for (name, rate) in rates {
+        if !(0.0..=1.0).contains(&rate) {
+            return Err(XOneError::Log(format!(
+                "invalid {XLOG_CONFIG_KEY}.Sampling.{name} [{rate}], expect 0.0 ~ 1.0"
+            )));
+        }
+    }
+    Ok([c.trace, c.debug, c.info])
+}
+
+#[cfg_attr(not(feature = "metric"), allow(unused_variables))]
+fn record_suppressed(reason: &str, level: &Level) {
+    #[cfg(feature = "metric")]
+    crate::xmetric::counter_inc(
+        SUPPRESSED_METRIC,
+        &[("reason", reason), ("level", level.as_str())],
+    );
+}
+
+/// 线程内 xorshift64* 伪随机数，返回 [0, 1)
+fn next_random() -> f64 {
+    thread_local! {
+        static STATE: Cell<u64> = Cell::new(
+            std::collections::hash_map::RandomState::new().build_hasher().finish() | 1,
+        );
+    }
+    STATE.with(|state| {
+        let mut x = state.get();
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        state.set(x);
+        (x.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 11) as f64 / (1u64 << 53) as f64
+    })
+}
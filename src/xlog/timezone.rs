@@ -0,0 +1,104 @@
+//! 日志时区
+//!
+//! 解析 `XLog.Timezone`，供 JSON / 控制台格式化器及文件切割周期使用。支持：
+//!
+//! - IANA 时区名：`Asia/Shanghai`、`America/New_York`
+//! - 固定偏移：`+08:00`、`-0530`、`UTC+8`
+//! - `UTC`（或空字符串）、`Local`（系统本地时区）
+
+use crate::error::XOneError;
+use chrono::{DateTime, FixedOffset, Offset, TimeZone, Utc};
+
+/// 日志时区
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogTimezone {
+    /// UTC
+    #[default]
+    Utc,
+    /// 系统本地时区
+    Local,
+    /// 固定偏移
+    Fixed(FixedOffset),
+    /// IANA 时区
+    Named(chrono_tz::Tz),
+}
+
+impl LogTimezone {
+    /// 解析时区配置，无法识别时返回错误
+    ///
+    /// ```
+    /// use x_one::xlog::timezone::LogTimezone;
+    ///
+    /// assert!(matches!(LogTimezone::parse("Asia/Shanghai"), Ok(LogTimezone::Named(_))));
+    /// assert!(matches!(LogTimezone::parse("+08:00"), Ok(LogTimezone::Fixed(_))));
+    /// assert_eq!(LogTimezone::parse("utc").unwrap(), LogTimezone::Utc);
+    /// assert!(LogTimezone::parse("Mars/Olympus").is_err());
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, XOneError> {
+        let s = s.trim();
+        if s.is_empty() || s.eq_ignore_ascii_case("UTC") || s.eq_ignore_ascii_case("Z") {
+            return Ok(LogTimezone::Utc);
+        }
+        if s.eq_ignore_ascii_case("Local") {
+            return Ok(LogTimezone::Local);
+        }
+        if let Some(offset) = parse_fixed_offset(s) {
+            return Ok(LogTimezone::Fixed(offset));
+        }
+        s.parse::<chrono_tz::Tz>()
+            .map(LogTimezone::Named)
+            .map_err(|_| {
+                XOneError::Log(format!(
+                    "invalid log timezone [{s}], expect IANA name like Asia/Shanghai or offset like +08:00"
+                ))
+            })
+    }
+
+    /// 指定时刻相对 UTC 的偏移
+    pub fn offset_at(&self, at: DateTime<Utc>) -> FixedOffset {
+        match self {
+            LogTimezone::Utc => Utc.fix(),
+            LogTimezone::Local => chrono::Local
+                .offset_from_utc_datetime(&at.naive_utc())
+                .fix(),
+            LogTimezone::Fixed(offset) => *offset,
+            LogTimezone::Named(tz) => tz.offset_from_utc_datetime(&at.naive_utc()).fix(),
+        }
+    }
+
+    /// 转换为该时区的时间
+    pub fn to_local(&self, at: DateTime<Utc>) -> DateTime<FixedOffset> {
+        at.with_timezone(&self.offset_at(at))
+    }
+
+    /// RFC3339 格式（微秒精度，UTC 输出 `Z`，其它时区输出 `+08:00` 形式偏移）
+    pub fn format_rfc3339(&self, at: DateTime<Utc>) -> String {
+        self.to_local(at)
+            .to_rfc3339_opts(chrono::SecondsFormat::Micros, true)
+    }
+}
+
+// ---- 以下为私有实现 ----
+
+/// 解析 `+08:00` / `-0530` / `+8` / `UTC+8` / `GMT-03:30` 形式的固定偏移
+fn parse_fixed_offset(s: &str) -> Option<FixedOffset> {
+    let upper = s.to_ascii_uppercase();
+    let rest = upper
+        .strip_prefix("UTC")
+        .or_else(|| upper.strip_prefix("GMT"))
+        .unwrap_or(&upper);
+    let (sign, digits) = match rest.as_bytes().first()? {
+        b'+' => (1, &rest[1..]),
+        b'-' => (-1, &rest[1..]),
+        _ => return None,
+    };
+
+    let (hours, minutes) = match digits.split_once(':') {
+        Some((h, m)) => (h, m),
+        None if digits.len() == 4 => digits.split_at(2),
+        None => (digits, "0"),
+    };
+    let hours: i32 = hours.parse().ok().filter(|h| (0..=14).contains(h))?;
+    let minutes: i32 = minutes.parse().ok().filter(|m| (0..60).contains(m))?;
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
@@ -259,8 +259,10 @@ mod tests {
     fn test_set_config_and_get_config_roundtrip() {
         reset_metrics();
 
-        let mut config = XMetricConfig::default();
-        config.namespace = "myapp".to_string();
+        let config = XMetricConfig {
+            namespace: "myapp".to_string(),
+            ..Default::default()
+        };
         set_config(config.clone());
 
         let got = get_config();
@@ -284,8 +286,10 @@ mod tests {
     fn test_build_metric_name_with_namespace_returns_prefixed_name() {
         reset_metrics();
 
-        let mut config = XMetricConfig::default();
-        config.namespace = "myapp".to_string();
+        let config = XMetricConfig {
+            namespace: "myapp".to_string(),
+            ..Default::default()
+        };
         set_config(config);
 
         let name = build_metric_name("http_requests");
@@ -1,13 +1,13 @@
 //! xmetric 配置结构体
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// XMetric 配置 key
 pub const XMETRIC_CONFIG_KEY: &str = "XMetric";
 
 /// XMetric 配置
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct XMetricConfig {
     /// 指标命名空间前缀
     #[serde(rename = "Namespace", default)]
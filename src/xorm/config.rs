@@ -18,13 +18,14 @@
 //!     Name: "analytics"
 //! ```
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// XOrm 配置 key
 pub const XORM_CONFIG_KEY: &str = "XOrm";
 
 /// 数据库驱动类型
-#[derive(Debug, Default, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
 pub enum Driver {
     /// PostgreSQL
     #[serde(rename = "postgres")]
@@ -59,7 +60,7 @@ impl std::fmt::Display for Driver {
 ///   EnableLog: true
 ///   Name: ""
 /// ```
-#[derive(Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 #[serde(default)]
 pub struct XOrmConfig {
     /// 数据库驱动（默认 postgres）
@@ -78,17 +79,17 @@ pub struct XOrmConfig {
     #[serde(rename = "MaxIdleConns")]
     pub max_idle_conns: u32,
 
-    /// 连接最大生存时间（duration 字符串，默认 "1h"）
-    #[serde(rename = "MaxLifetime")]
-    pub max_lifetime: String,
+    /// 连接最大生存时间（duration 字符串，默认 "1h"，空字符串或 "0s" 表示不限制）
+    #[serde(rename = "MaxLifetime", with = "crate::xutil::serde_duration::option")]
+    pub max_lifetime: Option<Duration>,
 
-    /// 空闲连接最大存活时间（duration 字符串，默认 "10m"）
-    #[serde(rename = "MaxIdleTime")]
-    pub max_idle_time: String,
+    /// 空闲连接最大存活时间（duration 字符串，默认 "10m"，空字符串或 "0s" 表示不限制）
+    #[serde(rename = "MaxIdleTime", with = "crate::xutil::serde_duration::option")]
+    pub max_idle_time: Option<Duration>,
 
     /// 慢查询阈值（duration 字符串，默认 "200ms"）
-    #[serde(rename = "SlowThreshold")]
-    pub slow_threshold: String,
+    #[serde(rename = "SlowThreshold", with = "crate::xutil::serde_duration")]
+    pub slow_threshold: Duration,
 
     /// 是否启用 SQL 日志（默认 true）
     #[serde(rename = "EnableLog")]
@@ -135,8 +136,8 @@ fn sanitize_dsn(dsn: &str) -> String {
     dsn.to_string()
 }
 
-/// 加载 XOrm 配置（支持单实例和多实例模式）
-pub(crate) fn load_configs() -> Vec<XOrmConfig> {
+/// 加载 XOrm 配置（支持单实例和多实例模式），配置值非法时返回错误
+pub(crate) fn load_configs() -> Result<Vec<XOrmConfig>, crate::error::XOneError> {
     crate::xconfig::parse_config_list::<XOrmConfig>(XORM_CONFIG_KEY)
 }
 
@@ -147,9 +148,9 @@ impl Default for XOrmConfig {
             dsn: String::new(),
             max_open_conns: 100,
             max_idle_conns: 10,
-            max_lifetime: "1h".to_string(),
-            max_idle_time: "10m".to_string(),
-            slow_threshold: "200ms".to_string(),
+            max_lifetime: Some(Duration::from_secs(3600)),
+            max_idle_time: Some(Duration::from_secs(600)),
+            slow_threshold: Duration::from_millis(200),
             enable_log: true,
             name: String::new(),
         }
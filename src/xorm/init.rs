@@ -16,7 +16,7 @@ pub fn init_xorm() -> Result<(), crate::error::XOneError> {
         return Ok(());
     }
 
-    let configs = super::config::load_configs();
+    let configs = super::config::load_configs()?;
 
     if configs.is_empty() {
         xutil::info_if_enable_debug("XOrm config empty, skip init");
@@ -61,8 +61,9 @@ pub fn init_xorm() -> Result<(), crate::error::XOneError> {
 /// 使用 `connect_lazy` 创建连接池，只解析 URL 不建立真实连接。
 #[doc(hidden)]
 pub fn build_pool(config: &XOrmConfig) -> Result<DbPool, crate::error::XOneError> {
-    let max_lifetime = xutil::to_duration(&config.max_lifetime);
-    let idle_timeout = xutil::to_duration(&config.max_idle_time);
+    // 未配置或为 0 时不限制
+    let max_lifetime = config.max_lifetime.filter(|d| !d.is_zero());
+    let idle_timeout = config.max_idle_time.filter(|d| !d.is_zero());
 
     match config.driver {
         Driver::Postgres => {
@@ -136,7 +136,7 @@ async fn run_pipeline(
 
     for (processor, (mut rx, tx)) in processors
         .into_iter()
-        .zip(receivers.into_iter().zip(senders.into_iter()))
+        .zip(receivers.into_iter().zip(senders))
     {
         let handle = tokio::spawn(async move {
             let proc_name = processor.name().to_string();
@@ -15,13 +15,14 @@
 //!     Name: "session"
 //! ```
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// XRedis 配置 key
 pub const XREDIS_CONFIG_KEY: &str = "XRedis";
 
 /// XRedis 配置
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(default)]
 pub struct XRedisConfig {
     /// Redis 服务器地址（redis:// URL 格式）
@@ -41,16 +42,16 @@ pub struct XRedisConfig {
     pub username: String,
 
     /// 连接超时（duration 字符串，默认 "500ms"）
-    #[serde(rename = "DialTimeout")]
-    pub dial_timeout: String,
+    #[serde(rename = "DialTimeout", with = "crate::xutil::serde_duration")]
+    pub dial_timeout: Duration,
 
     /// 读超时（duration 字符串，默认 "500ms"）
-    #[serde(rename = "ReadTimeout")]
-    pub read_timeout: String,
+    #[serde(rename = "ReadTimeout", with = "crate::xutil::serde_duration")]
+    pub read_timeout: Duration,
 
     /// 写超时（duration 字符串，默认 "500ms"）
-    #[serde(rename = "WriteTimeout")]
-    pub write_timeout: String,
+    #[serde(rename = "WriteTimeout", with = "crate::xutil::serde_duration")]
+    pub write_timeout: Duration,
 
     /// 最大重试次数（默认 3，设为 0 禁用重试）
     #[serde(rename = "MaxRetries")]
@@ -68,17 +69,17 @@ impl Default for XRedisConfig {
             password: String::new(),
             db: 0,
             username: String::new(),
-            dial_timeout: "500ms".to_string(),
-            read_timeout: "500ms".to_string(),
-            write_timeout: "500ms".to_string(),
+            dial_timeout: Duration::from_millis(500),
+            read_timeout: Duration::from_millis(500),
+            write_timeout: Duration::from_millis(500),
             max_retries: 3,
             name: String::new(),
         }
     }
 }
 
-/// 加载 XRedis 配置（支持单实例和多实例模式）
-pub(crate) fn load_configs() -> Vec<XRedisConfig> {
+/// 加载 XRedis 配置（支持单实例和多实例模式），配置值非法时返回错误
+pub(crate) fn load_configs() -> Result<Vec<XRedisConfig>, crate::error::XOneError> {
     crate::xconfig::parse_config_list::<XRedisConfig>(XREDIS_CONFIG_KEY)
 }
 
@@ -100,7 +101,7 @@ mod tests {
     #[serial]
     fn test_load_configs_no_config_returns_empty() {
         crate::xconfig::reset_config();
-        let result = load_configs();
+        let result = load_configs().unwrap();
         assert!(result.is_empty(), "无配置时应返回空列表");
         crate::xconfig::reset_config();
     }
@@ -115,7 +116,7 @@ mod tests {
 "#;
         let config: serde_yaml::Value = serde_yaml::from_str(yaml).unwrap();
         crate::xconfig::set_config(config);
-        let result = load_configs();
+        let result = load_configs().unwrap();
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].addr, "redis://myhost:6380");
         assert_eq!(result[0].name, "cache");
@@ -134,7 +135,7 @@ mod tests {
 "#;
         let config: serde_yaml::Value = serde_yaml::from_str(yaml).unwrap();
         crate::xconfig::set_config(config);
-        let result = load_configs();
+        let result = load_configs().unwrap();
         assert_eq!(result.len(), 2);
         assert_eq!(result[0].name, "first");
         assert_eq!(result[1].name, "second");
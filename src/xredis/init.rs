@@ -15,7 +15,7 @@ pub fn init_xredis() -> Result<(), crate::error::XOneError> {
         return Ok(());
     }
 
-    let configs = super::config::load_configs();
+    let configs = super::config::load_configs()?;
 
     if configs.is_empty() {
         xutil::info_if_enable_debug("XRedis config empty, skip init");
@@ -72,9 +72,7 @@ pub fn init_xredis() -> Result<(), crate::error::XOneError> {
     let count = store.len();
     drop(store);
 
-    xutil::info_if_enable_debug(&format!(
-        "XRedis init success, client_count=[{count}]"
-    ));
+    xutil::info_if_enable_debug(&format!("XRedis init success, client_count=[{count}]"));
     Ok(())
 }
 
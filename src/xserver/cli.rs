@@ -0,0 +1,88 @@
+//! 内置命令行开关
+//!
+//! `x_one::init()` 启动时识别以下开关，执行后直接退出进程，不启动任何模块：
+//!
+//! - `--version`：输出服务版本（`Server.Version`）
+//! - `--print-config`：输出合并后的最终配置（敏感字段脱敏）
+//! - `--check-config`：加载并按 schema 校验配置（不受 `Server.Validation.Mode` 影响），失败时以非 0 状态码退出
+//!
+//! ```bash
+//! ./my-app --print-config --XAxum.Port=9000
+//! ```
+
+use crate::error::XOneError;
+use crate::xconfig;
+use crate::xutil;
+
+/// 内置命令
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CliCommand {
+    /// `--version`
+    Version,
+    /// `--print-config`
+    PrintConfig,
+    /// `--check-config`
+    CheckConfig,
+}
+
+impl CliCommand {
+    /// 命令行开关名（不含 `--`）
+    pub fn flag(&self) -> &'static str {
+        match self {
+            CliCommand::Version => "version",
+            CliCommand::PrintConfig => "print-config",
+            CliCommand::CheckConfig => "check-config",
+        }
+    }
+}
+
+/// 从启动参数中检测内置命令（同时存在多个时按 version > print-config > check-config 取第一个）
+pub fn detect_cli_command() -> Option<CliCommand> {
+    [
+        CliCommand::Version,
+        CliCommand::PrintConfig,
+        CliCommand::CheckConfig,
+    ]
+    .into_iter()
+    .find(|c| xutil::has_flag_in_args(c.flag()))
+}
+
+/// 加载配置并执行内置命令，返回需要输出的内容
+///
+/// 仅加载一次配置，即使配置了 `Server.Reload.Enable: true` 也不启动配置文件监听。
+pub fn run_cli_command(command: CliCommand) -> Result<String, XOneError> {
+    xconfig::load_store()?;
+    match command {
+        CliCommand::Version => Ok(xconfig::get_server_version()),
+        CliCommand::PrintConfig => {
+            let config = xconfig::get_value("")
+                .unwrap_or(serde_yaml::Value::Mapping(serde_yaml::Mapping::new()));
+            let sanitized = xconfig::init::sanitize_config(&config);
+            serde_yaml::to_string(&sanitized)
+                .map_err(|e| XOneError::Config(format!("print config failed, err=[{e}]")))
+        }
+        CliCommand::CheckConfig => {
+            let config = xconfig::get_value("")
+                .unwrap_or(serde_yaml::Value::Mapping(serde_yaml::Mapping::new()));
+            let violations = xconfig::schema::validate_config(&config);
+            if !violations.is_empty() {
+                return Err(xconfig::schema::violations_error(&violations));
+            }
+            Ok("config check passed".to_string())
+        }
+    }
+}
+
+/// 执行内置命令并退出进程：成功输出到 stdout 并以 0 退出，失败输出到 stderr 并以 1 退出
+pub(crate) fn run_cli_command_and_exit(command: CliCommand) -> ! {
+    match run_cli_command(command) {
+        Ok(output) => {
+            println!("{}", output.trim_end());
+            std::process::exit(0)
+        }
+        Err(e) => {
+            eprintln!("{} failed, err=[{e}]", command.flag());
+            std::process::exit(1)
+        }
+    }
+}
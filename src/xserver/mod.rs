@@ -1,6 +1,7 @@
 //! Server trait 定义和服务运行逻辑
 
 pub mod blocking;
+pub mod cli;
 pub mod runner;
 pub mod server;
 
@@ -13,6 +13,7 @@
 //! x_one::shutdown().ok();
 //! ```
 
+use super::cli;
 use super::server::Server;
 use crate::error::XOneError;
 use crate::xhook;
@@ -39,7 +40,14 @@ static INIT_RESULT: OnceLock<Result<(), String>> = OnceLock::new();
 ///
 /// 只执行一次，后续调用直接返回缓存的结果。
 /// 使用 server 时框架自动调用；不使用 server 时可手动调用。
+///
+/// 启动参数包含 `--version` / `--print-config` / `--check-config` 时，
+/// 仅加载配置并执行对应命令，随后退出进程（见 [`cli`](super::cli)）。
 pub fn init() -> Result<(), XOneError> {
+    if let Some(command) = cli::detect_cli_command() {
+        cli::run_cli_command_and_exit(command);
+    }
+
     INIT_RESULT
         .get_or_init(|| {
             register_builtin_hooks();
@@ -6,9 +6,27 @@
 //! XTrace:
 //!   Enable: true
 //!   Console: false
+//!   Otlp:
+//!     Enable: true
+//!     Endpoint: "http://otel-collector:4318"
+//!     Protocol: "http/protobuf"
+//!     Compression: "gzip"
+//!   Propagators: ["tracecontext", "baggage", "b3multi"]
+//!   Sampler:
+//!     Type: "parentbased_traceidratio"
+//!     Ratio: 0.05
+//!     Rules:
+//!       - Path: "/healthz"
+//!         Ratio: 0
+//!       - Path: "/api/pay/*"
+//!         Ratio: 1
 //! ```
 
+use crate::error::XOneError;
+use opentelemetry_sdk::trace::Sampler;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
 
 /// XTrace 配置 key
 pub const XTRACE_CONFIG_KEY: &str = "XTrace";
@@ -30,17 +48,36 @@ pub struct XTraceConfig {
     /// 是否输出到控制台（默认 false）
     #[serde(rename = "Console", default)]
     pub console: bool,
+
+    /// 通过 OTLP 导出 span（需启用 `trace-otlp` 特性）
+    #[serde(rename = "Otlp", default)]
+    pub otlp: OtlpConfig,
+
+    /// 采样策略（默认 parentbased_always_on）
+    #[serde(rename = "Sampler", default)]
+    pub sampler: SamplerConfig,
+
+    /// 跨进程传播格式（默认 ["tracecontext", "baggage"]），按顺序组合为复合 propagator
+    #[serde(rename = "Propagators", default = "default_propagators")]
+    pub propagators: Vec<PropagatorType>,
 }
 
 fn default_enable() -> bool {
     true
 }
 
+fn default_propagators() -> Vec<PropagatorType> {
+    vec![PropagatorType::TraceContext, PropagatorType::Baggage]
+}
+
 impl Default for XTraceConfig {
     fn default() -> Self {
         Self {
             enable: default_enable(),
             console: false,
+            otlp: OtlpConfig::default(),
+            sampler: SamplerConfig::default(),
+            propagators: default_propagators(),
         }
     }
 }
@@ -52,7 +89,310 @@ impl XTraceConfig {
     }
 }
 
-/// 加载 XTrace 配置
-pub(crate) fn load_config() -> XTraceConfig {
-    crate::xconfig::parse_config::<XTraceConfig>(XTRACE_CONFIG_KEY).unwrap_or_default()
+/// OTLP 传输协议
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum OtlpProtocol {
+    /// HTTP + protobuf，请求 `{Endpoint}/v1/traces`
+    #[default]
+    #[serde(rename = "http/protobuf")]
+    HttpProtobuf,
+    /// HTTP + JSON，请求 `{Endpoint}/v1/traces`
+    #[serde(rename = "http/json")]
+    HttpJson,
+    /// gRPC（HTTP/2），调用 `TraceService/Export`
+    #[serde(rename = "grpc")]
+    Grpc,
+}
+
+impl OtlpProtocol {
+    /// 协议的默认端点（http 为 4318 端口，grpc 为 4317 端口）
+    pub fn default_endpoint(self) -> &'static str {
+        match self {
+            OtlpProtocol::HttpProtobuf | OtlpProtocol::HttpJson => "http://localhost:4318",
+            OtlpProtocol::Grpc => "http://localhost:4317",
+        }
+    }
+}
+
+/// OTLP 请求体压缩方式
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OtlpCompression {
+    /// 不压缩
+    #[default]
+    None,
+    /// gzip 压缩（HTTP 为 `Content-Encoding: gzip`，gRPC 为 `grpc-encoding: gzip`）
+    Gzip,
+}
+
+/// OTLP span 导出配置
+///
+/// 结束的 span 写入 BatchSpanProcessor 的有界队列，由后台线程按 `MaxBatchSize` / `ExportInterval`
+/// 批量发送，队列满时丢弃。`shutdown_xtrace` 时发送队列中剩余的 span。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OtlpConfig {
+    /// 是否启用（默认 false）
+    #[serde(rename = "Enable")]
+    pub enable: bool,
+
+    /// Collector 地址（默认按协议为 `http://localhost:4318` 或 `http://localhost:4317`）
+    ///
+    /// HTTP 协议下未以 `/v1/traces` 结尾时自动追加。
+    #[serde(rename = "Endpoint")]
+    pub endpoint: String,
+
+    /// 传输协议（默认 "http/protobuf"）：http/protobuf / http/json / grpc
+    #[serde(rename = "Protocol")]
+    pub protocol: OtlpProtocol,
+
+    /// 附加的请求头（如鉴权 token）
+    #[serde(rename = "Headers")]
+    pub headers: BTreeMap<String, String>,
+
+    /// 请求体压缩方式（默认 "none"）：none / gzip
+    #[serde(rename = "Compression")]
+    pub compression: OtlpCompression,
+
+    /// 单次发送超时（默认 "10s"）
+    #[serde(rename = "Timeout", with = "crate::xutil::serde_duration")]
+    pub timeout: Duration,
+
+    /// 待发送队列容量（默认 2048），队列满时丢弃新结束的 span
+    #[serde(rename = "MaxQueueSize")]
+    pub max_queue_size: usize,
+
+    /// 单批最多发送的 span 数（默认 512），不超过 `MaxQueueSize`
+    #[serde(rename = "MaxBatchSize")]
+    pub max_batch_size: usize,
+
+    /// 批量发送间隔（默认 "5s"），队列中积累满一批时立即发送
+    #[serde(rename = "ExportInterval", with = "crate::xutil::serde_duration")]
+    pub export_interval: Duration,
+}
+
+impl Default for OtlpConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            endpoint: String::new(),
+            protocol: OtlpProtocol::HttpProtobuf,
+            headers: BTreeMap::new(),
+            compression: OtlpCompression::None,
+            timeout: Duration::from_secs(10),
+            max_queue_size: 2048,
+            max_batch_size: 512,
+            export_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+impl OtlpConfig {
+    /// Collector 地址，未配置时为协议的默认端点
+    pub(crate) fn collector_endpoint(&self) -> &str {
+        let endpoint = self.endpoint.trim();
+        if endpoint.is_empty() {
+            self.protocol.default_endpoint()
+        } else {
+            endpoint.trim_end_matches('/')
+        }
+    }
+
+    /// 实际请求的 URL
+    ///
+    /// ```
+    /// use x_one::xtrace::config::{OtlpConfig, OtlpProtocol};
+    ///
+    /// let mut c = OtlpConfig::default();
+    /// assert_eq!(c.export_url(), "http://localhost:4318/v1/traces");
+    ///
+    /// c.endpoint = "http://collector:4317/".to_string();
+    /// c.protocol = OtlpProtocol::Grpc;
+    /// assert_eq!(
+    ///     c.export_url(),
+    ///     "http://collector:4317/opentelemetry.proto.collector.trace.v1.TraceService/Export"
+    /// );
+    /// ```
+    pub fn export_url(&self) -> String {
+        let endpoint = self.collector_endpoint();
+        match self.protocol {
+            OtlpProtocol::Grpc => {
+                format!("{endpoint}/opentelemetry.proto.collector.trace.v1.TraceService/Export")
+            }
+            _ if endpoint.ends_with("/v1/traces") => endpoint.to_string(),
+            _ => format!("{endpoint}/v1/traces"),
+        }
+    }
+
+    /// 校验参数，队列容量、批大小、发送间隔或超时为 0 时返回错误
+    pub fn validate(&self) -> Result<(), XOneError> {
+        if self.max_queue_size == 0
+            || self.max_batch_size == 0
+            || self.export_interval.is_zero()
+            || self.timeout.is_zero()
+        {
+            return Err(XOneError::Config(format!(
+                "invalid {XTRACE_CONFIG_KEY}.Otlp, MaxQueueSize, MaxBatchSize, ExportInterval and Timeout must be greater than 0"
+            )));
+        }
+        let url = self.export_url();
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(XOneError::Config(format!(
+                "invalid {XTRACE_CONFIG_KEY}.Otlp.Endpoint [{}], expect http:// or https:// url",
+                self.endpoint
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// 跨进程传播格式，取值与 OpenTelemetry `OTEL_PROPAGATORS` 一致
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PropagatorType {
+    /// W3C Trace Context（`traceparent` / `tracestate`）
+    #[serde(rename = "tracecontext")]
+    TraceContext,
+    /// W3C Baggage（`baggage`）
+    Baggage,
+    /// Zipkin B3 单头（`b3`），提取时同时支持多头
+    B3,
+    /// Zipkin B3 多头（`X-B3-TraceId` 等），提取时同时支持单头
+    #[serde(rename = "b3multi")]
+    B3Multi,
+    /// Jaeger（`uber-trace-id`）
+    Jaeger,
+}
+
+/// 采样器类型，取值与 OpenTelemetry `OTEL_TRACES_SAMPLER` 一致
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SamplerType {
+    /// 全部采样
+    AlwaysOn,
+    /// 全部不采样
+    AlwaysOff,
+    /// 按 trace_id 比例采样
+    #[serde(rename = "traceidratio")]
+    TraceIdRatio,
+    /// 有父 span 时沿用父级决定，否则全部采样
+    #[default]
+    #[serde(rename = "parentbased_always_on")]
+    ParentBasedAlwaysOn,
+    /// 有父 span 时沿用父级决定，否则全部不采样
+    #[serde(rename = "parentbased_always_off")]
+    ParentBasedAlwaysOff,
+    /// 有父 span 时沿用父级决定，否则按 trace_id 比例采样
+    #[serde(rename = "parentbased_traceidratio")]
+    ParentBasedTraceIdRatio,
+}
+
+impl SamplerType {
+    /// 是否沿用父 span 的采样决定
+    pub fn is_parent_based(self) -> bool {
+        matches!(
+            self,
+            SamplerType::ParentBasedAlwaysOn
+                | SamplerType::ParentBasedAlwaysOff
+                | SamplerType::ParentBasedTraceIdRatio
+        )
+    }
+}
+
+/// 采样策略配置
+///
+/// `Type` / `Ratio` 作用于所有 span；`Rules` 仅作用于 xaxum `trace_middleware` 创建的
+/// server span，按顺序匹配请求路径与方法，首个命中的规则决定该请求的采样比例。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SamplerConfig {
+    /// 采样器类型（默认 "parentbased_always_on"）
+    #[serde(rename = "Type")]
+    pub sampler_type: SamplerType,
+
+    /// `traceidratio` / `parentbased_traceidratio` 的采样比例（默认 1.0），取值 [0, 1]
+    #[serde(rename = "Ratio")]
+    pub ratio: f64,
+
+    /// 按请求路由的采样规则
+    #[serde(rename = "Rules")]
+    pub rules: Vec<SamplingRuleConfig>,
+}
+
+impl Default for SamplerConfig {
+    fn default() -> Self {
+        Self {
+            sampler_type: SamplerType::ParentBasedAlwaysOn,
+            ratio: 1.0,
+            rules: Vec::new(),
+        }
+    }
+}
+
+impl SamplerConfig {
+    /// 转换为 TracerProvider 使用的 SDK 采样器
+    pub fn to_sampler(&self) -> Sampler {
+        let root = match self.sampler_type {
+            SamplerType::AlwaysOn | SamplerType::ParentBasedAlwaysOn => Sampler::AlwaysOn,
+            SamplerType::AlwaysOff | SamplerType::ParentBasedAlwaysOff => Sampler::AlwaysOff,
+            SamplerType::TraceIdRatio | SamplerType::ParentBasedTraceIdRatio => {
+                Sampler::TraceIdRatioBased(self.ratio)
+            }
+        };
+        if self.sampler_type.is_parent_based() {
+            Sampler::ParentBased(Box::new(root))
+        } else {
+            root
+        }
+    }
+
+    /// 校验参数，比例不在 [0, 1] 或规则 Path 为空时返回错误
+    pub fn validate(&self) -> Result<(), XOneError> {
+        if !is_valid_ratio(self.ratio) {
+            return Err(XOneError::Config(format!(
+                "invalid {XTRACE_CONFIG_KEY}.Sampler.Ratio [{}], expect value in [0, 1]",
+                self.ratio
+            )));
+        }
+        for (i, rule) in self.rules.iter().enumerate() {
+            if rule.path.trim().is_empty() {
+                return Err(XOneError::Config(format!(
+                    "invalid {XTRACE_CONFIG_KEY}.Sampler.Rules[{i}].Path, must not be empty"
+                )));
+            }
+            if !is_valid_ratio(rule.ratio) {
+                return Err(XOneError::Config(format!(
+                    "invalid {XTRACE_CONFIG_KEY}.Sampler.Rules[{i}].Ratio [{}], expect value in [0, 1]",
+                    rule.ratio
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 按请求路由的采样规则
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct SamplingRuleConfig {
+    /// 请求路径，支持 `*` 通配符（如 `/api/pay/*`）
+    #[serde(rename = "Path")]
+    pub path: String,
+
+    /// 请求方法（如 `GET`），为空时匹配所有方法
+    #[serde(rename = "Method")]
+    pub method: String,
+
+    /// 采样比例，取值 [0, 1]；为 0 时不创建 span
+    #[serde(rename = "Ratio")]
+    pub ratio: f64,
+}
+
+fn is_valid_ratio(ratio: f64) -> bool {
+    (0.0..=1.0).contains(&ratio)
+}
+
+/// 加载 XTrace 配置（未配置时使用默认值，配置值非法时返回错误）
+pub(crate) fn load_config() -> Result<XTraceConfig, XOneError> {
+    Ok(crate::xconfig::try_get::<XTraceConfig>(XTRACE_CONFIG_KEY)?.unwrap_or_default())
 }
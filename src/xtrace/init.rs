@@ -4,7 +4,6 @@ use crate::xconfig;
 use crate::xutil;
 use opentelemetry::global;
 use opentelemetry_sdk::Resource;
-use opentelemetry_sdk::propagation::TraceContextPropagator;
 use opentelemetry_sdk::trace::SdkTracerProvider;
 use parking_lot::Mutex;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -26,7 +25,7 @@ fn provider_store() -> &'static Mutex<Option<SdkTracerProvider>> {
 
 /// 初始化 XTrace
 pub fn init_xtrace() -> Result<(), crate::error::XOneError> {
-    let config = super::config::load_config();
+    let config = super::config::load_config()?;
 
     if !config.is_enabled() {
         xutil::info_if_enable_debug("XTrace disabled by config");
@@ -35,26 +34,43 @@ pub fn init_xtrace() -> Result<(), crate::error::XOneError> {
     }
 
     let service_name = xconfig::get_server_name();
-
-    let provider = if config.console {
+    let policy = super::sampler::SamplingPolicy::from_config(&config.sampler)?;
+
+    let mut builder = SdkTracerProvider::builder()
+        .with_resource(Resource::builder().with_service_name(service_name).build())
+        .with_sampler(super::propagation::DeferredAwareSampler::new(
+            config.sampler.to_sampler(),
+        ));
+    if config.console {
         xutil::info_if_enable_debug("XTrace init with console exporter");
-        let exporter = opentelemetry_stdout::SpanExporter::default();
-        SdkTracerProvider::builder()
-            .with_simple_exporter(exporter)
-            .with_resource(Resource::builder().with_service_name(service_name).build())
-            .build()
-    } else {
+        builder = builder.with_simple_exporter(opentelemetry_stdout::SpanExporter::default());
+    }
+    if config.otlp.enable {
+        #[cfg(feature = "trace-otlp")]
+        {
+            xutil::info_if_enable_debug(&format!(
+                "XTrace init with otlp exporter, url: {}",
+                config.otlp.export_url()
+            ));
+            builder = builder.with_span_processor(super::otlp::build_processor(&config.otlp)?);
+        }
+        #[cfg(not(feature = "trace-otlp"))]
+        return Err(crate::error::XOneError::Config(format!(
+            "{}.Otlp requires the trace-otlp feature",
+            super::config::XTRACE_CONFIG_KEY
+        )));
+    }
+    if !config.console && !config.otlp.enable {
         xutil::info_if_enable_debug("XTrace init with noop (no exporter configured)");
-        SdkTracerProvider::builder()
-            .with_resource(Resource::builder().with_service_name(service_name).build())
-            .build()
-    };
+    }
+    let provider = builder.build();
 
     global::set_tracer_provider(provider.clone());
-    global::set_text_map_propagator(TraceContextPropagator::new());
+    global::set_text_map_propagator(super::propagation::build_propagator(&config.propagators));
 
     let mut store = provider_store().lock();
     *store = Some(provider);
+    super::sampler::set_policy(policy);
 
     TRACE_ENABLED.store(true, Ordering::Release);
     xutil::info_if_enable_debug("XTrace init success");
@@ -63,6 +79,7 @@ pub fn init_xtrace() -> Result<(), crate::error::XOneError> {
 
 /// 关闭 XTrace
 ///
+/// 发送 OTLP 批量处理器队列中剩余的 span 后关闭 TracerProvider。
 /// 超时由 xhook 框架通过 `HookOptions.timeout` 统一控制。
 pub fn shutdown_xtrace() -> Result<(), crate::error::XOneError> {
     if !TRACE_ENABLED.load(Ordering::Acquire) {
@@ -76,7 +93,11 @@ pub fn shutdown_xtrace() -> Result<(), crate::error::XOneError> {
         store.take()
     };
 
+    // 先发送批量处理器队列中剩余的 span，再关闭 provider
     let result = if let Some(provider) = provider {
+        if let Err(e) = provider.force_flush() {
+            xutil::warn_if_enable_debug(&format!("XTrace flush failed, err=[{e}]"));
+        }
         provider
             .shutdown()
             .map_err(|e| crate::error::XOneError::Other(format!("XTrace shutdown failed: {e}")))
@@ -84,6 +105,7 @@ pub fn shutdown_xtrace() -> Result<(), crate::error::XOneError> {
         Ok(())
     };
 
+    super::sampler::set_policy(None);
     // 无论 shutdown 成功与否，都重置标志
     TRACE_ENABLED.store(false, Ordering::Release);
     xutil::info_if_enable_debug("XTrace shutdown complete");
@@ -1,10 +1,14 @@
 //! xtrace - 链路追踪模块
 //!
-//! 基于 OpenTelemetry 实现，提供 TracerProvider 初始化、
-//! Tracer 获取、Trace 启用判断等功能。
+//! 基于 OpenTelemetry 实现，提供 TracerProvider 初始化、OTLP span 批量导出、
+//! 采样策略、跨进程传播格式、Tracer 获取、Trace 启用判断等功能。
 
 pub mod config;
 pub mod init;
+#[cfg(feature = "trace-otlp")]
+pub mod otlp;
+pub mod propagation;
+pub mod sampler;
 
 pub use config::XTraceConfig;
 pub use init::is_trace_enabled;
@@ -0,0 +1,198 @@
+//! OTLP span 导出
+//!
+//! [`build_processor`] 按 `XTrace.Otlp` 创建 OpenTelemetry SDK 的 `BatchSpanProcessor`：
+//! 结束的 span 写入容量为 `MaxQueueSize` 的队列（满时丢弃），后台线程满 `MaxBatchSize` 个
+//! 或每隔 `ExportInterval` 交给 `opentelemetry-otlp` 的 `SpanExporter` 发送一批：
+//!
+//! - `http/protobuf` / `http/json`：`POST {Endpoint}/v1/traces`
+//! - `grpc`：经 tonic 调用 `opentelemetry.proto.collector.trace.v1.TraceService/Export`
+//!
+//! `Compression` 为 gzip 时压缩请求体。
+
+use super::config::{OtlpCompression, OtlpConfig, OtlpProtocol, XTRACE_CONFIG_KEY};
+use crate::error::XOneError;
+use crate::xutil;
+use opentelemetry_http::{Bytes, HttpClient, HttpError, Request, Response};
+use opentelemetry_otlp::{WithExportConfig, WithHttpConfig, WithTonicConfig};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::error::OTelSdkResult;
+use opentelemetry_sdk::trace::{BatchConfigBuilder, BatchSpanProcessor, SpanData, SpanExporter};
+use reqwest::header::{CONTENT_ENCODING, HeaderValue};
+use std::collections::HashMap;
+use std::future::Future;
+use std::io::Write as _;
+use std::pin::Pin;
+use std::sync::OnceLock;
+use tonic::metadata::{Ascii, MetadataKey, MetadataMap, MetadataValue};
+
+/// 按配置创建批量发送到 OTLP Collector 的 span 处理器，参数非法时返回错误
+pub fn build_processor(c: &OtlpConfig) -> Result<BatchSpanProcessor, XOneError> {
+    c.validate()?;
+    let exporter = build_exporter(c)?;
+    let config = BatchConfigBuilder::default()
+        .with_max_queue_size(c.max_queue_size)
+        .with_max_export_batch_size(c.max_batch_size)
+        .with_scheduled_delay(c.export_interval)
+        .build();
+    let exporter = LoggingExporter {
+        inner: exporter,
+        runtime: match c.protocol {
+            OtlpProtocol::Grpc => Some(grpc_runtime()?),
+            _ => None,
+        },
+    };
+    Ok(BatchSpanProcessor::builder(exporter)
+        .with_batch_config(config)
+        .build())
+}
+
+// ---- 以下为私有实现 ----
+
+/// gRPC 导出使用的运行时，tonic 通道的后台任务与请求都在其上执行，进程内只创建一次
+fn grpc_runtime() -> Result<&'static tokio::runtime::Runtime, XOneError> {
+    static INSTANCE: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+    if let Some(runtime) = INSTANCE.get() {
+        return Ok(runtime);
+    }
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(1)
+        .thread_name("xtrace-otlp-grpc")
+        .enable_all()
+        .build()
+        .map_err(|e| {
+            XOneError::Other(format!("XTrace build otlp grpc runtime failed, err=[{e}]"))
+        })?;
+    Ok(INSTANCE.get_or_init(|| runtime))
+}
+
+fn build_exporter(c: &OtlpConfig) -> Result<opentelemetry_otlp::SpanExporter, XOneError> {
+    let mut metadata = MetadataMap::new();
+    for (name, value) in &c.headers {
+        let key = MetadataKey::<Ascii>::from_bytes(name.as_bytes());
+        let value = MetadataValue::<Ascii>::try_from(value.as_str());
+        let (Ok(key), Ok(value)) = (key, value) else {
+            return Err(XOneError::Config(format!(
+                "invalid {XTRACE_CONFIG_KEY}.Otlp.Headers entry [{name}]"
+            )));
+        };
+        metadata.insert(key, value);
+    }
+
+    let exporter = match c.protocol {
+        OtlpProtocol::Grpc => {
+            let mut builder = opentelemetry_otlp::SpanExporter::builder()
+                .with_tonic()
+                .with_endpoint(c.collector_endpoint())
+                .with_timeout(c.timeout)
+                .with_metadata(metadata);
+            if c.compression == OtlpCompression::Gzip {
+                builder = builder.with_compression(opentelemetry_otlp::Compression::Gzip);
+            }
+            // tonic 在创建通道时需要 tokio 运行时上下文
+            let _guard = grpc_runtime()?.enter();
+            builder.build()
+        }
+        OtlpProtocol::HttpProtobuf | OtlpProtocol::HttpJson => {
+            let protocol = match c.protocol {
+                OtlpProtocol::HttpJson => opentelemetry_otlp::Protocol::HttpJson,
+                _ => opentelemetry_otlp::Protocol::HttpBinary,
+            };
+            let headers: HashMap<String, String> = c
+                .headers
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect();
+            let client = build_http_client(c)?;
+            opentelemetry_otlp::SpanExporter::builder()
+                .with_http()
+                .with_http_client(GzipHttpClient {
+                    client,
+                    gzip: c.compression == OtlpCompression::Gzip,
+                })
+                .with_protocol(protocol)
+                .with_endpoint(c.export_url())
+                .with_timeout(c.timeout)
+                .with_headers(headers)
+                .build()
+        }
+    };
+    exporter.map_err(|e| XOneError::Other(format!("XTrace init otlp exporter failed, err=[{e}]")))
+}
+
+/// 创建阻塞 HTTP 客户端
+///
+/// 阻塞客户端内部持有运行时，在独立线程中创建以免调用方处于 tokio 运行时内时报错。
+fn build_http_client(c: &OtlpConfig) -> Result<reqwest::blocking::Client, XOneError> {
+    let timeout = c.timeout;
+    std::thread::spawn(move || {
+        reqwest::blocking::Client::builder()
+            .timeout(timeout)
+            .build()
+    })
+    .join()
+    .map_err(|_| XOneError::Other("XTrace build otlp http client panicked".to_string()))?
+    .map_err(|e| XOneError::Other(format!("XTrace build otlp http client failed, err=[{e}]")))
+}
+
+/// 按需 gzip 压缩请求体的 HTTP 客户端
+#[derive(Debug)]
+struct GzipHttpClient {
+    client: reqwest::blocking::Client,
+    gzip: bool,
+}
+
+#[async_trait::async_trait]
+impl HttpClient for GzipHttpClient {
+    async fn send_bytes(&self, mut request: Request<Bytes>) -> Result<Response<Bytes>, HttpError> {
+        if self.gzip {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(request.body())?;
+            *request.body_mut() = Bytes::from(encoder.finish()?);
+            request
+                .headers_mut()
+                .insert(CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+        }
+        self.client.send_bytes(request).await
+    }
+}
+
+/// 发送失败时输出调试日志的 SpanExporter
+///
+/// gRPC 请求在 [`grpc_runtime`] 上执行，HTTP 请求由 `BatchSpanProcessor` 的后台线程同步发送。
+#[derive(Debug)]
+struct LoggingExporter {
+    inner: opentelemetry_otlp::SpanExporter,
+    runtime: Option<&'static tokio::runtime::Runtime>,
+}
+
+impl SpanExporter for LoggingExporter {
+    fn export(
+        &mut self,
+        batch: Vec<SpanData>,
+    ) -> Pin<Box<dyn Future<Output = OTelSdkResult> + Send + 'static>> {
+        let count = batch.len();
+        let export = self.inner.export(batch);
+        let runtime = self.runtime;
+        Box::pin(async move {
+            let result = match runtime {
+                Some(runtime) => runtime.block_on(export),
+                None => export.await,
+            };
+            if let Err(e) = &result {
+                xutil::warn_if_enable_debug(&format!(
+                    "XTrace otlp export failed, dropped=[{count}], err=[{e}]"
+                ));
+            }
+            result
+        })
+    }
+
+    fn shutdown(&mut self) -> OTelSdkResult {
+        self.inner.shutdown()
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.inner.set_resource(resource);
+    }
+}
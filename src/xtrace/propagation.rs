@@ -0,0 +1,332 @@
+//! 跨进程传播格式
+//!
+//! 按 `XTrace.Propagators` 组合 W3C Trace Context、W3C Baggage、B3 与 Jaeger propagator，
+//! 由 `init_xtrace` 设置为全局 propagator，xaxum `trace_middleware` 提取与 xhttp 出站注入共用。
+//!
+//! B3 请求头未携带采样状态（deferred）时，提取出的远端 span 上下文附带推迟标记，
+//! 由 [`DeferredAwareSampler`] 交给本地根采样器决定，而不是按父级未采样丢弃。
+//!
+//! ```
+//! use opentelemetry::propagation::TextMapPropagator;
+//! use std::collections::HashMap;
+//! use x_one::xtrace::config::PropagatorType;
+//! use x_one::xtrace::propagation::build_propagator;
+//!
+//! let propagator = build_propagator(&[PropagatorType::TraceContext, PropagatorType::Jaeger]);
+//! let mut headers = HashMap::new();
+//! headers.insert(
+//!     "uber-trace-id".to_string(),
+//!     "4bf92f3577b34da6a3ce929d0e0e4736:00f067aa0ba902b7:0:1".to_string(),
+//! );
+//! let cx = propagator.extract(&headers);
+//! # use opentelemetry::trace::TraceContextExt;
+//! assert!(cx.span().span_context().is_sampled());
+//! ```
+
+use super::config::PropagatorType;
+use opentelemetry::propagation::text_map_propagator::FieldIter;
+use opentelemetry::propagation::{
+    Extractor, Injector, TextMapCompositePropagator, TextMapPropagator,
+};
+use opentelemetry::trace::{
+    Link, SamplingResult, SpanContext, SpanId, SpanKind, TraceContextExt, TraceFlags, TraceId,
+    TraceState,
+};
+use opentelemetry::{Context, KeyValue};
+use opentelemetry_sdk::propagation::{BaggagePropagator, TraceContextPropagator};
+use opentelemetry_sdk::trace::ShouldSample;
+use std::sync::OnceLock;
+
+const B3_SINGLE_HEADER: &str = "b3";
+const B3_TRACE_ID_HEADER: &str = "x-b3-traceid";
+const B3_SPAN_ID_HEADER: &str = "x-b3-spanid";
+const B3_SAMPLED_HEADER: &str = "x-b3-sampled";
+const B3_FLAGS_HEADER: &str = "x-b3-flags";
+const B3_PARENT_SPAN_ID_HEADER: &str = "x-b3-parentspanid";
+const JAEGER_HEADER: &str = "uber-trace-id";
+
+/// 按配置顺序组合 propagator，重复项只保留首个
+///
+/// 提取时按顺序执行，后面的格式覆盖前面提取到的 span 上下文；注入时写入所有格式的请求头。
+pub fn build_propagator(types: &[PropagatorType]) -> TextMapCompositePropagator {
+    let mut seen = Vec::with_capacity(types.len());
+    let mut propagators: Vec<Box<dyn TextMapPropagator + Send + Sync>> = Vec::new();
+    for &t in types {
+        if seen.contains(&t) {
+            continue;
+        }
+        seen.push(t);
+        propagators.push(match t {
+            PropagatorType::TraceContext => Box::new(TraceContextPropagator::new()),
+            PropagatorType::Baggage => Box::new(BaggagePropagator::new()),
+            PropagatorType::B3 => Box::new(B3Propagator::single_header()),
+            PropagatorType::B3Multi => Box::new(B3Propagator::multiple_headers()),
+            PropagatorType::Jaeger => Box::new(JaegerPropagator::new()),
+        });
+    }
+    TextMapCompositePropagator::new(propagators)
+}
+
+/// Zipkin B3 propagator
+///
+/// 提取时优先读取单头 `b3`，不存在时读取 `X-B3-*` 多头；注入格式由构造方式决定。
+#[derive(Debug, Clone, Copy)]
+pub struct B3Propagator {
+    single: bool,
+}
+
+impl B3Propagator {
+    /// 注入单头 `b3: {TraceId}-{SpanId}-{Sampled}`
+    pub fn single_header() -> Self {
+        Self { single: true }
+    }
+
+    /// 注入多头 `X-B3-TraceId` / `X-B3-SpanId` / `X-B3-Sampled`
+    pub fn multiple_headers() -> Self {
+        Self { single: false }
+    }
+
+    fn extract_single(&self, extractor: &dyn Extractor) -> Option<(SpanContext, bool)> {
+        let value = extractor.get(B3_SINGLE_HEADER)?.trim();
+        let mut parts = value.split('-');
+        let trace_id = parse_trace_id(parts.next()?)?;
+        let span_id = parse_span_id(parts.next()?)?;
+        // 第三段为采样状态，第四段为父 span id（忽略）
+        let sampled = parse_b3_sampling_state(parts.next())?;
+        Some(b3_span_context(trace_id, span_id, sampled))
+    }
+
+    fn extract_multi(&self, extractor: &dyn Extractor) -> Option<(SpanContext, bool)> {
+        let trace_id = parse_trace_id(extractor.get(B3_TRACE_ID_HEADER)?.trim())?;
+        let span_id = parse_span_id(extractor.get(B3_SPAN_ID_HEADER)?.trim())?;
+        // X-B3-Flags: 1 表示 debug，隐含采样
+        let sampled = if extractor.get(B3_FLAGS_HEADER).map(str::trim) == Some("1") {
+            Some(true)
+        } else {
+            parse_b3_sampling_state(extractor.get(B3_SAMPLED_HEADER).map(str::trim))?
+        };
+        Some(b3_span_context(trace_id, span_id, sampled))
+    }
+}
+
+impl TextMapPropagator for B3Propagator {
+    fn inject_context(&self, cx: &Context, injector: &mut dyn Injector) {
+        let span = cx.span();
+        let sc = span.span_context();
+        if !sc.is_valid() {
+            return;
+        }
+        let sampled = if sc.is_sampled() { "1" } else { "0" };
+        if self.single {
+            injector.set(
+                B3_SINGLE_HEADER,
+                format!("{}-{}-{sampled}", sc.trace_id(), sc.span_id()),
+            );
+        } else {
+            injector.set(B3_TRACE_ID_HEADER, sc.trace_id().to_string());
+            injector.set(B3_SPAN_ID_HEADER, sc.span_id().to_string());
+            injector.set(B3_SAMPLED_HEADER, sampled.to_string());
+        }
+    }
+
+    fn extract_with_context(&self, cx: &Context, extractor: &dyn Extractor) -> Context {
+        match self
+            .extract_single(extractor)
+            .or_else(|| self.extract_multi(extractor))
+        {
+            Some((sc, deferred)) => {
+                let cx = cx.with_remote_span_context(sc.clone());
+                if deferred {
+                    cx.with_value(DeferredSampling {
+                        trace_id: sc.trace_id(),
+                        span_id: sc.span_id(),
+                    })
+                } else {
+                    cx
+                }
+            }
+            None => cx.clone(),
+        }
+    }
+
+    fn fields(&self) -> FieldIter<'_> {
+        static SINGLE: OnceLock<[String; 1]> = OnceLock::new();
+        static MULTI: OnceLock<[String; 5]> = OnceLock::new();
+        if self.single {
+            FieldIter::new(SINGLE.get_or_init(|| [B3_SINGLE_HEADER.to_string()]))
+        } else {
+            FieldIter::new(MULTI.get_or_init(|| {
+                [
+                    B3_TRACE_ID_HEADER.to_string(),
+                    B3_SPAN_ID_HEADER.to_string(),
+                    B3_SAMPLED_HEADER.to_string(),
+                    B3_FLAGS_HEADER.to_string(),
+                    B3_PARENT_SPAN_ID_HEADER.to_string(),
+                ]
+            }))
+        }
+    }
+}
+
+/// Jaeger propagator，读写 `uber-trace-id: {TraceId}:{SpanId}:{ParentSpanId}:{Flags}`
+///
+/// 仅传播 span 上下文，不处理 `uberctx-*` baggage。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JaegerPropagator;
+
+impl JaegerPropagator {
+    /// 创建 Jaeger propagator
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl TextMapPropagator for JaegerPropagator {
+    fn inject_context(&self, cx: &Context, injector: &mut dyn Injector) {
+        let span = cx.span();
+        let sc = span.span_context();
+        if !sc.is_valid() {
+            return;
+        }
+        let flags = if sc.is_sampled() { "1" } else { "0" };
+        injector.set(
+            JAEGER_HEADER,
+            format!("{}:{}:0:{flags}", sc.trace_id(), sc.span_id()),
+        );
+    }
+
+    fn extract_with_context(&self, cx: &Context, extractor: &dyn Extractor) -> Context {
+        match extract_jaeger(extractor) {
+            Some(sc) => cx.with_remote_span_context(sc),
+            None => cx.clone(),
+        }
+    }
+
+    fn fields(&self) -> FieldIter<'_> {
+        static FIELDS: OnceLock<[String; 1]> = OnceLock::new();
+        FieldIter::new(FIELDS.get_or_init(|| [JAEGER_HEADER.to_string()]))
+    }
+}
+
+/// 远端 span 的采样决定是否被推迟（B3 请求头未携带采样状态）
+///
+/// 仅当 `parent_cx` 中的 span 为远端 span 且正是推迟标记对应的 span 时返回 true，
+/// 其后提取的其他格式覆盖了 span 上下文时不再视为推迟。
+pub fn is_sampling_deferred(parent_cx: &Context) -> bool {
+    let span = parent_cx.span();
+    let sc = span.span_context();
+    sc.is_remote()
+        && parent_cx
+            .get::<DeferredSampling>()
+            .is_some_and(|d| d.trace_id == sc.trace_id() && d.span_id == sc.span_id())
+}
+
+/// 支持推迟采样的采样器包装
+///
+/// 父级为采样决定被推迟的远端 span 时，按根 span 交给内部采样器决定（沿用远端 trace_id），
+/// 其他情况直接委托内部采样器。`init_xtrace` 用其包装 `XTrace.Sampler` 配置的采样器。
+#[derive(Debug, Clone)]
+pub struct DeferredAwareSampler<S> {
+    inner: S,
+}
+
+impl<S> DeferredAwareSampler<S> {
+    /// 包装采样器
+    pub fn new(inner: S) -> Self {
+        Self { inner }
+    }
+}
+
+impl<S: ShouldSample + Clone + 'static> ShouldSample for DeferredAwareSampler<S> {
+    fn should_sample(
+        &self,
+        parent_context: Option<&Context>,
+        trace_id: TraceId,
+        name: &str,
+        span_kind: &SpanKind,
+        attributes: &[KeyValue],
+        links: &[Link],
+    ) -> SamplingResult {
+        let root = Context::new();
+        let parent_context = match parent_context {
+            Some(cx) if is_sampling_deferred(cx) => Some(&root),
+            other => other,
+        };
+        self.inner
+            .should_sample(parent_context, trace_id, name, span_kind, attributes, links)
+    }
+}
+
+// ---- 以下为私有实现 ----
+
+/// 推迟采样标记，记录对应的远端 span
+#[derive(Debug, Clone, Copy)]
+struct DeferredSampling {
+    trace_id: TraceId,
+    span_id: SpanId,
+}
+
+/// 解析 B3 采样状态：`1` / `d` / `true` 为采样，`0` / `false` 为不采样，
+/// 缺失时为推迟（`Some(None)`），无法识别时返回 `None`
+fn parse_b3_sampling_state(state: Option<&str>) -> Option<Option<bool>> {
+    match state {
+        None | Some("") => Some(None),
+        Some("1" | "d" | "true") => Some(Some(true)),
+        Some("0" | "false") => Some(Some(false)),
+        Some(_) => None,
+    }
+}
+
+/// 构建 B3 远端 span 上下文，返回是否推迟采样
+fn b3_span_context(
+    trace_id: TraceId,
+    span_id: SpanId,
+    sampled: Option<bool>,
+) -> (SpanContext, bool) {
+    (
+        remote_span_context(trace_id, span_id, sampled.unwrap_or(false)),
+        sampled.is_none(),
+    )
+}
+
+fn extract_jaeger(extractor: &dyn Extractor) -> Option<SpanContext> {
+    let value = extractor.get(JAEGER_HEADER)?.trim();
+    // 部分客户端会对冒号做 URL 编码
+    let value = value.replace("%3A", ":").replace("%3a", ":");
+    let parts: Vec<&str> = value.split(':').collect();
+    let [trace_id, span_id, _parent, flags] = parts.as_slice() else {
+        return None;
+    };
+    let trace_id = parse_trace_id(trace_id)?;
+    let span_id = parse_span_id(span_id)?;
+    // flags 第 1 位为采样，第 2 位为 debug（隐含采样）
+    let flags = u8::from_str_radix(flags, 16).ok()?;
+    Some(remote_span_context(trace_id, span_id, flags & 0x03 != 0))
+}
+
+/// 解析 16 或 32 位十六进制 trace id（不足 32 位时左侧补零）
+fn parse_trace_id(s: &str) -> Option<TraceId> {
+    if s.is_empty() || s.len() > 32 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let id = TraceId::from_hex(s).ok()?;
+    (id != TraceId::INVALID).then_some(id)
+}
+
+/// 解析最长 16 位的十六进制 span id
+fn parse_span_id(s: &str) -> Option<SpanId> {
+    if s.is_empty() || s.len() > 16 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let id = SpanId::from_hex(s).ok()?;
+    (id != SpanId::INVALID).then_some(id)
+}
+
+fn remote_span_context(trace_id: TraceId, span_id: SpanId, sampled: bool) -> SpanContext {
+    let flags = if sampled {
+        TraceFlags::SAMPLED
+    } else {
+        TraceFlags::default()
+    };
+    SpanContext::new(trace_id, span_id, flags, true, TraceState::default())
+}
@@ -0,0 +1,137 @@
+//! 按请求路由采样
+//!
+//! `XTrace.Sampler.Rules` 编译为 [`SamplingPolicy`]，由 xaxum `trace_middleware` 在创建
+//! server span 前按请求方法与路径匹配：
+//!
+//! - 未命中任何规则时由 TracerProvider 的采样器（`XTrace.Sampler.Type`）决定
+//! - 命中 `Ratio: 0` 的规则时不创建 span，请求直接透传
+//! - 命中其他规则时按该规则的比例采样；`Type` 为 `parentbased_*` 时，
+//!   带有父 span 的请求仍沿用父级决定
+//!
+//! ```
+//! use x_one::xtrace::config::{SamplerConfig, SamplingRuleConfig};
+//! use x_one::xtrace::sampler::SamplingPolicy;
+//!
+//! let c = SamplerConfig {
+//!     rules: vec![SamplingRuleConfig {
+//!         path: "/healthz".to_string(),
+//!         ratio: 0.0,
+//!         ..Default::default()
+//!     }],
+//!     ..Default::default()
+//! };
+//! let policy = SamplingPolicy::from_config(&c).unwrap().unwrap();
+//! assert!(policy.match_rule("GET", "/healthz").unwrap().is_drop());
+//! assert!(policy.match_rule("GET", "/api/user").is_none());
+//! ```
+
+use super::config::{SamplerConfig, SamplingRuleConfig};
+use super::propagation::DeferredAwareSampler;
+use crate::error::XOneError;
+use crate::xutil::glob_match;
+use opentelemetry::Context;
+use opentelemetry::trace::{SamplingResult, SpanKind, TraceId};
+use opentelemetry_sdk::trace::{Sampler, ShouldSample};
+use parking_lot::RwLock;
+use std::sync::{Arc, OnceLock};
+
+/// 编译后的按路由采样策略
+#[derive(Debug)]
+pub struct SamplingPolicy {
+    rules: Vec<SamplingRule>,
+}
+
+impl SamplingPolicy {
+    /// 从配置创建策略，未配置规则时返回 `None`，参数非法时返回错误
+    pub fn from_config(c: &SamplerConfig) -> Result<Option<Self>, XOneError> {
+        c.validate()?;
+        if c.rules.is_empty() {
+            return Ok(None);
+        }
+        let parent_based = c.sampler_type.is_parent_based();
+        let rules = c
+            .rules
+            .iter()
+            .map(|rule| SamplingRule::new(rule, parent_based))
+            .collect();
+        Ok(Some(Self { rules }))
+    }
+
+    /// 按顺序匹配请求方法与路径，返回首个命中的规则
+    pub fn match_rule(&self, method: &str, path: &str) -> Option<&SamplingRule> {
+        self.rules.iter().find(|rule| rule.matches(method, path))
+    }
+}
+
+/// 单条采样规则
+#[derive(Debug)]
+pub struct SamplingRule {
+    path: String,
+    method: Option<String>,
+    ratio: f64,
+    sampler: DeferredAwareSampler<Sampler>,
+}
+
+impl SamplingRule {
+    fn new(c: &SamplingRuleConfig, parent_based: bool) -> Self {
+        let ratio = Sampler::TraceIdRatioBased(c.ratio);
+        let method = c.method.trim();
+        Self {
+            path: c.path.trim().to_string(),
+            method: (!method.is_empty()).then(|| method.to_string()),
+            ratio: c.ratio,
+            sampler: DeferredAwareSampler::new(if parent_based {
+                Sampler::ParentBased(Box::new(ratio))
+            } else {
+                ratio
+            }),
+        }
+    }
+
+    fn matches(&self, method: &str, path: &str) -> bool {
+        self.method
+            .as_deref()
+            .is_none_or(|m| m.eq_ignore_ascii_case(method))
+            && glob_match(&self.path, path)
+    }
+
+    /// 规则的采样比例
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    /// 比例为 0，命中的请求不创建 span
+    pub fn is_drop(&self) -> bool {
+        self.ratio <= 0.0
+    }
+
+    /// 对即将创建的 span 做采样决定
+    ///
+    /// `trace_id` 为 span 所属的 trace：有父 span 时为父级 trace_id，否则为新生成的 id。
+    /// 父级采样决定被推迟时（B3 未携带采样状态）按规则比例决定。
+    pub fn should_sample(
+        &self,
+        parent_cx: &Context,
+        trace_id: TraceId,
+        name: &str,
+        kind: &SpanKind,
+    ) -> SamplingResult {
+        self.sampler
+            .should_sample(Some(parent_cx), trace_id, name, kind, &[], &[])
+    }
+}
+
+fn policy_store() -> &'static RwLock<Option<Arc<SamplingPolicy>>> {
+    static INSTANCE: OnceLock<RwLock<Option<Arc<SamplingPolicy>>>> = OnceLock::new();
+    INSTANCE.get_or_init(|| RwLock::new(None))
+}
+
+/// 当前生效的策略，未配置规则时返回 `None`
+pub fn policy() -> Option<Arc<SamplingPolicy>> {
+    policy_store().read().clone()
+}
+
+/// 替换全局策略（`init_xtrace` 按配置调用）
+pub fn set_policy(policy: Option<SamplingPolicy>) {
+    *policy_store().write() = policy.map(Arc::new);
+}
@@ -12,8 +12,68 @@ pub fn get_config_from_args(key: &str) -> Option<String> {
     find_arg_value(key, &args)
 }
 
+/// 判断启动命令行参数中是否包含指定开关（如 `--version`）
+pub fn has_flag_in_args(flag: &str) -> bool {
+    let args = get_os_args();
+    find_flag(flag, &args)
+}
+
+/// 从启动命令行参数中获取配置覆盖项
+///
+/// 识别以大写字母开头的 key（如 `--XAxum.Port=9000`、`--XAxum.Port 9000`），
+/// 按出现顺序返回 `(key, value)`，`--server.config.location` 等小写内置参数不会被识别。
+pub fn get_override_args() -> Vec<(String, String)> {
+    let args = get_os_args();
+    find_override_args(&args)
+}
+
 // ---- 以下为私有实现 ----
 
+/// 在参数列表中查找开关
+fn find_flag(flag: &str, args: &[String]) -> bool {
+    args.iter()
+        .any(|arg| arg.strip_prefix("--").is_some_and(|f| f == flag))
+}
+
+/// 在参数列表中查找配置覆盖项
+fn find_override_args(args: &[String]) -> Vec<(String, String)> {
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let Some(after_dash) = args[i].strip_prefix("--") else {
+            i += 1;
+            continue;
+        };
+
+        let (key, value) = match after_dash.split_once('=') {
+            Some((key, value)) => (key, Some(value.to_string())),
+            None => (after_dash, None),
+        };
+        if !is_override_arg_key(key) {
+            i += 1;
+            continue;
+        }
+
+        match value {
+            Some(value) => result.push((key.to_string(), value)),
+            // 空格分隔：下一个参数不是 -- 开头时作为值
+            None => {
+                if let Some(next) = args.get(i + 1).filter(|n| !n.starts_with("--")) {
+                    result.push((key.to_string(), next.clone()));
+                    i += 1;
+                }
+            }
+        }
+        i += 1;
+    }
+    result
+}
+
+/// 配置覆盖 key：合法参数 key 且首字符为大写字母
+fn is_override_arg_key(key: &str) -> bool {
+    is_valid_arg_key(key) && key.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
 /// 在参数列表中查找指定 key 的值
 fn find_arg_value(key: &str, args: &[String]) -> Option<String> {
     if !is_valid_arg_key(key) {
@@ -122,6 +182,44 @@ mod tests {
         assert_eq!(find_arg_value("config", &args), None);
     }
 
+    // ---- find_flag 测试 ----
+
+    #[test]
+    fn test_find_flag() {
+        let args = vec!["--print-config".to_string(), "--XAxum.Port=1".to_string()];
+        assert!(find_flag("print-config", &args));
+        assert!(!find_flag("version", &args));
+        assert!(!find_flag("XAxum.Port", &args));
+    }
+
+    // ---- find_override_args 测试 ----
+
+    #[test]
+    fn test_find_override_args_both_formats() {
+        let args = vec![
+            "--XAxum.Port=9000".to_string(),
+            "--server.profiles.active=dev".to_string(),
+            "--XLog.Level".to_string(),
+            "debug".to_string(),
+            "--print-config".to_string(),
+            "--XOrm.DSN=a=b".to_string(),
+        ];
+        assert_eq!(
+            find_override_args(&args),
+            vec![
+                ("XAxum.Port".to_string(), "9000".to_string()),
+                ("XLog.Level".to_string(), "debug".to_string()),
+                ("XOrm.DSN".to_string(), "a=b".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_override_args_missing_value_skipped() {
+        let args = vec!["--XLog.Level".to_string(), "--version".to_string()];
+        assert!(find_override_args(&args).is_empty());
+    }
+
     #[test]
     fn test_find_arg_value_space_separated_last_arg_returns_none() {
         // --config 是最后一个参数，后面没有 value
@@ -25,3 +25,50 @@ pub fn to_duration(s: &str) -> Option<Duration> {
 
     humantime::parse_duration(s).ok()
 }
+
+/// 将人类可读的字节大小字符串转换为字节数
+///
+/// 支持十进制单位 `B`、`K`/`KB`、`M`/`MB`、`G`/`GB`、`T`/`TB`（1000 进制）
+/// 和二进制单位 `KiB`、`MiB`、`GiB`、`TiB`（1024 进制），单位忽略大小写，
+/// 数值与单位之间允许空格，纯数字按字节处理。
+///
+/// 空字符串、解析失败或溢出时返回 `None`。
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(x_one::xutil::to_byte_size("64MiB"), Some(64 * 1024 * 1024));
+/// assert_eq!(x_one::xutil::to_byte_size("1.5KB"), Some(1500));
+/// assert_eq!(x_one::xutil::to_byte_size("512"), Some(512));
+/// assert_eq!(x_one::xutil::to_byte_size("10XB"), None);
+/// ```
+pub fn to_byte_size(s: &str) -> Option<u64> {
+    let s = s.trim();
+    if s.is_empty() {
+        return None;
+    }
+
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" => 1000,
+        "m" | "mb" => 1000_u64.pow(2),
+        "g" | "gb" => 1000_u64.pow(3),
+        "t" | "tb" => 1000_u64.pow(4),
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        "tib" => 1 << 40,
+        _ => return None,
+    };
+
+    if let Ok(n) = number.parse::<u64>() {
+        return n.checked_mul(multiplier);
+    }
+    let n = number.parse::<f64>().ok()?;
+    let bytes = n * multiplier as f64;
+    (bytes.is_finite() && bytes >= 0.0 && bytes <= u64::MAX as f64).then(|| bytes.round() as u64)
+}
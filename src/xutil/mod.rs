@@ -1,7 +1,7 @@
 //! xutil - 通用工具模块
 //!
 //! 提供文件操作、JSON 序列化、环境变量、命令行解析、
-//! 重试机制、时长转换等基础工具函数。
+//! 重试机制、时长与字节大小转换、通配符匹配等基础工具函数。
 
 pub mod cmd;
 pub mod convert;
@@ -12,18 +12,22 @@ pub mod file;
 pub mod future;
 pub mod json;
 pub mod net;
+pub mod pattern;
 pub mod pool;
 pub mod retry;
+pub mod serde_byte_size;
+pub mod serde_duration;
 pub mod spawn;
 
 // Re-export 常用 API，方便外部使用 xutil::xxx 调用
-pub use cmd::get_config_from_args;
-pub use convert::to_duration;
+pub use cmd::{get_config_from_args, get_override_args, has_flag_in_args};
+pub use convert::{to_byte_size, to_duration};
 pub use debug_log::{error_if_enable_debug, info_if_enable_debug, warn_if_enable_debug};
 pub use default_value::{IsZero, default_if_empty, take_or_default};
 pub use env::{DEBUG_KEY, xone_enable_debug};
 pub use file::{dir_exist, file_exist};
 pub use json::{to_json_string, to_json_string_indent};
 pub use net::{get_local_ip, get_local_private_ip, get_local_public_ip};
+pub use pattern::glob_match;
 pub use retry::{retry, retry_async};
 pub use spawn::{extract_panic_message, spawn_safe};
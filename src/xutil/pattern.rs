@@ -0,0 +1,33 @@
+//! 通配符匹配工具
+
+/// `*` 通配符匹配，`*` 匹配任意长度（含空）的字符序列，区分大小写
+///
+/// # Examples
+///
+/// ```
+/// use x_one::xutil::glob_match;
+///
+/// assert!(glob_match("/api/pay/*", "/api/pay/order"));
+/// assert!(glob_match("*token*", "access_token_v2"));
+/// assert!(glob_match("/healthz", "/healthz"));
+/// assert!(!glob_match("/healthz", "/healthz/live"));
+/// ```
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // 不含通配符，需完全相同
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
@@ -0,0 +1,51 @@
+//! 字节大小字段的 serde 适配
+//!
+//! 配置中的字节大小可写作整数（字节数）或带单位的字符串（如 `"512MiB"`、`"1GB"`），
+//! 通过 `#[serde(with = "...")]` 直接反序列化为 `u64`，无法解析时返回反序列化错误。
+//!
+//! ```
+//! #[derive(serde::Deserialize)]
+//! struct MyConfig {
+//!     #[serde(rename = "MaxSize", with = "x_one::xutil::serde_byte_size")]
+//!     max_size: u64,
+//! }
+//!
+//! let c: MyConfig = serde_yaml::from_str("MaxSize: 64MiB").unwrap();
+//! assert_eq!(c.max_size, 64 * 1024 * 1024);
+//! let c: MyConfig = serde_yaml::from_str("MaxSize: 1024").unwrap();
+//! assert_eq!(c.max_size, 1024);
+//! assert!(serde_yaml::from_str::<MyConfig>("MaxSize: huge").is_err());
+//! ```
+
+use serde::{Deserialize, Deserializer, Serializer};
+
+/// 整数或字符串形式的字节大小
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawByteSize {
+    Bytes(u64),
+    Text(String),
+}
+
+/// 将整数或字节大小字符串反序列化为字节数
+pub fn deserialize<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match RawByteSize::deserialize(deserializer)? {
+        RawByteSize::Bytes(n) => Ok(n),
+        RawByteSize::Text(s) => super::to_byte_size(&s).ok_or_else(|| {
+            serde::de::Error::custom(format!(
+                "invalid byte size [{s}], expect format like 512/64MiB/1GB"
+            ))
+        }),
+    }
+}
+
+/// 将字节数序列化为整数
+pub fn serialize<S>(n: &u64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_u64(*n)
+}
@@ -0,0 +1,93 @@
+//! `Duration` 字段的 serde 适配
+//!
+//! 配置中的时长以人类可读字符串书写（如 `"500ms"`、`"1h30m"`），
+//! 通过 `#[serde(with = "...")]` 直接反序列化为 `Duration`，
+//! 无法解析时返回反序列化错误，而不是静默回退默认值。
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! #[derive(serde::Deserialize)]
+//! struct MyConfig {
+//!     #[serde(rename = "Timeout", with = "x_one::xutil::serde_duration")]
+//!     timeout: Duration,
+//! }
+//!
+//! let c: MyConfig = serde_yaml::from_str("Timeout: 1m30s").unwrap();
+//! assert_eq!(c.timeout, Duration::from_secs(90));
+//! assert!(serde_yaml::from_str::<MyConfig>("Timeout: soon").is_err());
+//! ```
+
+use serde::{Deserialize, Deserializer, Serializer};
+use std::time::Duration;
+
+/// 将 duration 字符串反序列化为 `Duration`
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    super::to_duration(&s).ok_or_else(|| {
+        serde::de::Error::custom(format!(
+            "invalid duration [{s}], expect format like 500ms/30s/1h30m"
+        ))
+    })
+}
+
+/// 将 `Duration` 序列化为 duration 字符串
+pub fn serialize<S>(d: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&humantime::format_duration(*d).to_string())
+}
+
+/// `Option<Duration>` 字段的 serde 适配，空字符串反序列化为 `None`
+///
+/// ```
+/// use std::time::Duration;
+///
+/// #[derive(serde::Deserialize)]
+/// struct MyConfig {
+///     #[serde(rename = "Timeout", with = "x_one::xutil::serde_duration::option")]
+///     timeout: Option<Duration>,
+/// }
+///
+/// let c: MyConfig = serde_yaml::from_str("Timeout: 30s").unwrap();
+/// assert_eq!(c.timeout, Some(Duration::from_secs(30)));
+/// let c: MyConfig = serde_yaml::from_str("Timeout: ''").unwrap();
+/// assert_eq!(c.timeout, None);
+/// ```
+pub mod option {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    /// 将 duration 字符串反序列化为 `Option<Duration>`，空字符串或 null 为 `None`
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let Some(s) = Option::<String>::deserialize(deserializer)? else {
+            return Ok(None);
+        };
+        if s.trim().is_empty() {
+            return Ok(None);
+        }
+        crate::xutil::to_duration(&s).map(Some).ok_or_else(|| {
+            serde::de::Error::custom(format!(
+                "invalid duration [{s}], expect format like 500ms/30s/1h30m"
+            ))
+        })
+    }
+
+    /// 将 `Option<Duration>` 序列化为 duration 字符串，`None` 序列化为空字符串
+    pub fn serialize<S>(d: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match d {
+            Some(d) => super::serialize(d, serializer),
+            None => serializer.serialize_str(""),
+        }
+    }
+}
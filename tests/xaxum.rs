@@ -18,3 +18,9 @@ mod session;
 
 #[path = "xaxum/trace.rs"]
 mod trace;
+
+#[path = "xaxum/admin.rs"]
+mod admin;
+
+#[path = "xaxum/request_debug.rs"]
+mod request_debug;
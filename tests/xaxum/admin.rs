@@ -0,0 +1,137 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use serial_test::serial;
+use std::time::Duration;
+use tower::ServiceExt;
+use tracing_subscriber::{EnvFilter, Registry, reload};
+use x_one::xaxum::admin::{LOG_LEVEL_ROUTE_PATH, log_level_router};
+use x_one::xlog::config::RequestDebugConfig;
+use x_one::xlog::request_debug::{RequestDebugPolicy, set_policy, sign_token};
+
+const SECRET: &str = "admin-secret";
+
+/// 安装测试用过滤器句柄（Layer 被 drop 后句柄失效，需由调用方持有）
+fn install(level: &str) -> reload::Layer<EnvFilter, Registry> {
+    let (layer, handle) = reload::Layer::new(EnvFilter::new(level));
+    x_one::xlog::level::install(handle, level);
+    layer
+}
+
+/// 启用 XLog.RequestDebug 校验策略
+fn enable_policy() {
+    let c = RequestDebugConfig {
+        enable: true,
+        secret: SECRET.to_string(),
+        ..Default::default()
+    };
+    set_policy(RequestDebugPolicy::from_config(&c).unwrap());
+}
+
+fn valid_token() -> String {
+    sign_token(SECRET, Duration::from_secs(60))
+}
+
+async fn send(method: &str, body: &str) -> (StatusCode, serde_json::Value) {
+    send_with_token(method, body, Some(&valid_token())).await
+}
+
+async fn send_with_token(
+    method: &str,
+    body: &str,
+    token: Option<&str>,
+) -> (StatusCode, serde_json::Value) {
+    let mut request = Request::builder()
+        .method(method)
+        .uri(LOG_LEVEL_ROUTE_PATH)
+        .header("content-type", "application/json");
+    if let Some(token) = token {
+        request = request.header("authorization", format!("Bearer {token}"));
+    }
+    let response = log_level_router()
+        .oneshot(request.body(Body::from(body.to_string())).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_get_log_level() {
+    let _layer = install("info");
+
+    let (status, body) = send("GET", "").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["level"], "info");
+}
+
+#[tokio::test]
+#[serial]
+async fn test_put_log_level() {
+    let _layer = install("info");
+    enable_policy();
+
+    let (status, body) = send("PUT", r#"{"level":"info,sqlx=debug","ttl":"10m"}"#).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["level"], "info,sqlx=debug");
+    assert_eq!(x_one::xlog::get_level().as_deref(), Some("info,sqlx=debug"));
+    set_policy(None);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_put_log_level_requires_request_debug_policy() {
+    let _layer = install("info");
+    set_policy(None);
+
+    let (status, body) = send("PUT", r#"{"level":"debug"}"#).await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert!(body["error"].as_str().unwrap().contains("RequestDebug"));
+    assert_eq!(x_one::xlog::get_level().as_deref(), Some("info"));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_put_log_level_rejects_invalid_token() {
+    let _layer = install("info");
+    enable_policy();
+
+    let body = r#"{"level":"debug"}"#;
+    let wrong = sign_token("other-secret", Duration::from_secs(60));
+    for token in [None, Some("garbage"), Some(wrong.as_str())] {
+        let (status, _) = send_with_token("PUT", body, token).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED, "token: {token:?}");
+    }
+
+    assert_eq!(x_one::xlog::get_level().as_deref(), Some("info"));
+    set_policy(None);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_put_log_level_invalid_returns_bad_request() {
+    let _layer = install("info");
+    enable_policy();
+
+    let (status, body) = send("PUT", r#"{"level":"sqlx=verbose"}"#).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(
+        body["error"]
+            .as_str()
+            .unwrap()
+            .contains("invalid log level")
+    );
+
+    let (status, body) = send("PUT", r#"{"level":"debug","ttl":"soon"}"#).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("invalid ttl"));
+
+    assert_eq!(x_one::xlog::get_level().as_deref(), Some("info"));
+    set_policy(None);
+}
@@ -138,3 +138,17 @@ async fn test_log_middleware_text_body_recorded() {
     let body_str = String::from_utf8(body.to_vec()).unwrap();
     assert_eq!(body_str, request_body, "log 中间件应透传 body，不改变内容");
 }
+
+#[test]
+fn test_log_body_sensitive_fields_redacted() {
+    use axum::http::HeaderMap;
+    use x_one::xaxum::middleware::log::body_to_string;
+
+    let mut headers = HeaderMap::new();
+    headers.insert("content-type", "application/json".parse().unwrap());
+
+    let result = body_to_string(br#"{"user":"alice","password":"p@ss"}"#, &headers);
+
+    assert!(result.contains(r#""user":"alice""#), "{result}");
+    assert!(result.contains(r#""password":"***""#), "{result}");
+}
@@ -0,0 +1,106 @@
+use axum::body::Body;
+use axum::routing::get;
+use axum::{Router, http::StatusCode};
+use serial_test::serial;
+use std::time::Duration;
+use tower::ServiceExt;
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::prelude::*;
+use x_one::xaxum::middleware::request_debug_middleware;
+use x_one::xlog::config::RequestDebugConfig;
+use x_one::xlog::request_debug::{self, RequestDebugLayer, RequestDebugPolicy, sign_token};
+
+const SECRET: &str = "test-secret";
+
+fn set_policy(enable: bool) {
+    let c = RequestDebugConfig {
+        enable,
+        secret: SECRET.to_string(),
+        ..Default::default()
+    };
+    request_debug::set_policy(RequestDebugPolicy::from_config(&c).unwrap());
+}
+
+/// handler 返回当前请求是否开启 DEBUG，以及 xhttp 出站请求携带的请求头
+fn build_app() -> Router {
+    Router::new()
+        .route(
+            "/debug",
+            get(|| async {
+                let outbound = x_one::xhttp::with_request_debug(
+                    reqwest::Client::new().get("http://127.0.0.1/downstream"),
+                )
+                .build()
+                .unwrap();
+                let header = outbound
+                    .headers()
+                    .get("X-Debug-Log")
+                    .map(|v| v.to_str().unwrap().to_string())
+                    .unwrap_or_default();
+                format!(
+                    "active={},debug={},header={header}",
+                    request_debug::is_active(),
+                    tracing::enabled!(tracing::Level::DEBUG)
+                )
+            }),
+        )
+        .layer(axum::middleware::from_fn(request_debug_middleware))
+}
+
+async fn call(header: Option<&str>) -> String {
+    let mut builder = axum::http::Request::builder().uri("/debug");
+    if let Some(token) = header {
+        builder = builder.header("X-Debug-Log", token);
+    }
+    let response = build_app()
+        .oneshot(builder.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+fn subscriber() -> impl tracing::Subscriber + Send + Sync {
+    tracing_subscriber::registry().with(RequestDebugLayer::new(EnvFilter::new("info"), true))
+}
+
+#[tokio::test]
+#[serial]
+async fn test_valid_header_enables_debug_and_propagates() {
+    set_policy(true);
+    let _guard = tracing::subscriber::set_default(subscriber());
+
+    let token = sign_token(SECRET, Duration::from_secs(60));
+    assert_eq!(
+        call(Some(&token)).await,
+        format!("active=true,debug=true,header={token}")
+    );
+    assert_eq!(call(None).await, "active=false,debug=false,header=");
+
+    set_policy(false);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_invalid_header_ignored() {
+    set_policy(true);
+    let _guard = tracing::subscriber::set_default(subscriber());
+
+    let token = sign_token("wrong-secret", Duration::from_secs(60));
+    assert_eq!(call(Some(&token)).await, "active=false,debug=false,header=");
+
+    set_policy(false);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_disabled_policy_passes_through() {
+    set_policy(false);
+    let _guard = tracing::subscriber::set_default(subscriber());
+
+    let token = sign_token(SECRET, Duration::from_secs(60));
+    assert_eq!(call(Some(&token)).await, "active=false,debug=false,header=");
+}
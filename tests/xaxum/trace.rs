@@ -115,3 +115,242 @@ async fn test_trace_middleware_disabled_passthrough() {
         .unwrap();
     assert_eq!(body, "pong");
 }
+
+/// 以给定采样配置初始化 trace
+fn setup_trace_with_sampler(sampler_yaml: &str) {
+    let yaml = format!("XTrace:\n  Enable: true\n  Sampler:\n{sampler_yaml}");
+    x_one::xconfig::set_config(serde_yaml::from_str(&yaml).unwrap());
+    x_one::xtrace::init::init_xtrace().unwrap();
+}
+
+/// handler 返回 `{span 是否有效} {是否采样}`
+fn build_sampling_app() -> Router {
+    Router::new()
+        .fallback(|| async {
+            let cx = Context::current();
+            let sc = cx.span().span_context().clone();
+            format!("{} {}", sc.is_valid(), sc.is_sampled())
+        })
+        .layer(axum::middleware::from_fn::<_, (axum::extract::Request,)>(
+            x_one::xaxum::middleware::trace::trace_middleware,
+        ))
+}
+
+async fn request_span_state(method: &str, uri: &str, traceparent: Option<&str>) -> String {
+    let mut builder = axum::http::Request::builder().method(method).uri(uri);
+    if let Some(traceparent) = traceparent {
+        builder = builder.header("traceparent", traceparent);
+    }
+    let response = build_sampling_app()
+        .oneshot(builder.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), axum::http::StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[tokio::test]
+#[serial]
+async fn test_trace_middleware_sampling_rules() {
+    setup_trace_with_sampler(
+        r#"    Type: "always_off"
+    Rules:
+      - Path: "/healthz"
+        Ratio: 0
+      - Path: "/api/pay/*"
+        Method: "POST"
+        Ratio: 1
+"#,
+    );
+
+    // Ratio 0：不创建 span
+    assert_eq!(
+        request_span_state("GET", "/healthz", None).await,
+        "false false"
+    );
+    // 命中规则：按规则比例采样，不受 always_off 影响
+    assert_eq!(
+        request_span_state("POST", "/api/pay/order", None).await,
+        "true true"
+    );
+    // 方法不匹配与未命中规则：由 always_off 决定
+    assert_eq!(
+        request_span_state("GET", "/api/pay/order", None).await,
+        "true false"
+    );
+    assert_eq!(
+        request_span_state("GET", "/api/user", None).await,
+        "true false"
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn test_trace_middleware_sampling_rule_parent_based() {
+    setup_trace_with_sampler(
+        r#"    Type: "parentbased_always_on"
+    Rules:
+      - Path: "/healthz"
+        Ratio: 0
+      - Path: "/api/*"
+        Ratio: 1
+"#,
+    );
+    let not_sampled = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00";
+    let sampled = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    // parentbased 下命中规则仍沿用父级决定
+    assert_eq!(
+        request_span_state("GET", "/api/user", Some(not_sampled)).await,
+        "true false"
+    );
+    assert_eq!(
+        request_span_state("GET", "/api/user", Some(sampled)).await,
+        "true true"
+    );
+    // Ratio 0 的规则不受父级影响
+    assert_eq!(
+        request_span_state("GET", "/healthz", Some(sampled)).await,
+        "false false"
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn test_trace_middleware_sampler_type_without_rules() {
+    setup_trace_with_sampler("    Type: \"always_off\"\n");
+    assert_eq!(
+        request_span_state("GET", "/api/user", None).await,
+        "true false"
+    );
+
+    setup_trace_with_sampler("    Type: \"always_on\"\n");
+    assert_eq!(
+        request_span_state("GET", "/api/user", None).await,
+        "true true"
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn test_trace_middleware_propagators_interop() {
+    x_one::xconfig::set_config(
+        serde_yaml::from_str(
+            r#"
+XTrace:
+  Enable: true
+  Propagators: ["tracecontext", "baggage", "b3multi", "jaeger"]
+"#,
+        )
+        .unwrap(),
+    );
+    x_one::xtrace::init::init_xtrace().unwrap();
+
+    // handler 内出站请求注入的头
+    let app = Router::new()
+        .route(
+            "/outbound",
+            get(|| async {
+                let outbound = x_one::xhttp::with_trace_context(
+                    reqwest::Client::new().get("http://127.0.0.1/downstream"),
+                )
+                .build()
+                .unwrap();
+                let header = |name: &str| {
+                    outbound
+                        .headers()
+                        .get(name)
+                        .map(|v| v.to_str().unwrap().to_string())
+                        .unwrap_or_default()
+                };
+                format!(
+                    "{}|{}|{}|{}",
+                    header("traceparent"),
+                    header("x-b3-traceid"),
+                    header("uber-trace-id"),
+                    header("baggage")
+                )
+            }),
+        )
+        .layer(axum::middleware::from_fn::<_, (axum::extract::Request,)>(
+            x_one::xaxum::middleware::trace::trace_middleware,
+        ));
+
+    let trace_id = "4bf92f3577b34da6a3ce929d0e0e4736";
+    let response = app
+        .oneshot(
+            axum::http::Request::builder()
+                .uri("/outbound")
+                .header("uber-trace-id", format!("{trace_id}:00f067aa0ba902b7:0:1"))
+                .header("baggage", "tenant=acme")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    let parts: Vec<&str> = body.split('|').collect();
+
+    // 入站 Jaeger 上下文延续到所有出站格式
+    assert!(
+        parts[0].starts_with(&format!("00-{trace_id}-")) && parts[0].ends_with("-01"),
+        "{body}"
+    );
+    assert_eq!(parts[1], trace_id, "{body}");
+    assert!(parts[2].starts_with(&format!("{trace_id}:")), "{body}");
+    assert_eq!(parts[3], "tenant=acme", "{body}");
+    // 出站 span id 为中间件创建的 server span，而非入站 span
+    assert!(!parts[0].contains("00f067aa0ba902b7"), "{body}");
+}
+
+#[tokio::test]
+#[serial]
+async fn test_trace_middleware_b3_deferred_sampling_decided_locally() {
+    x_one::xconfig::set_config(
+        serde_yaml::from_str("XTrace:\n  Enable: true\n  Propagators: [\"b3multi\"]\n").unwrap(),
+    );
+    x_one::xtrace::init::init_xtrace().unwrap();
+
+    let app = Router::new()
+        .route(
+            "/sampled",
+            get(|| async {
+                let cx = Context::current();
+                let sc = cx.span().span_context().clone();
+                format!("{}|{}", sc.trace_id(), sc.is_sampled())
+            }),
+        )
+        .layer(axum::middleware::from_fn::<_, (axum::extract::Request,)>(
+            x_one::xaxum::middleware::trace::trace_middleware,
+        ));
+
+    let trace_id = "4bf92f3577b34da6a3ce929d0e0e4736";
+    let call = |sampled: Option<&'static str>| {
+        let app = app.clone();
+        async move {
+            let mut req = axum::http::Request::builder()
+                .uri("/sampled")
+                .header("x-b3-traceid", trace_id)
+                .header("x-b3-spanid", "00f067aa0ba902b7");
+            if let Some(sampled) = sampled {
+                req = req.header("x-b3-sampled", sampled);
+            }
+            let response = app.oneshot(req.body(Body::empty()).unwrap()).await.unwrap();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            String::from_utf8(body.to_vec()).unwrap()
+        }
+    };
+
+    // 未携带采样状态：由本地采样器（默认 parentbased_always_on 的根采样器）决定，沿用远端 trace_id
+    assert_eq!(call(None).await, format!("{trace_id}|true"));
+    // 远端明确不采样：沿用父级决定
+    assert_eq!(call(Some("0")).await, format!("{trace_id}|false"));
+}
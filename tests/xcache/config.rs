@@ -1,10 +1,11 @@
+use std::time::Duration;
 use x_one::xcache::config::*;
 
 #[test]
 fn test_default_config() {
     let config = XCacheConfig::default();
     assert_eq!(config.max_capacity, 100_000);
-    assert_eq!(config.default_ttl, "5m");
+    assert_eq!(config.default_ttl, Duration::from_secs(300));
     assert!(config.name.is_empty());
 }
 
@@ -17,7 +18,7 @@ Name: "primary"
 "#;
     let config: XCacheConfig = serde_yaml::from_str(yaml).unwrap();
     assert_eq!(config.max_capacity, 200_000);
-    assert_eq!(config.default_ttl, "10m");
+    assert_eq!(config.default_ttl, Duration::from_secs(600));
     assert_eq!(config.name, "primary");
 }
 
@@ -26,5 +27,5 @@ fn test_deserialize_minimal_yaml() {
     let yaml = "{}";
     let config: XCacheConfig = serde_yaml::from_str(yaml).unwrap();
     assert_eq!(config.max_capacity, 100_000);
-    assert_eq!(config.default_ttl, "5m");
+    assert_eq!(config.default_ttl, Duration::from_secs(300));
 }
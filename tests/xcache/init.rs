@@ -1,4 +1,5 @@
 use serial_test::serial;
+use std::time::Duration;
 use x_one::xcache::init::*;
 use x_one::xcache::*;
 
@@ -60,7 +61,7 @@ fn test_create_cache_instance() {
 fn test_config_default() {
     let config = XCacheConfig::default();
     assert_eq!(config.max_capacity, 100_000);
-    assert_eq!(config.default_ttl, "5m");
+    assert_eq!(config.default_ttl, Duration::from_secs(300));
     assert!(config.name.is_empty());
 }
 
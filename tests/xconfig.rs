@@ -1,9 +1,21 @@
+#[path = "xconfig/arg_override.rs"]
+mod arg_override;
+
 #[path = "xconfig/config.rs"]
 mod config;
 
 #[path = "xconfig/env_expand.rs"]
 mod env_expand;
 
+#[path = "xconfig/env_override.rs"]
+mod env_override;
+
+#[path = "xconfig/format.rs"]
+mod format;
+
+#[path = "xconfig/import.rs"]
+mod import;
+
 #[path = "xconfig/init.rs"]
 mod init;
 
@@ -15,3 +27,15 @@ mod main;
 
 #[path = "xconfig/profiles.rs"]
 mod profiles;
+
+#[path = "xconfig/watcher.rs"]
+mod watcher;
+
+#[path = "xconfig/source.rs"]
+mod source;
+
+#[path = "xconfig/schema.rs"]
+mod schema;
+
+#[path = "xconfig/secret.rs"]
+mod secret;
@@ -0,0 +1,72 @@
+use x_one::xconfig::arg_override::*;
+
+fn args(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_arg_override_dotted_path_with_coercion() {
+    let mut c: serde_yaml::Value =
+        serde_yaml::from_str("XAxum:\n  Port: 8000\nXLog:\n  Level: info\n").unwrap();
+
+    let applied = apply_arg_overrides_from(
+        &mut c,
+        args(&[("XAxum.Port", "9000"), ("XLog.Level", "debug")]),
+    );
+
+    assert_eq!(c["XAxum"]["Port"].as_i64(), Some(9000));
+    assert_eq!(c["XLog"]["Level"].as_str(), Some("debug"));
+    assert_eq!(applied.len(), 2);
+    assert_eq!(applied[0].key, "XAxum.Port");
+}
+
+#[test]
+fn test_arg_override_missing_key_parses_scalar() {
+    let mut c: serde_yaml::Value = serde_yaml::from_str("XAxum:\n  Host: 0.0.0.0\n").unwrap();
+
+    let applied = apply_arg_overrides_from(
+        &mut c,
+        args(&[("XAxum.Port", "9000"), ("XAxum.usehttp2", "true")]),
+    );
+
+    assert_eq!(c["XAxum"]["Port"].as_i64(), Some(9000));
+    assert_eq!(c["XAxum"]["UseHttp2"].as_bool(), Some(true));
+    assert_eq!(applied[1].key, "XAxum.UseHttp2");
+    let axum: x_one::xaxum::AxumConfig = serde_yaml::from_value(c["XAxum"].clone()).unwrap();
+    assert_eq!(axum.port, 9000);
+    assert!(axum.use_http2);
+}
+
+#[test]
+fn test_arg_override_list_index_and_later_wins() {
+    let mut c: serde_yaml::Value =
+        serde_yaml::from_str("XRedis:\n  - Addr: a:6379\n  - Addr: b:6379\n").unwrap();
+
+    apply_arg_overrides_from(
+        &mut c,
+        args(&[("XRedis.1.Addr", "x:6379"), ("XRedis.1.Addr", "y:6379")]),
+    );
+
+    assert_eq!(c["XRedis"][0]["Addr"].as_str(), Some("a:6379"));
+    assert_eq!(c["XRedis"][1]["Addr"].as_str(), Some("y:6379"));
+}
+
+#[test]
+fn test_arg_override_invalid_ignored() {
+    let mut c: serde_yaml::Value = serde_yaml::from_str("XAxum:\n  Port: 8000\n").unwrap();
+
+    let applied = apply_arg_overrides_from(
+        &mut c,
+        args(&[
+            ("XAxum.Port", "abc"),
+            ("XAxum..Host", "x"),
+            ("XAxum.Port.Sub", "1"),
+        ]),
+    );
+
+    assert!(applied.is_empty());
+    assert_eq!(c["XAxum"]["Port"].as_i64(), Some(8000));
+}
@@ -71,7 +71,7 @@ fn test_expand_env_placeholders_in_value() {
 
     let map = value.as_mapping().unwrap();
     let v = map
-        .get(&serde_yaml::Value::String("key".to_string()))
+        .get(serde_yaml::Value::String("key".to_string()))
         .unwrap();
     assert_eq!(v.as_str().unwrap(), "expanded");
     remove_env("TEST_EXPAND_VAL");
@@ -0,0 +1,186 @@
+use serial_test::serial;
+use std::time::Duration;
+use x_one::xconfig::env_override::*;
+
+fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+fn config(yaml: &str) -> serde_yaml::Value {
+    serde_yaml::from_str(yaml).unwrap()
+}
+
+#[test]
+fn test_override_matches_key_ignore_case() {
+    let mut c = config("XOrm:\n  DSN: base\n");
+
+    let applied = apply_env_overrides_from(&mut c, vars(&[("XONE_XORM__DSN", "from-env")]));
+
+    assert_eq!(c["XOrm"]["DSN"].as_str(), Some("from-env"));
+    assert_eq!(
+        applied,
+        vec![EnvOverride {
+            env_key: "XONE_XORM__DSN".to_string(),
+            key: "XOrm.DSN".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_override_coerces_to_existing_type() {
+    let mut c =
+        config("XAxum:\n  Port: 8000\n  UseHttp2: false\n  Ratio: 0.5\n  Host: \"0.0.0.0\"\n");
+
+    apply_env_overrides_from(
+        &mut c,
+        vars(&[
+            ("XONE_XAXUM__PORT", "9000"),
+            ("XONE_XAXUM__USEHTTP2", "TRUE"),
+            ("XONE_XAXUM__RATIO", "0.8"),
+            ("XONE_XAXUM__HOST", "127.0.0.1"),
+        ]),
+    );
+
+    assert_eq!(c["XAxum"]["Port"].as_i64(), Some(9000));
+    assert_eq!(c["XAxum"]["UseHttp2"].as_bool(), Some(true));
+    assert_eq!(c["XAxum"]["Ratio"].as_f64(), Some(0.8));
+    assert_eq!(c["XAxum"]["Host"].as_str(), Some("127.0.0.1"));
+}
+
+#[test]
+fn test_override_invalid_type_keeps_original() {
+    let mut c = config("XAxum:\n  Port: 8000\n");
+
+    let applied = apply_env_overrides_from(&mut c, vars(&[("XONE_XAXUM__PORT", "abc")]));
+
+    assert!(applied.is_empty());
+    assert_eq!(c["XAxum"]["Port"].as_i64(), Some(8000));
+}
+
+#[test]
+fn test_override_list_index() {
+    let mut c = config("XRedis:\n  - Name: a\n    Addr: a:6379\n  - Name: b\n    Addr: b:6379\n");
+
+    let applied = apply_env_overrides_from(
+        &mut c,
+        vars(&[
+            ("XONE_XREDIS__1__ADDR", "new:6379"),
+            ("XONE_XREDIS__5__ADDR", "x"),
+        ]),
+    );
+
+    assert_eq!(c["XRedis"][0]["Addr"].as_str(), Some("a:6379"));
+    assert_eq!(c["XRedis"][1]["Addr"].as_str(), Some("new:6379"));
+    assert_eq!(applied.len(), 1);
+    assert_eq!(applied[0].key, "XRedis.1.Addr");
+}
+
+#[test]
+fn test_override_creates_missing_node() {
+    let mut c = config("XLog:\n  Level: info\n");
+
+    apply_env_overrides_from(&mut c, vars(&[("XONE_XLog__Path", "./logs")]));
+
+    assert_eq!(c["XLog"]["Path"].as_str(), Some("./logs"));
+    assert_eq!(c["XLog"]["Level"].as_str(), Some("info"));
+}
+
+#[test]
+fn test_override_missing_key_uses_schema_name_and_scalar_type() {
+    let mut c = config("XAxum:\n  Host: 0.0.0.0\n");
+
+    let applied = apply_env_overrides_from(
+        &mut c,
+        vars(&[
+            ("XONE_XAXUM__PORT", "9000"),
+            ("XONE_XAXUM__USEHTTP2", "true"),
+        ]),
+    );
+
+    assert_eq!(c["XAxum"]["Port"].as_i64(), Some(9000));
+    assert_eq!(c["XAxum"]["UseHttp2"].as_bool(), Some(true));
+    assert!(c["XAxum"].get("PORT").is_none());
+    let keys = applied.iter().map(|o| o.key.as_str()).collect::<Vec<_>>();
+    assert_eq!(keys, vec!["XAxum.Port", "XAxum.UseHttp2"]);
+
+    let axum: x_one::xaxum::AxumConfig = serde_yaml::from_value(c["XAxum"].clone()).unwrap();
+    assert_eq!(axum.port, 9000);
+    assert!(axum.use_http2);
+}
+
+#[test]
+fn test_override_missing_module_creates_declared_path() {
+    let mut c = config("Server:\n  Name: demo\n");
+
+    apply_env_overrides_from(&mut c, vars(&[("XONE_XREDIS__DB", "3")]));
+
+    assert_eq!(c["XRedis"]["DB"].as_i64(), Some(3));
+}
+
+#[test]
+fn test_override_missing_string_key_stays_string() {
+    let mut c = config("XRedis:\n  Addr: localhost:6379\n");
+
+    apply_env_overrides_from(&mut c, vars(&[("XONE_XREDIS__PASSWORD", "123456")]));
+
+    assert_eq!(c["XRedis"]["Password"].as_str(), Some("123456"));
+}
+
+#[test]
+fn test_override_missing_undeclared_key_parses_scalar() {
+    let mut c = config("MyModule:\n  Name: demo\n");
+
+    apply_env_overrides_from(
+        &mut c,
+        vars(&[
+            ("XONE_MyModule__Retry", "3"),
+            ("XONE_MyModule__Ratio", "0.5"),
+            ("XONE_MyModule__Tag", "blue"),
+        ]),
+    );
+
+    assert_eq!(c["MyModule"]["Retry"].as_i64(), Some(3));
+    assert_eq!(c["MyModule"]["Ratio"].as_f64(), Some(0.5));
+    assert_eq!(c["MyModule"]["Tag"].as_str(), Some("blue"));
+}
+
+#[test]
+fn test_override_ignores_unrelated_vars() {
+    let mut c = config("Name: base\n");
+    let original = c.clone();
+
+    let applied = apply_env_overrides_from(
+        &mut c,
+        vars(&[
+            ("XONE_ENABLE_DEBUG", "true"),
+            ("XONE_UNKNOWN", "x"),
+            ("XONE_A____B", "x"),
+            ("OTHER__NAME", "x"),
+        ]),
+    );
+
+    assert!(applied.is_empty());
+    assert_eq!(c, original);
+}
+
+#[test]
+#[serial]
+fn test_override_applied_on_reload() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("application.yml");
+    std::fs::write(&path, "XAxum:\n  Port: 8000\n").unwrap();
+    let location = path.to_str().unwrap().to_string();
+    x_one::xconfig::set_config(config("{}"));
+    unsafe { std::env::set_var("XONE_XAXUM__PORT", "9000") };
+    x_one::xconfig::start_watch(&location, Duration::from_secs(3600)).unwrap();
+
+    x_one::xconfig::reload().unwrap();
+
+    assert_eq!(x_one::xconfig::get_int("XAxum.Port"), 9000);
+    unsafe { std::env::remove_var("XONE_XAXUM__PORT") };
+    x_one::xconfig::stop_watch().unwrap();
+    x_one::xconfig::reset_config();
+}
@@ -0,0 +1,61 @@
+use x_one::xconfig::format::*;
+use x_one::xconfig::init::load_local_config;
+
+#[test]
+fn test_config_format_from_path() {
+    assert_eq!(
+        ConfigFormat::from_path("a/application.yml"),
+        ConfigFormat::Yaml
+    );
+    assert_eq!(
+        ConfigFormat::from_path("application.YAML"),
+        ConfigFormat::Yaml
+    );
+    assert_eq!(
+        ConfigFormat::from_path("application.toml"),
+        ConfigFormat::Toml
+    );
+    assert_eq!(
+        ConfigFormat::from_path("application.json"),
+        ConfigFormat::Json
+    );
+    assert_eq!(ConfigFormat::from_path("application"), ConfigFormat::Yaml);
+    assert!(ConfigFormat::is_config_file("db.toml"));
+    assert!(!ConfigFormat::is_config_file("XOrm.DSN"));
+}
+
+#[test]
+fn test_load_local_config_toml_and_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let toml_path = dir.path().join("application.toml");
+    std::fs::write(
+        &toml_path,
+        "[Server]\nName = \"toml-app\"\n\n[XAxum]\nPort = 9000\n\n[[XRedis]]\nAddr = \"a:6379\"\n",
+    )
+    .unwrap();
+    let json_path = dir.path().join("application.json");
+    std::fs::write(
+        &json_path,
+        r#"{"Server": {"Name": "json-app"}, "XLog": {"Console": true}}"#,
+    )
+    .unwrap();
+
+    let c = load_local_config(toml_path.to_str().unwrap()).unwrap();
+    assert_eq!(c["Server"]["Name"].as_str(), Some("toml-app"));
+    assert_eq!(c["XAxum"]["Port"].as_i64(), Some(9000));
+    assert_eq!(c["XRedis"][0]["Addr"].as_str(), Some("a:6379"));
+
+    let c = load_local_config(json_path.to_str().unwrap()).unwrap();
+    assert_eq!(c["Server"]["Name"].as_str(), Some("json-app"));
+    assert_eq!(c["XLog"]["Console"].as_bool(), Some(true));
+}
+
+#[test]
+fn test_load_local_config_invalid_toml_returns_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("bad.toml");
+    std::fs::write(&path, "[Server\nName = ").unwrap();
+
+    let err = load_local_config(path.to_str().unwrap()).unwrap_err();
+    assert!(err.to_string().contains("parse config file failed"));
+}
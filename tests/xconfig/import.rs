@@ -0,0 +1,120 @@
+use serial_test::serial;
+use x_one::xconfig::import::*;
+
+fn write(dir: &std::path::Path, name: &str, content: &str) -> String {
+    let path = dir.join(name);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, content).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+#[test]
+fn test_imports_merged_in_order_main_file_wins() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "conf/db.yml",
+        "XOrm:\n  DSN: db\n  MaxOpenConns: 10\nXLog:\n  Level: warn\n",
+    );
+    write(
+        dir.path(),
+        "conf/redis.toml",
+        "[XOrm]\nMaxOpenConns = 20\n\n[XRedis]\nAddr = \"r:6379\"\n",
+    );
+    let main = write(
+        dir.path(),
+        "application.yml",
+        "Server:\n  Name: app\n  Imports: [\"conf/db.yml\", \"conf/redis.toml\"]\nXLog:\n  Level: info\n",
+    );
+
+    let (c, files) = load_config_with_imports(&main).unwrap();
+
+    assert_eq!(c["XOrm"]["DSN"].as_str(), Some("db"));
+    assert_eq!(c["XOrm"]["MaxOpenConns"].as_i64(), Some(20));
+    assert_eq!(c["XRedis"]["Addr"].as_str(), Some("r:6379"));
+    assert_eq!(c["XLog"]["Level"].as_str(), Some("info"));
+    assert_eq!(files.len(), 2);
+    assert!(files[0].ends_with("db.yml"));
+}
+
+#[test]
+fn test_nested_imports_relative_to_importing_file() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "a/b/leaf.json",
+        r#"{"XCache": {"Name": "leaf"}}"#,
+    );
+    write(
+        dir.path(),
+        "a/mid.yml",
+        "Server:\n  Imports: [\"b/leaf.json\"]\nXHttp:\n  Timeout: 5s\n",
+    );
+    let main = write(
+        dir.path(),
+        "application.yml",
+        "Server:\n  Name: app\n  Imports: [\"a/mid.yml\"]\n",
+    );
+
+    let (c, files) = load_config_with_imports(&main).unwrap();
+
+    assert_eq!(c["XCache"]["Name"].as_str(), Some("leaf"));
+    assert_eq!(c["XHttp"]["Timeout"].as_str(), Some("5s"));
+    assert_eq!(c["Server"]["Name"].as_str(), Some("app"));
+    assert_eq!(files.len(), 2);
+}
+
+#[test]
+fn test_import_cycle_detected() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "b.yml",
+        "Server:\n  Imports: [\"application.yml\"]\n",
+    );
+    let main = write(
+        dir.path(),
+        "application.yml",
+        "Server:\n  Imports: [\"b.yml\"]\n",
+    );
+
+    let err = load_config_with_imports(&main).unwrap_err();
+    assert!(err.to_string().contains("config import cycle detected"));
+}
+
+#[test]
+fn test_import_missing_file_returns_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let main = write(
+        dir.path(),
+        "application.yml",
+        "Server:\n  Imports: [\"missing.yml\"]\n",
+    );
+
+    let err = load_config_with_imports(&main).unwrap_err();
+    assert!(err.to_string().contains("config import file not found"));
+}
+
+#[test]
+#[serial]
+fn test_init_toml_profile_with_imports() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "dev-db.toml", "[XOrm]\nDSN = \"dev-db\"\n");
+    write(
+        dir.path(),
+        "application-dev.toml",
+        "[Server]\nImports = [\"dev-db.toml\"]\n\n[XLog]\nLevel = \"debug\"\n",
+    );
+    let main = write(
+        dir.path(),
+        "application.toml",
+        "[Server]\nName = \"app\"\n\n[Server.Profiles]\nActive = \"dev\"\n\n[XLog]\nLevel = \"info\"\n",
+    );
+    unsafe { std::env::set_var("SERVER_CONFIG_LOCATION", &main) };
+
+    let c = x_one::xconfig::init::init_xconfig().unwrap().unwrap();
+
+    unsafe { std::env::remove_var("SERVER_CONFIG_LOCATION") };
+    assert_eq!(c["XLog"]["Level"].as_str(), Some("debug"));
+    assert_eq!(c["XOrm"]["DSN"].as_str(), Some("dev-db"));
+}
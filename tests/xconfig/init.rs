@@ -144,3 +144,29 @@ fn test_init_xconfig_no_config_file() {
     assert!(result.is_ok());
     assert!(result.unwrap().is_none());
 }
+
+#[test]
+fn test_deep_merge_config_nested_and_override() {
+    let base: serde_yaml::Value =
+        serde_yaml::from_str("XOrm:\n  DSN: base\n  MaxOpen: 10\nList: [1, 2]\n").unwrap();
+    let overlay: serde_yaml::Value =
+        serde_yaml::from_str("XOrm:\n  DSN: overlay\nList: [3]\nNew: x\n").unwrap();
+
+    let merged = deep_merge_config(base, overlay);
+
+    assert_eq!(merged["XOrm"]["DSN"].as_str(), Some("overlay"));
+    assert_eq!(merged["XOrm"]["MaxOpen"].as_i64(), Some(10));
+    assert_eq!(merged["List"].as_sequence().unwrap().len(), 1);
+    assert_eq!(merged["New"].as_str(), Some("x"));
+}
+
+#[test]
+fn test_deep_merge_config_ignore_ascii_case_keeps_base_key() {
+    let base: serde_yaml::Value = serde_yaml::from_str("XOrm:\n  DSN: base\n").unwrap();
+    let overlay: serde_yaml::Value = serde_yaml::from_str("XORM:\n  dsn: overlay\n").unwrap();
+
+    let merged = deep_merge_config(base, overlay);
+
+    assert_eq!(merged["XOrm"]["DSN"].as_str(), Some("overlay"));
+    assert!(merged.get("XORM").is_none());
+}
@@ -10,6 +10,7 @@ fn test_get_string_no_config() {
 
 #[test]
 #[serial]
+#[allow(clippy::approx_constant)]
 fn test_get_float64_existing_key_returns_value() {
     reset_config();
     let yaml = "metrics:\n  threshold: 3.14\n";
@@ -322,3 +323,71 @@ fn test_get_string_slice_missing_key_returns_empty() {
     reset_config();
     assert!(get_string_slice("nonexistent").is_empty());
 }
+
+#[test]
+#[serial]
+fn test_get_duration() {
+    reset_config();
+    set_config(serde_yaml::from_str("A:\n  Timeout: 1m30s\n  Bad: soon\n  Num: 5\n").unwrap());
+    assert_eq!(
+        get_duration("A.Timeout"),
+        Some(std::time::Duration::from_secs(90))
+    );
+    assert_eq!(get_duration("A.Bad"), None);
+    assert_eq!(get_duration("A.Num"), None);
+    assert_eq!(get_duration("A.Missing"), None);
+    reset_config();
+}
+
+#[test]
+#[serial]
+fn test_get_byte_size() {
+    reset_config();
+    set_config(serde_yaml::from_str("A:\n  Buf: 64MiB\n  Raw: 512\n  Bad: 1XB\n").unwrap());
+    assert_eq!(get_byte_size("A.Buf"), Some(64 * 1024 * 1024));
+    assert_eq!(get_byte_size("A.Raw"), Some(512));
+    assert_eq!(get_byte_size("A.Bad"), None);
+    assert_eq!(get_byte_size("A.Missing"), None);
+    reset_config();
+}
+
+#[test]
+#[serial]
+fn test_get_map() {
+    reset_config();
+    set_config(serde_yaml::from_str("Labels:\n  env: prod\n  shard: 3\n  nested: [1]\n").unwrap());
+    let m = get_map("Labels");
+    assert_eq!(m.len(), 2);
+    assert_eq!(m["env"], "prod");
+    assert_eq!(m["shard"], "3");
+    assert!(get_map("Missing").is_empty());
+    reset_config();
+}
+
+#[test]
+#[serial]
+fn test_get_or() {
+    reset_config();
+    set_config(serde_yaml::from_str("Port: 9000\nName: abc\n").unwrap());
+    assert_eq!(get_or("Port", 8000_u16), 9000);
+    assert_eq!(get_or("Name", 8000_u16), 8000);
+    assert_eq!(get_or("Missing", 8000_u16), 8000);
+    reset_config();
+}
+
+#[test]
+#[serial]
+fn test_try_get() {
+    reset_config();
+    set_config(serde_yaml::from_str("XAxum:\n  Port: abc\n").unwrap());
+    assert_eq!(try_get::<u16>("Missing").unwrap(), None);
+    assert_eq!(
+        try_get::<String>("XAxum.Port").unwrap(),
+        Some("abc".to_string())
+    );
+
+    let err = try_get::<u16>("XAxum.Port").unwrap_err();
+    assert!(matches!(err, x_one::XOneError::Config(_)));
+    assert!(err.to_string().contains("parse config [XAxum.Port] failed"));
+    reset_config();
+}
@@ -69,5 +69,117 @@ fn test_detect_profiles_active_none() {
 ";
     let config: serde_yaml::Value = serde_yaml::from_str(yaml).unwrap();
     let result = detect_profiles_active(&config);
-    assert!(result.is_none());
+    assert!(result.is_empty());
+}
+
+fn groups(pairs: &[(&str, &[&str])]) -> std::collections::HashMap<String, Vec<String>> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.iter().map(|m| m.to_string()).collect()))
+        .collect()
+}
+
+fn list(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_parse_profiles_list_trims_and_dedups() {
+    assert_eq!(
+        parse_profiles_list(" prod, cn-east,,canary ,prod"),
+        list(&["prod", "cn-east", "canary"])
+    );
+    assert!(parse_profiles_list(" , ").is_empty());
+}
+
+#[test]
+fn test_expand_profile_groups_inserts_members_after_group() {
+    let g = groups(&[("prod", &["cn-east", "prod-db"]), ("prod-db", &["pg"])]);
+
+    let result = expand_profile_groups(&list(&["prod", "canary"]), &g);
+
+    assert_eq!(
+        result,
+        list(&["prod", "cn-east", "prod-db", "pg", "canary"])
+    );
+}
+
+#[test]
+fn test_expand_profile_groups_cycle_terminates() {
+    let g = groups(&[("a", &["b"]), ("b", &["a", "c"])]);
+
+    let result = expand_profile_groups(&list(&["a"]), &g);
+
+    assert_eq!(result, list(&["a", "b", "c"]));
+}
+
+#[test]
+#[serial]
+fn test_detect_profiles_active_list_with_groups_from_config() {
+    remove_env(PROFILES_ACTIVE_ENV_KEY);
+    let yaml = r#"
+Server:
+  Profiles:
+    Active: "prod, canary"
+    Groups:
+      prod: ["cn-east"]
+"#;
+    let config: serde_yaml::Value = serde_yaml::from_str(yaml).unwrap();
+
+    let result = detect_profiles_active(&config);
+
+    assert_eq!(result, list(&["prod", "cn-east", "canary"]));
+}
+
+#[test]
+#[serial]
+fn test_detect_profiles_active_env_list_uses_config_groups() {
+    set_env(PROFILES_ACTIVE_ENV_KEY, "staging,canary");
+    let yaml = r#"
+Server:
+  Profiles:
+    Active: dev
+    Groups:
+      canary: ["canary-db"]
+"#;
+    let config: serde_yaml::Value = serde_yaml::from_str(yaml).unwrap();
+
+    let result = detect_profiles_active(&config);
+
+    remove_env(PROFILES_ACTIVE_ENV_KEY);
+    assert_eq!(result, list(&["staging", "canary", "canary-db"]));
+}
+
+#[test]
+#[serial]
+fn test_init_merges_profiles_in_order() {
+    remove_env(PROFILES_ACTIVE_ENV_KEY);
+    let dir = tempfile::tempdir().unwrap();
+    let write = |name: &str, content: &str| {
+        let path = dir.path().join(name);
+        std::fs::write(&path, content).unwrap();
+        path.to_string_lossy().to_string()
+    };
+    let main = write(
+        "application.yml",
+        "Server:\n  Name: app\n  Profiles:\n    Active: \"prod,canary\"\n    Groups:\n      prod: [\"cn-east\"]\nXLog:\n  Level: info\nXAxum:\n  Port: 8080\n",
+    );
+    write(
+        "application-prod.yml",
+        "XLog:\n  Level: warn\nXOrm:\n  DSN: prod\n",
+    );
+    write("application-cn-east.yml", "XOrm:\n  DSN: cn-east\n");
+    write("application-canary.yml", "XLog:\n  Level: debug\n");
+    set_env("SERVER_CONFIG_LOCATION", &main);
+
+    let c = x_one::xconfig::init::init_xconfig().unwrap().unwrap();
+
+    remove_env("SERVER_CONFIG_LOCATION");
+    assert_eq!(c["XLog"]["Level"].as_str(), Some("debug"));
+    assert_eq!(c["XOrm"]["DSN"].as_str(), Some("cn-east"));
+    assert_eq!(c["XAxum"]["Port"].as_i64(), Some(8080));
+    assert_eq!(
+        x_one::xconfig::active_profiles(),
+        list(&["prod", "cn-east", "canary"])
+    );
 }
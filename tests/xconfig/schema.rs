@@ -0,0 +1,230 @@
+use serial_test::serial;
+use x_one::xconfig::schema::*;
+
+fn yaml(s: &str) -> serde_yaml::Value {
+    serde_yaml::from_str(s).unwrap()
+}
+
+fn paths(violations: &[SchemaViolation]) -> Vec<&str> {
+    violations.iter().map(|v| v.path.as_str()).collect()
+}
+
+#[test]
+fn test_validate_builtin_schema_valid_config() {
+    let c = yaml(
+        "Server:\n  Name: app\nXAxum:\n  Port: 8000\nXLog:\n  Level: info\nXOrm:\n  - DSN: x\n    MaxOpenConns: 10\nMyModule:\n  Any: 1\n",
+    );
+    let schema = serde_json::from_str(include_str!("../../config_schema.json")).unwrap();
+    assert!(validate_with_schema(&c, &schema).is_empty());
+}
+
+/// 序列化模块默认配置，去掉未设置（null）的可选字段
+fn default_node<T: serde::Serialize>(config: T) -> serde_yaml::Value {
+    fn strip_null(value: &mut serde_yaml::Value) {
+        if let serde_yaml::Value::Mapping(map) = value {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(strip_null);
+        }
+    }
+    let mut value = serde_yaml::to_value(config).unwrap();
+    strip_null(&mut value);
+    value
+}
+
+#[test]
+fn test_builtin_schema_accepts_module_default_configs() {
+    let mut server = default_node(x_one::xconfig::ServerConfig::default());
+    server["Name"] = "app".into();
+    let modules = [
+        ("Server", server),
+        ("XAxum", default_node(x_one::xaxum::AxumConfig::default())),
+        ("XLog", default_node(x_one::xlog::XLogConfig::default())),
+        (
+            "XTrace",
+            default_node(x_one::xtrace::XTraceConfig::default()),
+        ),
+        ("XHttp", default_node(x_one::xhttp::XHttpConfig::default())),
+        ("XOrm", default_node(x_one::xorm::XOrmConfig::default())),
+        (
+            "XRedis",
+            default_node(x_one::xredis::XRedisConfig::default()),
+        ),
+        (
+            "XMetric",
+            default_node(x_one::xmetric::XMetricConfig::default()),
+        ),
+        (
+            "XCache",
+            default_node(x_one::xcache::XCacheConfig::default()),
+        ),
+    ];
+    let schema = serde_json::from_str(include_str!("../../config_schema.json")).unwrap();
+
+    for (key, node) in modules {
+        let mut c = serde_yaml::Mapping::new();
+        c.insert(key.into(), node);
+        let violations = validate_with_schema(&serde_yaml::Value::Mapping(c), &schema);
+        assert!(violations.is_empty(), "{key}: {violations:?}");
+    }
+}
+
+#[test]
+fn test_validate_builtin_schema_reports_all_violations_with_path() {
+    let c = yaml(
+        "Server:\n  Version: v1\nXAxum:\n  Port: [1]\nXLog:\n  Level: verbose\nXOrm:\n  - DSN: x\n    MaxOpenConn: 10\n",
+    );
+    let schema = serde_json::from_str(include_str!("../../config_schema.json")).unwrap();
+    let violations = validate_with_schema(&c, &schema);
+
+    assert_eq!(
+        paths(&violations),
+        vec![
+            "Server.Name",
+            "XAxum.Port",
+            "XLog.Level",
+            "XOrm.0.MaxOpenConn"
+        ]
+    );
+    assert_eq!(violations[0].message, "missing required key");
+    assert!(
+        violations[1]
+            .message
+            .contains("expected type [integer|string]")
+    );
+    assert!(violations[2].message.contains("not in enum"));
+    assert_eq!(
+        violations[3].message,
+        "unknown key `MaxOpenConn` in XOrm[0], did you mean `MaxOpenConns`?"
+    );
+}
+
+#[test]
+fn test_validate_unresolved_ref_reported() {
+    let schema = serde_json::json!({
+        "properties": { "A": { "$ref": "#/definitions/Missing" } }
+    });
+    let violations = validate_with_schema(&yaml("A: 1\n"), &schema);
+    assert_eq!(paths(&violations), vec!["A"]);
+}
+
+#[test]
+fn test_validate_minimum_maximum() {
+    let schema = serde_json::json!({
+        "properties": {
+            "Ratio": { "type": "number", "minimum": 0, "maximum": 1 },
+            "Size": { "type": ["integer", "string"], "minimum": 1 }
+        }
+    });
+
+    assert!(validate_with_schema(&yaml("Ratio: 0.5\nSize: 1\n"), &schema).is_empty());
+    assert!(validate_with_schema(&yaml("Size: 10MB\n"), &schema).is_empty());
+
+    let violations = validate_with_schema(&yaml("Ratio: 1.5\nSize: 0\n"), &schema);
+    assert_eq!(paths(&violations), vec!["Ratio", "Size"]);
+    assert_eq!(
+        violations[0].message,
+        "value [1.5] is greater than maximum [1]"
+    );
+    assert_eq!(violations[1].message, "value [0] is less than minimum [1]");
+}
+
+#[test]
+fn test_builtin_schema_enforces_minimum_maximum() {
+    let c = yaml("XTrace:\n  Sampler:\n    Ratio: 2\n");
+    let schema = serde_json::from_str(include_str!("../../config_schema.json")).unwrap();
+    let violations = validate_with_schema(&c, &schema);
+    assert_eq!(paths(&violations), vec!["XTrace.Sampler.Ratio"]);
+}
+
+#[test]
+fn test_validate_unsupported_keyword_reported() {
+    let schema = serde_json::json!({
+        "properties": {
+            "Name": { "type": "string", "pattern": "^[a-z]+$", "description": "名称" }
+        }
+    });
+    let violations = validate_with_schema(&yaml("Name: Abc\n"), &schema);
+    assert_eq!(paths(&violations), vec!["Name"]);
+    assert_eq!(
+        violations[0].message,
+        "unsupported schema keyword `pattern`"
+    );
+}
+
+#[test]
+#[serial]
+fn test_register_schema_validates_user_module() {
+    reset_schemas();
+    register_schema(
+        "MyModule",
+        serde_json::json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": { "Enable": { "type": "boolean" } },
+            "required": ["Enable"]
+        }),
+    );
+
+    let violations = validate_config(&yaml("Server:\n  Name: app\nMyModule:\n  Enabel: true\n"));
+    assert_eq!(
+        paths(&violations),
+        vec!["MyModule.Enabel", "MyModule.Enable"]
+    );
+
+    reset_schemas();
+    assert!(validate_config(&yaml("Server:\n  Name: app\nMyModule:\n  Enabel: true\n")).is_empty());
+}
+
+#[test]
+fn test_validation_mode_parse() {
+    assert_eq!(
+        ValidationMode::parse("Strict"),
+        Some(ValidationMode::Strict)
+    );
+    assert_eq!(
+        ValidationMode::parse("lenient"),
+        Some(ValidationMode::Lenient)
+    );
+    assert_eq!(ValidationMode::parse("off"), Some(ValidationMode::Off));
+    assert_eq!(ValidationMode::parse("loose"), None);
+}
+
+#[test]
+#[serial]
+fn test_init_strict_mode_fails_on_violation() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("application.yml");
+    std::fs::write(
+        &path,
+        "Server:\n  Name: app\n  Validation:\n    Mode: strict\nXOrm:\n  MaxOpenConn: 10\n",
+    )
+    .unwrap();
+    unsafe { std::env::set_var("SERVER_CONFIG_LOCATION", path.to_str().unwrap()) };
+
+    let err = x_one::xconfig::init::init_xconfig().unwrap_err();
+    assert!(err.to_string().contains("XOrm.MaxOpenConn: unknown key"));
+
+    std::fs::write(&path, "Server:\n  Name: app\nXOrm:\n  MaxOpenConn: 10\n").unwrap();
+    assert!(x_one::xconfig::init::init_xconfig().unwrap().is_some());
+
+    unsafe { std::env::remove_var("SERVER_CONFIG_LOCATION") };
+}
+
+#[test]
+fn test_unknown_key_suggestion_ignores_case_and_skips_unrelated() {
+    let c = yaml(
+        "Server:\n  Name: app\nXRedis:\n  addr: x\n  Foo: 1\nXAxum:\n  Swagger:\n    Tittle: t\n",
+    );
+    let schema = serde_json::from_str(include_str!("../../config_schema.json")).unwrap();
+    let violations = validate_with_schema(&c, &schema);
+
+    let messages: Vec<&str> = violations.iter().map(|v| v.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        vec![
+            "unknown key `addr` in XRedis, did you mean `Addr`?",
+            "unknown key `Foo` in XRedis",
+            "unknown key `Tittle` in XAxum.Swagger, did you mean `Title`?",
+        ]
+    );
+}
@@ -0,0 +1,139 @@
+use serial_test::serial;
+use x_one::xconfig::secret::*;
+
+fn config(yaml: &str) -> serde_yaml::Value {
+    serde_yaml::from_str(yaml).unwrap()
+}
+
+fn with_secret_key<R>(key: &str, f: impl FnOnce() -> R) -> R {
+    unsafe { std::env::set_var(SECRET_KEY_ENV_KEY, key) };
+    let result = f();
+    unsafe { std::env::remove_var(SECRET_KEY_ENV_KEY) };
+    result
+}
+
+#[test]
+fn test_file_reference_replaced_with_trimmed_content() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("db_password");
+    std::fs::write(&file, "p@ss\n").unwrap();
+    let mut c = config(&format!(
+        "XOrm:\n  DSN: \"postgres://app:${{file:{}}}@db/app\"\n",
+        file.display()
+    ));
+
+    let paths = resolve_secrets(&mut c).unwrap();
+
+    assert_eq!(
+        c["XOrm"]["DSN"].as_str(),
+        Some("postgres://app:p@ss@db/app")
+    );
+    assert_eq!(paths, vec!["XOrm.DSN".to_string()]);
+}
+
+#[test]
+fn test_file_reference_in_sequence() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("token");
+    std::fs::write(&file, "abc").unwrap();
+    let mut c = config(&format!(
+        "XRedis:\n  - Password: \"${{file:{}}}\"\n",
+        file.display()
+    ));
+
+    let paths = resolve_secrets(&mut c).unwrap();
+
+    assert_eq!(c["XRedis"][0]["Password"].as_str(), Some("abc"));
+    assert_eq!(paths, vec!["XRedis.0.Password".to_string()]);
+}
+
+#[test]
+fn test_missing_secret_file_returns_error() {
+    let mut c = config("XOrm:\n  DSN: \"${file:/not/exist/secret}\"\n");
+
+    let err = resolve_secrets(&mut c).unwrap_err().to_string();
+
+    assert!(err.contains("XOrm.DSN"), "{err}");
+    assert!(err.contains("/not/exist/secret"), "{err}");
+}
+
+#[test]
+fn test_plain_values_untouched() {
+    let mut c = config("XLog:\n  Level: info\n  Path: \"${HOME}\"\nXAxum:\n  Port: 8080\n");
+    let before = c.clone();
+
+    let paths = resolve_secrets(&mut c).unwrap();
+
+    assert_eq!(c, before);
+    assert!(paths.is_empty());
+}
+
+#[test]
+#[serial]
+fn test_encrypted_value_round_trip() {
+    let key = generate_key().unwrap();
+    let encrypted = encrypt_value("redis-pass", &key).unwrap();
+    assert!(encrypted.starts_with("ENC(") && encrypted.ends_with(')'));
+    let mut c = config(&format!("XRedis:\n  Password: \"{encrypted}\"\n"));
+
+    let paths = with_secret_key(&key, || resolve_secrets(&mut c)).unwrap();
+
+    assert_eq!(c["XRedis"]["Password"].as_str(), Some("redis-pass"));
+    assert_eq!(paths, vec!["XRedis.Password".to_string()]);
+}
+
+#[test]
+#[serial]
+fn test_encrypted_value_without_key_returns_error() {
+    let key = generate_key().unwrap();
+    let encrypted = encrypt_value("redis-pass", &key).unwrap();
+    let mut c = config(&format!("XRedis:\n  Password: \"{encrypted}\"\n"));
+    unsafe { std::env::remove_var(SECRET_KEY_ENV_KEY) };
+
+    let err = resolve_secrets(&mut c).unwrap_err().to_string();
+
+    assert!(err.contains("XRedis.Password"), "{err}");
+    assert!(err.contains(SECRET_KEY_ENV_KEY), "{err}");
+}
+
+#[test]
+#[serial]
+fn test_encrypted_value_with_wrong_key_returns_error() {
+    let encrypted = encrypt_value("redis-pass", &generate_key().unwrap()).unwrap();
+    let mut c = config(&format!("XRedis:\n  Password: \"{encrypted}\"\n"));
+
+    let err = with_secret_key(&generate_key().unwrap(), || resolve_secrets(&mut c))
+        .unwrap_err()
+        .to_string();
+
+    assert!(err.contains("authentication failed"), "{err}");
+}
+
+#[test]
+fn test_encrypt_with_invalid_key_returns_error() {
+    assert!(encrypt_value("x", "not-base64!").is_err());
+    assert!(encrypt_value("x", "c2hvcnQ=").is_err());
+}
+
+#[test]
+#[serial]
+fn test_init_resolves_secrets_after_overrides() {
+    let dir = tempfile::tempdir().unwrap();
+    let secret_file = dir.path().join("db_password");
+    std::fs::write(&secret_file, "from-file\n").unwrap();
+    let main = dir.path().join("application.yml");
+    std::fs::write(
+        &main,
+        format!(
+            "Server:\n  Name: app\nXOrm:\n  DSN: \"${{file:{}}}\"\n",
+            secret_file.display()
+        ),
+    )
+    .unwrap();
+    unsafe { std::env::set_var("SERVER_CONFIG_LOCATION", &main) };
+
+    let c = x_one::xconfig::init::init_xconfig().unwrap().unwrap();
+
+    unsafe { std::env::remove_var("SERVER_CONFIG_LOCATION") };
+    assert_eq!(c["XOrm"]["DSN"].as_str(), Some("from-file"));
+}
@@ -0,0 +1,235 @@
+use serial_test::serial;
+use std::io::{Read, Write};
+use std::sync::Arc;
+use std::time::Duration;
+use x_one::xconfig::source::*;
+use x_one::xconfig::*;
+
+/// 固定返回配置的测试配置源
+struct StaticSource(&'static str);
+
+impl ConfigSource for StaticSource {
+    fn name(&self) -> String {
+        "static".to_string()
+    }
+
+    fn load(&self) -> Result<Option<serde_yaml::Value>, x_one::XOneError> {
+        Ok(Some(serde_yaml::from_str(self.0).unwrap()))
+    }
+}
+
+fn empty_config() -> serde_yaml::Value {
+    serde_yaml::Value::Mapping(serde_yaml::Mapping::new())
+}
+
+fn source_config(yaml: &str) -> SourceConfig {
+    serde_yaml::from_str(yaml).unwrap()
+}
+
+/// 启动只响应一次请求的 HTTP 服务，返回请求地址
+fn serve_once(status: &'static str, body: &'static str) -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        if let Ok((mut stream, _)) = listener.accept() {
+            let mut buf = [0u8; 4096];
+            let _ = stream.read(&mut buf);
+            let resp = format!(
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = stream.write_all(resp.as_bytes());
+        }
+    });
+    format!("http://{addr}/kv")
+}
+
+#[test]
+fn test_load_sources_later_source_wins() {
+    let base: serde_yaml::Value =
+        serde_yaml::from_str("XLog:\n  Level: info\n  Path: ./log\n").unwrap();
+    let sources: Vec<Arc<dyn ConfigSource>> = vec![
+        Arc::new(StaticSource("XLog:\n  Level: warn\n")),
+        Arc::new(StaticSource("XLog:\n  Level: debug\n")),
+    ];
+
+    let merged = load_sources(base, &sources).unwrap();
+
+    assert_eq!(merged["XLog"]["Level"].as_str(), Some("debug"));
+    assert_eq!(merged["XLog"]["Path"].as_str(), Some("./log"));
+}
+
+#[test]
+fn test_file_source_optional_missing() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("missing.yml");
+
+    assert!(
+        FileSource::new(&path)
+            .optional(true)
+            .load()
+            .unwrap()
+            .is_none()
+    );
+    assert!(FileSource::new(&path).load().is_err());
+}
+
+#[test]
+fn test_directory_source_loads_secret_files() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("XOrm.DSN"), "postgres://secret\n").unwrap();
+    std::fs::write(dir.path().join("extra.yml"), "XLog:\n  Level: warn\n").unwrap();
+    std::fs::write(dir.path().join(".hidden"), "ignored").unwrap();
+
+    let value = DirectorySource::new(dir.path()).load().unwrap().unwrap();
+
+    assert_eq!(value["XOrm"]["DSN"].as_str(), Some("postgres://secret"));
+    assert_eq!(value["XLog"]["Level"].as_str(), Some("warn"));
+    assert!(value.get(".hidden").is_none());
+}
+
+#[test]
+fn test_directory_source_reports_plain_file_keys_as_secrets() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("XOrm.DSN"), "postgres://secret\n").unwrap();
+    std::fs::write(dir.path().join("extra.yml"), "XLog:\n  Level: warn\n").unwrap();
+
+    let (value, keys) = DirectorySource::new(dir.path())
+        .load_with_secrets()
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(value["XOrm"]["DSN"].as_str(), Some("postgres://secret"));
+    assert_eq!(keys, vec!["XOrm.DSN".to_string()]);
+}
+
+#[test]
+fn test_directory_source_missing_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("missing");
+
+    assert!(
+        DirectorySource::new(&path)
+            .optional(true)
+            .load()
+            .unwrap()
+            .is_none()
+    );
+    assert!(DirectorySource::new(&path).load().is_err());
+}
+
+#[test]
+#[serial]
+fn test_env_source_nested_keys() {
+    unsafe { std::env::set_var("XONE_TEST_SRC_XOrm__DSN", "from-env") };
+
+    let value = EnvSource::new("XONE_TEST_SRC_").load().unwrap().unwrap();
+    let merged = load_sources(
+        serde_yaml::from_str("XOrm:\n  DSN: base\n").unwrap(),
+        &[
+            Arc::new(StaticSource("{}")),
+            Arc::new(EnvSource::new("XONE_TEST_SRC_")),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(value["XOrm"]["DSN"].as_str(), Some("from-env"));
+    assert_eq!(merged["XOrm"]["DSN"].as_str(), Some("from-env"));
+    unsafe { std::env::remove_var("XONE_TEST_SRC_XOrm__DSN") };
+}
+
+#[test]
+fn test_http_source_expands_flat_kv() {
+    let url = serve_once(
+        "200 OK",
+        r#"{"XOrm.DSN": "from-http", "XLog": {"Level": "warn"}}"#,
+    );
+
+    let value = HttpSource::new(&url).load().unwrap().unwrap();
+
+    assert_eq!(value["XOrm"]["DSN"].as_str(), Some("from-http"));
+    assert_eq!(value["XLog"]["Level"].as_str(), Some("warn"));
+}
+
+#[test]
+fn test_http_source_error_status() {
+    let url = serve_once("500 Internal Server Error", "oops");
+    assert!(HttpSource::new(&url).load().is_err());
+
+    let url = serve_once("500 Internal Server Error", "oops");
+    let source = HttpSource::new(&url)
+        .timeout(Duration::from_secs(1))
+        .optional(true);
+    assert!(source.load().unwrap().is_none());
+}
+
+#[test]
+fn test_load_sources_rejects_file_reference_from_remote_source() {
+    let url = serve_once("200 OK", r#"{"XOrm.DSN": "${file:/etc/hostname}"}"#);
+    let sources: Vec<Arc<dyn ConfigSource>> = vec![Arc::new(HttpSource::new(&url))];
+
+    let err = load_sources(empty_config(), &sources).unwrap_err();
+    assert!(err.to_string().contains("path=[XOrm.DSN]"), "{err}");
+}
+
+#[test]
+fn test_load_sources_keeps_file_reference_from_local_source() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("extra.yml");
+    std::fs::write(&path, "XOrm:\n  DSN: \"${file:/run/secrets/dsn}\"\n").unwrap();
+    let sources: Vec<Arc<dyn ConfigSource>> = vec![Arc::new(FileSource::new(path))];
+
+    let merged = load_sources(empty_config(), &sources).unwrap();
+    assert_eq!(
+        merged["XOrm"]["DSN"].as_str(),
+        Some("${file:/run/secrets/dsn}")
+    );
+}
+
+#[test]
+fn test_build_source_unknown_type() {
+    let c = source_config("Type: consul\n");
+    let err = build_source(&c, std::path::Path::new("")).err().unwrap();
+    assert!(err.to_string().contains("consul"));
+}
+
+#[test]
+fn test_build_source_relative_path() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("extra.yml"), "Key: extra\n").unwrap();
+    let c = source_config("Type: file\nPath: extra.yml\n");
+
+    let value = build_source(&c, dir.path())
+        .unwrap()
+        .load()
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(value["Key"].as_str(), Some("extra"));
+}
+
+#[test]
+#[serial]
+fn test_reload_applies_sources_with_precedence() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("extra.yml"), "A: declared\nB: declared\n").unwrap();
+    let path = dir.path().join("application.yml");
+    std::fs::write(
+        &path,
+        "Server:\n  Sources:\n    - Type: file\n      Path: extra.yml\nA: local\nB: local\nC: local\n",
+    )
+    .unwrap();
+    let location = path.to_str().unwrap().to_string();
+    set_config(empty_config());
+    add_source(StaticSource("B: registered\n"));
+    start_watch(&location, Duration::from_secs(3600)).unwrap();
+
+    reload().unwrap();
+
+    assert_eq!(get_string("A"), "declared");
+    assert_eq!(get_string("B"), "registered");
+    assert_eq!(get_string("C"), "local");
+    stop_watch().unwrap();
+    reset_sources();
+    reset_config();
+}
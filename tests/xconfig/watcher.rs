@@ -0,0 +1,269 @@
+use serial_test::serial;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use x_one::xconfig::*;
+
+/// 写入配置文件并以其内容初始化全局配置
+fn setup_config_file(dir: &tempfile::TempDir, content: &str) -> String {
+    let path = dir.path().join("application.yml");
+    std::fs::write(&path, content).unwrap();
+    let location = path.to_str().unwrap().to_string();
+    set_config(init::load_local_config(&location).unwrap());
+    location
+}
+
+#[test]
+#[serial]
+fn test_reload_applies_changed_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let location = setup_config_file(&dir, "Server:\n  Name: before\n");
+    start_watch(&location, Duration::from_secs(3600)).unwrap();
+
+    std::fs::write(&location, "Server:\n  Name: after\n").unwrap();
+    let changed = reload().unwrap();
+
+    assert!(changed);
+    assert_eq!(get_string("Server.Name"), "after");
+    stop_watch().unwrap();
+    reset_config();
+}
+
+#[test]
+#[serial]
+fn test_reload_unchanged_file_returns_false() {
+    let dir = tempfile::tempdir().unwrap();
+    let location = setup_config_file(&dir, "Server:\n  Name: same\n");
+    start_watch(&location, Duration::from_secs(3600)).unwrap();
+
+    assert!(!reload().unwrap());
+    stop_watch().unwrap();
+    reset_config();
+}
+
+#[test]
+#[serial]
+fn test_reload_invalid_yaml_keeps_previous_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let location = setup_config_file(&dir, "Server:\n  Name: valid\n");
+    start_watch(&location, Duration::from_secs(3600)).unwrap();
+
+    std::fs::write(&location, "Server: [unclosed\n").unwrap();
+    let result = reload();
+
+    assert!(result.is_err());
+    assert_eq!(get_string("Server.Name"), "valid");
+    stop_watch().unwrap();
+    reset_config();
+}
+
+#[test]
+#[serial]
+fn test_reload_empty_file_keeps_previous_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let location = setup_config_file(&dir, "Server:\n  Name: valid\n");
+    start_watch(&location, Duration::from_secs(3600)).unwrap();
+
+    std::fs::write(&location, "").unwrap();
+
+    assert!(reload().is_err());
+    assert_eq!(get_string("Server.Name"), "valid");
+    stop_watch().unwrap();
+    reset_config();
+}
+
+#[test]
+#[serial]
+fn test_reload_expands_env_placeholders() {
+    let dir = tempfile::tempdir().unwrap();
+    let location = setup_config_file(&dir, "Server:\n  Name: before\n");
+    start_watch(&location, Duration::from_secs(3600)).unwrap();
+    unsafe { std::env::set_var("XONE_TEST_RELOAD_NAME", "from-env") };
+
+    std::fs::write(&location, "Server:\n  Name: ${XONE_TEST_RELOAD_NAME}\n").unwrap();
+    reload().unwrap();
+
+    assert_eq!(get_string("Server.Name"), "from-env");
+    unsafe { std::env::remove_var("XONE_TEST_RELOAD_NAME") };
+    stop_watch().unwrap();
+    reset_config();
+}
+
+#[test]
+#[serial]
+fn test_reload_merges_profile_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let location = setup_config_file(
+        &dir,
+        "Server:\n  Name: base\n  Profiles:\n    Active: dev\nXAxum:\n  Port: 8000\n",
+    );
+    let profile_location = dir.path().join("application-dev.yml");
+    std::fs::write(&profile_location, "XAxum:\n  Port: 9000\n").unwrap();
+    start_watch(&location, Duration::from_secs(3600)).unwrap();
+
+    reload().unwrap();
+    assert_eq!(get_int("XAxum.Port"), 9000);
+
+    std::fs::write(&profile_location, "XAxum:\n  Port: 9100\n").unwrap();
+    reload().unwrap();
+    assert_eq!(get_int("XAxum.Port"), 9100);
+
+    stop_watch().unwrap();
+    reset_config();
+}
+
+#[test]
+#[serial]
+fn test_reload_rejected_by_strict_schema_keeps_active_profiles() {
+    let dir = tempfile::tempdir().unwrap();
+    let location = setup_config_file(
+        &dir,
+        "Server:\n  Name: app\n  Validation:\n    Mode: strict\n  Profiles:\n    Active: dev\n",
+    );
+    start_watch(&location, Duration::from_secs(3600)).unwrap();
+    reload().unwrap();
+    assert_eq!(active_profiles(), vec!["dev".to_string()]);
+
+    // 切换 profile 的同时引入未知 key，strict 模式下整次更新被拒绝
+    std::fs::write(
+        &location,
+        "Server:\n  Name: app\n  Validation:\n    Mode: strict\n  Profiles:\n    Active: prod\nXLog:\n  Levle: debug\n",
+    )
+    .unwrap();
+    assert!(reload().is_err());
+
+    assert_eq!(active_profiles(), vec!["dev".to_string()]);
+    assert_eq!(
+        get_string("Server.Profiles.Active"),
+        "dev",
+        "配置应保持为上一次接受的版本"
+    );
+    stop_watch().unwrap();
+    reset_config();
+}
+
+#[test]
+#[serial]
+fn test_subscribe_only_notified_for_changed_subtree() {
+    let dir = tempfile::tempdir().unwrap();
+    let location = setup_config_file(&dir, "XAxum:\n  Port: 8000\nXLog:\n  Level: info\n");
+    start_watch(&location, Duration::from_secs(3600)).unwrap();
+
+    let axum_calls = Arc::new(AtomicUsize::new(0));
+    let log_calls = Arc::new(AtomicUsize::new(0));
+    let axum_counter = axum_calls.clone();
+    let axum_id = subscribe("XAxum", move |change| {
+        assert_eq!(change.key, "XAxum");
+        assert_eq!(
+            change
+                .old
+                .as_ref()
+                .and_then(|v| v.get("Port"))
+                .and_then(|v| v.as_i64()),
+            Some(8000)
+        );
+        assert_eq!(
+            change
+                .new
+                .as_ref()
+                .and_then(|v| v.get("Port"))
+                .and_then(|v| v.as_i64()),
+            Some(9000)
+        );
+        axum_counter.fetch_add(1, Ordering::SeqCst);
+    });
+    let log_counter = log_calls.clone();
+    let log_id = subscribe("XLog.Level", move |_| {
+        log_counter.fetch_add(1, Ordering::SeqCst);
+    });
+
+    std::fs::write(&location, "XAxum:\n  Port: 9000\nXLog:\n  Level: info\n").unwrap();
+    reload().unwrap();
+
+    assert_eq!(axum_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(log_calls.load(Ordering::SeqCst), 0);
+
+    assert!(unsubscribe(axum_id));
+    assert!(unsubscribe(log_id));
+    assert!(!unsubscribe(axum_id));
+    stop_watch().unwrap();
+    reset_config();
+}
+
+#[test]
+#[serial]
+fn test_subscribe_callback_panic_does_not_break_reload() {
+    let dir = tempfile::tempdir().unwrap();
+    let location = setup_config_file(&dir, "Key: a\n");
+    start_watch(&location, Duration::from_secs(3600)).unwrap();
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let panic_id = subscribe("Key", |_| panic!("callback panic"));
+    let counter = calls.clone();
+    let id = subscribe("Key", move |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+
+    std::fs::write(&location, "Key: b\n").unwrap();
+    assert!(reload().unwrap());
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(get_string("Key"), "b");
+    unsubscribe(panic_id);
+    unsubscribe(id);
+    stop_watch().unwrap();
+    reset_config();
+}
+
+#[test]
+#[serial]
+fn test_watch_channel_receives_new_value() {
+    let dir = tempfile::tempdir().unwrap();
+    let location = setup_config_file(&dir, "XLog:\n  Level: info\n");
+    start_watch(&location, Duration::from_secs(3600)).unwrap();
+
+    let mut rx = watch("XLog.Level");
+    assert_eq!(rx.borrow().as_ref().and_then(|v| v.as_str()), Some("info"));
+
+    std::fs::write(&location, "XLog:\n  Level: debug\n").unwrap();
+    reload().unwrap();
+
+    assert!(rx.has_changed().unwrap());
+    assert_eq!(
+        rx.borrow_and_update().as_ref().and_then(|v| v.as_str()),
+        Some("debug")
+    );
+    stop_watch().unwrap();
+    reset_config();
+}
+
+#[test]
+#[serial]
+fn test_start_watch_detects_file_change() {
+    let dir = tempfile::tempdir().unwrap();
+    let location = setup_config_file(&dir, "Server:\n  Name: before\n");
+    start_watch(&location, Duration::from_millis(20)).unwrap();
+
+    // 保证文件大小变化，避免依赖文件系统 mtime 精度
+    std::fs::write(&location, "Server:\n  Name: after-watch\n").unwrap();
+
+    let mut applied = false;
+    for _ in 0..100 {
+        if get_string("Server.Name") == "after-watch" {
+            applied = true;
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+
+    assert!(applied, "监听线程应自动加载变更后的配置");
+    stop_watch().unwrap();
+    reset_config();
+}
+
+#[test]
+#[serial]
+fn test_stop_watch_without_start_is_ok() {
+    stop_watch().unwrap();
+    assert!(stop_watch().is_ok());
+}
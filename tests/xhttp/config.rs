@@ -1,11 +1,12 @@
+use std::time::Duration;
 use x_one::xhttp::config::*;
 
 #[test]
 fn test_default_config() {
     let config = XHttpConfig::default();
-    assert_eq!(config.timeout, "30s");
-    assert_eq!(config.dial_timeout, "10s");
-    assert_eq!(config.dial_keep_alive, "30s");
+    assert_eq!(config.timeout, Duration::from_secs(30));
+    assert_eq!(config.dial_timeout, Duration::from_secs(10));
+    assert_eq!(config.dial_keep_alive, Duration::from_secs(30));
     assert_eq!(config.pool_max_idle_per_host, 10);
 }
 
@@ -18,8 +19,8 @@ DialKeepAlive: "60s"
 PoolMaxIdlePerHost: 20
 "#;
     let config: XHttpConfig = serde_yaml::from_str(yaml).unwrap();
-    assert_eq!(config.timeout, "60s");
-    assert_eq!(config.dial_timeout, "5s");
+    assert_eq!(config.timeout, Duration::from_secs(60));
+    assert_eq!(config.dial_timeout, Duration::from_secs(5));
     assert_eq!(config.pool_max_idle_per_host, 20);
 }
 
@@ -27,5 +28,11 @@ PoolMaxIdlePerHost: 20
 fn test_deserialize_minimal_yaml() {
     let yaml = "{}";
     let config: XHttpConfig = serde_yaml::from_str(yaml).unwrap();
-    assert_eq!(config.timeout, "30s");
+    assert_eq!(config.timeout, Duration::from_secs(30));
+}
+
+#[test]
+fn test_deserialize_invalid_duration_returns_error() {
+    let result = serde_yaml::from_str::<XHttpConfig>("DialTimeout: 10 parsecs");
+    assert!(result.is_err());
 }
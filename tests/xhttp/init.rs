@@ -1,3 +1,5 @@
+use serial_test::serial;
+use std::time::Duration;
 use x_one::xhttp::client::*;
 use x_one::xhttp::*;
 
@@ -11,25 +13,26 @@ fn test_build_client_default_config() {
 #[test]
 fn test_build_client_custom_config() {
     let config = XHttpConfig {
-        timeout: "60s".to_string(),
-        dial_timeout: "5s".to_string(),
-        dial_keep_alive: "120s".to_string(),
+        timeout: Duration::from_secs(60),
+        dial_timeout: Duration::from_secs(5),
+        dial_keep_alive: Duration::from_secs(120),
         pool_max_idle_per_host: 50,
-        ..XHttpConfig::default()
     };
     let result = build_client(&config);
     assert!(result.is_ok());
 }
 
 #[test]
-fn test_build_client_with_invalid_duration_uses_default() {
-    // to_duration 无效时回退默认值，不会导致构建失败
-    let config = XHttpConfig {
-        timeout: "invalid".to_string(),
-        ..XHttpConfig::default()
-    };
-    let result = build_client(&config);
-    assert!(result.is_ok());
+#[serial]
+fn test_init_xhttp_invalid_duration_returns_error() {
+    // 非法 duration 不再静默回退默认值，初始化时报告带 key 路径的错误
+    x_one::xconfig::set_config(serde_yaml::from_str("XHttp:\n  Timeout: soon\n").unwrap());
+    let err = x_one::xhttp::init::init_xhttp().unwrap_err();
+    x_one::xconfig::reset_config();
+
+    let msg = err.to_string();
+    assert!(msg.contains("parse config [XHttp] failed"), "{msg}");
+    assert!(msg.contains("invalid duration [soon]"), "{msg}");
 }
 
 #[test]
@@ -42,8 +45,8 @@ fn test_client_returns_instance() {
 #[test]
 fn test_config_default() {
     let config = XHttpConfig::default();
-    assert_eq!(config.timeout, "30s");
-    assert_eq!(config.dial_timeout, "10s");
-    assert_eq!(config.dial_keep_alive, "30s");
+    assert_eq!(config.timeout, Duration::from_secs(30));
+    assert_eq!(config.dial_timeout, Duration::from_secs(10));
+    assert_eq!(config.dial_keep_alive, Duration::from_secs(30));
     assert_eq!(config.pool_max_idle_per_host, 10);
 }
@@ -41,3 +41,12 @@ fn test_patch_request_builder() {
 fn test_head_request_builder() {
     let _builder = head("http://example.com");
 }
+
+#[test]
+fn test_with_trace_context_without_trace() {
+    // xtrace 未启用时不注入任何头
+    let request = with_trace_context(c().get("http://example.com"))
+        .build()
+        .unwrap();
+    assert!(request.headers().get("traceparent").is_none());
+}
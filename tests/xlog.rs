@@ -15,3 +15,36 @@ mod kv_layer;
 
 #[path = "xlog/otel_fmt.rs"]
 mod otel_fmt;
+
+#[path = "xlog/retention.rs"]
+mod retention;
+
+#[path = "xlog/rolling.rs"]
+mod rolling;
+
+#[path = "xlog/timezone.rs"]
+mod timezone;
+
+#[path = "xlog/level.rs"]
+mod level;
+
+#[path = "xlog/redact.rs"]
+mod redact;
+
+#[path = "xlog/throttle.rs"]
+mod throttle;
+
+#[path = "xlog/sink.rs"]
+mod sink;
+
+#[path = "xlog/format.rs"]
+mod format;
+
+#[path = "xlog/otlp.rs"]
+mod otlp;
+
+#[path = "xlog/output.rs"]
+mod output;
+
+#[path = "xlog/request_debug.rs"]
+mod request_debug;
@@ -1,3 +1,4 @@
+use std::time::Duration;
 use x_one::xlog::config::*;
 
 #[test]
@@ -6,8 +7,8 @@ fn test_default_config_values() {
     assert_eq!(c.name, "app");
     assert_eq!(c.level, LogLevel::Info);
     assert_eq!(c.path, "./log");
-    assert_eq!(c.max_age, "7d");
-    assert_eq!(c.rotate_time, "1d");
+    assert_eq!(c.max_age, Duration::from_secs(604800));
+    assert_eq!(c.rotate_time, Duration::from_secs(86400));
     assert_eq!(c.timezone, "Asia/Shanghai");
     assert!(!c.console);
     assert!(!c.console_format_is_raw);
@@ -27,3 +28,48 @@ fn test_config_with_values() {
     assert_eq!(c.path, "/var/log");
     assert!(c.console);
 }
+
+#[test]
+fn test_retention_fields_deserialize() {
+    let c: XLogConfig =
+        serde_yaml::from_str("MaxAge: 3d\nMaxFiles: 10\nMaxTotalSize: 1GiB\n").unwrap();
+    assert_eq!(c.max_age, Duration::from_secs(3 * 86400));
+    assert_eq!(c.max_files, 10);
+    assert_eq!(c.max_total_size, 1 << 30);
+
+    let c: XLogConfig = serde_yaml::from_str("MaxTotalSize: 2048\n").unwrap();
+    assert_eq!(c.max_total_size, 2048);
+    assert_eq!(c.max_files, 0);
+
+    assert!(serde_yaml::from_str::<XLogConfig>("MaxTotalSize: lots\n").is_err());
+}
+
+#[test]
+fn test_targets_compile_to_filter_directives() {
+    let c: XLogConfig = serde_yaml::from_str(
+        "Level: debug\nTargets:\n  sqlx: warn\n  my_app::billing: trace\n  hyper: error\n",
+    )
+    .unwrap();
+    assert_eq!(c.targets.len(), 3);
+    assert_eq!(
+        c.filter_directives().unwrap(),
+        "debug,hyper=error,my_app::billing=trace,sqlx=warn"
+    );
+
+    assert_eq!(XLogConfig::default().filter_directives().unwrap(), "info");
+}
+
+#[test]
+fn test_targets_invalid_level_returns_error() {
+    assert!(serde_yaml::from_str::<XLogConfig>("Targets:\n  sqlx: verbose\n").is_err());
+    assert!(serde_yaml::from_str::<XLogConfig>("Level: loud\n").is_err());
+}
+
+#[test]
+fn test_targets_invalid_target_returns_error() {
+    for target in ["\"\"", "\"sqlx=debug\"", "\"a,b\"", "\"my app\""] {
+        let c: XLogConfig = serde_yaml::from_str(&format!("Targets:\n  {target}: warn\n")).unwrap();
+        let err = c.filter_directives().unwrap_err();
+        assert!(err.to_string().contains("invalid log target"), "{err}");
+    }
+}
@@ -64,3 +64,18 @@ fn test_format_console_line_without_caller() {
     // 空 caller 不应出现括号
     assert!(!line.contains("()"));
 }
+
+#[test]
+fn test_format_plain_line_has_no_color() {
+    let line = format_plain_line(
+        &tracing::Level::WARN,
+        "2024-01-01 00:00:00.000",
+        "warn msg",
+        "abc123",
+        "src/main.rs:42",
+    );
+    assert_eq!(
+        line,
+        "WARN[2024-01-01 00:00:00.000] abc123 warn msg (src/main.rs:42)\n"
+    );
+}
@@ -0,0 +1,164 @@
+use opentelemetry::trace::{Tracer, TracerProvider};
+use x_one::xlog::LogTimezone;
+use x_one::xlog::config::{LogFormat, XLogConfig};
+use x_one::xlog::format::{GcpFormat, fmt_layer, logfmt_value};
+use x_one::xlog::kv_layer::KvLayer;
+
+/// 以指定格式输出一条带 KV 与 OpenTelemetry Span 的日志，返回输出内容
+fn capture(format: LogFormat) -> String {
+    use tracing_subscriber::prelude::*;
+
+    let buf = std::sync::Arc::new(parking_lot::Mutex::new(Vec::new()));
+    let buf_clone = buf.clone();
+    let writer =
+        move || -> Box<dyn std::io::Write + Send> { Box::new(SharedWriter(buf_clone.clone())) };
+
+    let subscriber = tracing_subscriber::registry().with(KvLayer).with(fmt_layer(
+        format,
+        LogTimezone::Utc,
+        writer,
+    ));
+
+    let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder().build();
+    let tracer = provider.tracer("test");
+    tracing::subscriber::with_default(subscriber, || {
+        tracer.in_span("request", |_cx| {
+            let _guard = x_one::xlog_kv!(user_id = "u123");
+            tracing::warn!(order_id = 42, password = "p@ss", "order failed");
+        });
+    });
+
+    String::from_utf8(buf.lock().clone()).unwrap()
+}
+
+#[test]
+fn test_format_config_defaults_to_json() {
+    assert_eq!(XLogConfig::default().format, LogFormat::Json);
+
+    let c: XLogConfig = serde_yaml::from_str("Format: ecs\nErrorFile: true\n").unwrap();
+    assert_eq!(c.format, LogFormat::Ecs);
+    assert_eq!(c.file_sinks().unwrap()[0].format, LogFormat::Ecs);
+
+    assert!(serde_yaml::from_str::<XLogConfig>("Format: xml\n").is_err());
+}
+
+#[test]
+fn test_ecs_format_fields() {
+    let output = capture(LogFormat::Ecs);
+    let json: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+
+    assert_eq!(json["log.level"], "WARN");
+    assert_eq!(json["message"], "order failed");
+    assert_eq!(json["ecs.version"], x_one::xlog::format::ECS_VERSION);
+    assert!(json["log.logger"].as_str().is_some());
+    assert!(json["log.origin.file.name"].as_str().is_some());
+    assert!(json["log.origin.file.line"].as_u64().is_some());
+    assert!(json["process.thread.name"].as_str().is_some());
+    let timestamp = json["@timestamp"].as_str().unwrap();
+    assert!(chrono::DateTime::parse_from_rfc3339(timestamp).is_ok());
+    assert_eq!(json["trace.id"].as_str().unwrap().len(), 32);
+    assert_eq!(json["span.id"].as_str().unwrap().len(), 16);
+    assert_eq!(json["user_id"], "u123");
+    assert_eq!(json["order_id"], 42);
+    assert_eq!(json["password"], "***");
+}
+
+#[test]
+fn test_gcp_format_fields() {
+    let output = capture(LogFormat::Gcp);
+    let json: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+
+    assert_eq!(json["severity"], "WARNING");
+    assert_eq!(json["message"], "order failed");
+    assert!(json["timestamp"].as_str().is_some());
+    let location = &json["logging.googleapis.com/sourceLocation"];
+    assert!(location["file"].as_str().is_some());
+    assert!(location["line"].as_str().unwrap().parse::<u32>().is_ok());
+    assert!(json["logging.googleapis.com/trace"].as_str().is_some());
+    assert_eq!(
+        json["logging.googleapis.com/spanId"]
+            .as_str()
+            .unwrap()
+            .len(),
+        16
+    );
+    assert_eq!(json["user_id"], "u123");
+    assert_eq!(json["password"], "***");
+}
+
+#[test]
+fn test_gcp_format_trace_with_project_id() {
+    use tracing_subscriber::prelude::*;
+
+    let buf = std::sync::Arc::new(parking_lot::Mutex::new(Vec::new()));
+    let buf_clone = buf.clone();
+    let writer =
+        move || -> Box<dyn std::io::Write + Send> { Box::new(SharedWriter(buf_clone.clone())) };
+    let subscriber = tracing_subscriber::registry().with(
+        tracing_subscriber::fmt::layer()
+            .event_format(GcpFormat::default().with_project_id("my-project"))
+            .with_writer(writer),
+    );
+
+    let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder().build();
+    let tracer = provider.tracer("test");
+    tracing::subscriber::with_default(subscriber, || {
+        tracer.in_span("request", |_cx| tracing::trace!("detail"));
+    });
+
+    let output = String::from_utf8(buf.lock().clone()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+    assert_eq!(json["severity"], "DEBUG");
+    let trace = json["logging.googleapis.com/trace"].as_str().unwrap();
+    assert!(trace.starts_with("projects/my-project/traces/"), "{trace}");
+    assert_eq!(trace.len(), "projects/my-project/traces/".len() + 32);
+}
+
+#[test]
+fn test_logfmt_format_line() {
+    let output = capture(LogFormat::Logfmt);
+    let line = output.trim_end();
+
+    assert!(!line.contains('\n'), "{line}");
+    assert!(line.starts_with("time="), "{line}");
+    assert!(line.contains(" level=warn "), "{line}");
+    assert!(line.contains(" msg=\"order failed\" "), "{line}");
+    assert!(line.contains(" caller="), "{line}");
+    assert!(line.contains(" trace_id="), "{line}");
+    assert!(line.contains(" span_id="), "{line}");
+    assert!(line.contains(" user_id=u123"), "{line}");
+    assert!(line.contains(" order_id=42"), "{line}");
+    assert!(line.contains(" password=***"), "{line}");
+}
+
+#[test]
+fn test_logfmt_value_escape() {
+    assert_eq!(logfmt_value("plain"), "plain");
+    assert_eq!(logfmt_value("a=b"), "\"a=b\"");
+    assert_eq!(logfmt_value("line1\nline2"), "\"line1\\nline2\"");
+    assert_eq!(logfmt_value("C:\\x y"), "\"C:\\\\x y\"");
+}
+
+#[test]
+fn test_text_and_json_formats_unchanged() {
+    let json: serde_json::Value = serde_json::from_str(capture(LogFormat::Json).trim()).unwrap();
+    assert_eq!(json["fields"]["message"], "order failed");
+    assert_eq!(json["fields"]["user_id"], "u123");
+
+    let text = capture(LogFormat::Text);
+    assert!(text.starts_with("WARN["), "{text}");
+    assert!(!text.contains('\x1b'), "{text}");
+}
+
+struct SharedWriter(std::sync::Arc<parking_lot::Mutex<Vec<u8>>>);
+
+impl std::io::Write for SharedWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
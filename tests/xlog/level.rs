@@ -0,0 +1,103 @@
+use serial_test::serial;
+use std::time::Duration;
+use tracing_subscriber::{EnvFilter, Registry, reload};
+use x_one::xlog;
+
+type FilterLayer = reload::Layer<EnvFilter, Registry>;
+type FilterHandle = reload::Handle<EnvFilter, Registry>;
+
+/// 安装测试用过滤器句柄（Layer 被 drop 后句柄失效，需由调用方持有）
+fn install(level: &str) -> (FilterLayer, FilterHandle) {
+    let (layer, handle) = FilterLayer::new(EnvFilter::new(level));
+    xlog::level::install(handle.clone(), level);
+    (layer, handle)
+}
+
+fn current_filter(handle: &FilterHandle) -> String {
+    handle.with_current(|f| f.to_string()).unwrap()
+}
+
+#[test]
+#[serial]
+fn test_set_level_reloads_filter() {
+    let (_layer, handle) = install("info");
+    assert_eq!(xlog::get_level().as_deref(), Some("info"));
+
+    xlog::set_level(" warn,sqlx=debug ").unwrap();
+
+    assert_eq!(xlog::get_level().as_deref(), Some("warn,sqlx=debug"));
+    let filter = current_filter(&handle);
+    assert!(filter.contains("sqlx=debug"), "filter: {filter}");
+}
+
+#[test]
+#[serial]
+fn test_set_level_invalid_directive_keeps_level() {
+    let (_layer, handle) = install("info");
+
+    let err = xlog::set_level("info,sqlx=verbose").unwrap_err();
+
+    assert!(err.to_string().contains("invalid log level"), "{err}");
+    assert_eq!(xlog::get_level().as_deref(), Some("info"));
+    assert_eq!(current_filter(&handle), "info");
+}
+
+#[test]
+#[serial]
+fn test_set_level_for_reverts_after_ttl() {
+    let (_layer, handle) = install("info");
+
+    xlog::set_level_for("debug", Duration::from_millis(100)).unwrap();
+    assert_eq!(xlog::get_level().as_deref(), Some("debug"));
+
+    std::thread::sleep(Duration::from_millis(400));
+    assert_eq!(xlog::get_level().as_deref(), Some("info"));
+    assert_eq!(current_filter(&handle), "info");
+}
+
+#[test]
+#[serial]
+fn test_set_level_cancels_pending_revert() {
+    let (_layer, _) = install("info");
+
+    xlog::set_level_for("debug", Duration::from_millis(100)).unwrap();
+    xlog::set_level("warn").unwrap();
+
+    std::thread::sleep(Duration::from_millis(400));
+    assert_eq!(xlog::get_level().as_deref(), Some("warn"));
+}
+
+#[test]
+#[serial]
+fn test_set_level_for_replaces_pending_revert() {
+    let (_layer, handle) = install("info");
+
+    xlog::set_level_for("debug", Duration::from_millis(100)).unwrap();
+    xlog::set_level_for("trace", Duration::from_millis(600)).unwrap();
+
+    // 第一次调整的到期时间已过，不应覆盖较新的调整
+    std::thread::sleep(Duration::from_millis(300));
+    assert_eq!(xlog::get_level().as_deref(), Some("trace"));
+    assert_eq!(current_filter(&handle), "trace");
+
+    std::thread::sleep(Duration::from_millis(600));
+    assert_eq!(xlog::get_level().as_deref(), Some("info"));
+    assert_eq!(current_filter(&handle), "info");
+}
+
+#[test]
+#[serial]
+fn test_shutdown_stops_revert_worker() {
+    let (_layer, _handle) = install("info");
+
+    xlog::set_level_for("debug", Duration::from_millis(100)).unwrap();
+    xlog::init::shutdown_xlog().unwrap();
+    std::thread::sleep(Duration::from_millis(300));
+    // 恢复线程已停止并被回收，不再自动恢复
+    assert_eq!(xlog::get_level().as_deref(), Some("debug"));
+
+    // 再次临时调整时重新启动恢复线程
+    xlog::set_level_for("warn", Duration::from_millis(100)).unwrap();
+    std::thread::sleep(Duration::from_millis(400));
+    assert_eq!(xlog::get_level().as_deref(), Some("info"));
+}
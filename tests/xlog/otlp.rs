@@ -0,0 +1,434 @@
+use opentelemetry::trace::{Tracer, TracerProvider};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::logs::SdkLoggerProvider;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use x_one::xlog::config::{OtlpConfig, OtlpProtocol, XLogConfig};
+use x_one::xlog::kv_layer::KvLayer;
+use x_one::xlog::otlp::{OtlpLogLayer, OtlpLogProcessor, OtlpStats};
+
+/// Collector 收到的请求
+#[derive(Debug, Clone)]
+struct Captured {
+    path: String,
+    content_type: String,
+    api_key: String,
+    body: Vec<u8>,
+}
+
+/// 本地 mock OTLP Collector（HTTP/1 与 h2c 自动识别）
+struct MockCollector {
+    addr: SocketAddr,
+    requests: Arc<parking_lot::Mutex<Vec<Captured>>>,
+    _runtime: tokio::runtime::Runtime,
+}
+
+impl MockCollector {
+    fn start(status: u16, delay: Duration) -> Self {
+        use axum::extract::Request;
+        use hyper_util::rt::{TokioExecutor, TokioIo};
+        use hyper_util::server::conn::auto;
+        use tower::ServiceExt;
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .enable_all()
+            .build()
+            .unwrap();
+        let requests = Arc::new(parking_lot::Mutex::new(Vec::new()));
+
+        let captured = requests.clone();
+        let router = axum::Router::new().fallback(move |req: Request| {
+            let captured = captured.clone();
+            async move {
+                let headers = req.headers().clone();
+                let header = |name: &str| {
+                    headers
+                        .get(name)
+                        .and_then(|v| v.to_str().ok())
+                        .unwrap_or_default()
+                        .to_string()
+                };
+                let (path, content_type, api_key) = (
+                    req.uri().path().to_string(),
+                    header("content-type"),
+                    header("x-api-key"),
+                );
+                let body = axum::body::to_bytes(req.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                tokio::time::sleep(delay).await;
+                let grpc = content_type.starts_with("application/grpc");
+                captured.lock().push(Captured {
+                    path,
+                    content_type,
+                    api_key,
+                    body: body.to_vec(),
+                });
+                let builder = axum::http::Response::builder().status(status);
+                if grpc {
+                    // 空的 ExportLogsServiceResponse 消息帧，grpc-status 放在响应头
+                    builder
+                        .header("content-type", "application/grpc")
+                        .header("grpc-status", "0")
+                        .body(axum::body::Body::from(vec![0u8; 5]))
+                        .unwrap()
+                } else {
+                    builder.body(axum::body::Body::empty()).unwrap()
+                }
+            }
+        });
+
+        let listener = runtime
+            .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+            .unwrap();
+        let addr = listener.local_addr().unwrap();
+        runtime.spawn(async move {
+            let builder = auto::Builder::new(TokioExecutor::new());
+            loop {
+                let (socket, _) = listener.accept().await.unwrap();
+                let router = router.clone();
+                let service =
+                    hyper::service::service_fn(move |req: Request<hyper::body::Incoming>| {
+                        router.clone().oneshot(req.map(axum::body::Body::new))
+                    });
+                let conn = builder
+                    .serve_connection(TokioIo::new(socket), service)
+                    .into_owned();
+                tokio::spawn(conn);
+            }
+        });
+
+        Self {
+            addr,
+            requests,
+            _runtime: runtime,
+        }
+    }
+
+    fn config(&self, protocol: OtlpProtocol) -> OtlpConfig {
+        OtlpConfig {
+            enable: true,
+            endpoint: format!("http://{}", self.addr),
+            protocol,
+            ..Default::default()
+        }
+    }
+
+    fn requests(&self) -> Vec<Captured> {
+        self.requests.lock().clone()
+    }
+}
+
+fn build_provider(c: &OtlpConfig) -> (SdkLoggerProvider, Arc<OtlpStats>) {
+    let processor = OtlpLogProcessor::new(c).unwrap();
+    let stats = processor.stats();
+    let provider = SdkLoggerProvider::builder()
+        .with_log_processor(processor)
+        .with_resource(
+            Resource::builder_empty()
+                .with_service_name("otlp-test")
+                .build(),
+        )
+        .build();
+    (provider, stats)
+}
+
+/// 在带 KV 与 OpenTelemetry Span 的上下文中输出一条 warn 日志
+fn emit_order_failed(provider: &SdkLoggerProvider) {
+    use tracing_subscriber::prelude::*;
+
+    let subscriber = tracing_subscriber::registry()
+        .with(KvLayer)
+        .with(OtlpLogLayer::new(provider));
+    let tracer = opentelemetry_sdk::trace::SdkTracerProvider::builder()
+        .build()
+        .tracer("test");
+    tracing::subscriber::with_default(subscriber, || {
+        tracer.in_span("request", |_cx| {
+            let _guard = x_one::xlog_kv!(user_id = "u123");
+            tracing::warn!(target: "otlp_test", order_id = 42, password = "p@ss", "order failed");
+        });
+    });
+}
+
+fn emit_many(provider: &SdkLoggerProvider, count: usize) {
+    use tracing_subscriber::prelude::*;
+
+    let subscriber = tracing_subscriber::registry().with(OtlpLogLayer::new(provider));
+    tracing::subscriber::with_default(subscriber, || {
+        for i in 0..count {
+            tracing::info!(target: "otlp_test", seq = i, "event");
+        }
+    });
+}
+
+fn attribute<'a>(record: &'a serde_json::Value, key: &str) -> &'a serde_json::Value {
+    record["attributes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|kv| kv["key"] == key)
+        .map(|kv| &kv["value"])
+        .unwrap_or_else(|| panic!("attribute {key} not found in {record}"))
+}
+
+#[test]
+fn test_otlp_config_defaults_and_validate() {
+    let c = XLogConfig::default().otlp;
+    assert!(!c.enable);
+    assert_eq!(c.protocol, OtlpProtocol::HttpProtobuf);
+    assert_eq!(c.max_queue_size, 2048);
+    assert_eq!(c.max_batch_size, 512);
+    assert_eq!(c.export_url(), "http://localhost:4318/v1/logs");
+    assert!(c.validate().is_ok());
+
+    let c: XLogConfig = serde_yaml::from_str(
+        "Otlp:\n  Enable: true\n  Endpoint: \"https://collector:4318/v1/logs\"\n  Protocol: http/json\n  Headers:\n    x-api-key: abc\n  ExportInterval: 5s\n",
+    )
+    .unwrap();
+    assert_eq!(c.otlp.protocol, OtlpProtocol::HttpJson);
+    assert_eq!(c.otlp.export_url(), "https://collector:4318/v1/logs");
+    assert_eq!(c.otlp.headers["x-api-key"], "abc");
+    assert_eq!(c.otlp.export_interval, Duration::from_secs(5));
+
+    let c: XLogConfig = serde_yaml::from_str("Otlp:\n  Protocol: grpc\n").unwrap();
+    assert_eq!(
+        c.otlp.export_url(),
+        "http://localhost:4317/opentelemetry.proto.collector.logs.v1.LogsService/Export"
+    );
+    assert!(serde_yaml::from_str::<XLogConfig>("Otlp:\n  Protocol: thrift\n").is_err());
+}
+
+#[test]
+fn test_otlp_config_invalid_values_return_error() {
+    let invalid = [
+        OtlpConfig {
+            max_queue_size: 0,
+            ..Default::default()
+        },
+        OtlpConfig {
+            export_interval: Duration::ZERO,
+            ..Default::default()
+        },
+        OtlpConfig {
+            endpoint: "collector:4318".to_string(),
+            ..Default::default()
+        },
+        OtlpConfig {
+            targets: [("bad target".to_string(), Default::default())].into(),
+            ..Default::default()
+        },
+    ];
+    for c in invalid {
+        let err = c.validate().unwrap_err();
+        assert!(err.to_string().contains("XLog.Otlp"), "{err}");
+        assert!(OtlpLogProcessor::new(&c).is_err());
+    }
+
+    let c = OtlpConfig {
+        headers: [("bad header".to_string(), "v".to_string())].into(),
+        ..Default::default()
+    };
+    let err = OtlpLogProcessor::new(&c).unwrap_err();
+    assert!(err.to_string().contains("XLog.Otlp.Headers"), "{err}");
+}
+
+#[test]
+fn test_otlp_http_json_exports_record_with_kv_and_trace() {
+    let collector = MockCollector::start(200, Duration::ZERO);
+    let (provider, stats) = build_provider(&collector.config(OtlpProtocol::HttpJson));
+
+    emit_order_failed(&provider);
+    provider.force_flush().unwrap();
+
+    let requests = collector.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/v1/logs");
+    assert_eq!(requests[0].content_type, "application/json");
+
+    let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+    let resource_logs = &body["resourceLogs"][0];
+    let service = resource_logs["resource"]["attributes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|kv| kv["key"] == "service.name")
+        .unwrap();
+    assert_eq!(service["value"]["stringValue"], "otlp-test");
+
+    let scope_logs = &resource_logs["scopeLogs"][0];
+    assert_eq!(scope_logs["scope"]["name"], "otlp_test");
+    let record = &scope_logs["logRecords"][0];
+    assert_eq!(record["body"]["stringValue"], "order failed");
+    assert_eq!(record["severityText"], "WARN");
+    assert_eq!(record["severityNumber"], 13);
+    assert_eq!(record["traceId"].as_str().unwrap().len(), 32);
+    assert_eq!(record["spanId"].as_str().unwrap().len(), 16);
+    assert_eq!(attribute(record, "user_id")["stringValue"], "u123");
+    assert_eq!(attribute(record, "order_id")["intValue"], "42");
+    assert_eq!(attribute(record, "password")["stringValue"], "***");
+    assert!(attribute(record, "code.lineno")["intValue"].is_string());
+
+    assert_eq!(stats.exported(), 1);
+    assert_eq!(stats.dropped(), 0);
+    provider.shutdown().unwrap();
+}
+
+#[test]
+fn test_otlp_http_protobuf_sends_headers_and_flushes_on_shutdown() {
+    let collector = MockCollector::start(200, Duration::ZERO);
+    let mut c = collector.config(OtlpProtocol::HttpProtobuf);
+    c.headers
+        .insert("x-api-key".to_string(), "secret-key".to_string());
+    c.export_interval = Duration::from_secs(60);
+    let (provider, stats) = build_provider(&c);
+
+    emit_order_failed(&provider);
+    assert!(collector.requests().is_empty());
+    // shutdown 时发送队列中剩余日志
+    provider.shutdown().unwrap();
+
+    let requests = collector.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/v1/logs");
+    assert_eq!(requests[0].content_type, "application/x-protobuf");
+    assert_eq!(requests[0].api_key, "secret-key");
+    let body = &requests[0].body;
+    assert!(contains(body, b"order failed"));
+    assert!(contains(body, b"otlp-test"));
+    assert!(!contains(body, b"p@ss"));
+    assert_eq!(stats.exported(), 1);
+}
+
+#[test]
+fn test_otlp_grpc_sends_framed_protobuf() {
+    let collector = MockCollector::start(200, Duration::ZERO);
+    let (provider, stats) = build_provider(&collector.config(OtlpProtocol::Grpc));
+
+    emit_order_failed(&provider);
+    provider.force_flush().unwrap();
+
+    let requests = collector.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(
+        requests[0].path,
+        "/opentelemetry.proto.collector.logs.v1.LogsService/Export"
+    );
+    assert_eq!(requests[0].content_type, "application/grpc");
+    let body = &requests[0].body;
+    assert_eq!(body[0], 0, "未压缩");
+    let len = u32::from_be_bytes(body[1..5].try_into().unwrap()) as usize;
+    assert_eq!(len, body.len() - 5);
+    assert!(contains(body, b"order failed"));
+    assert_eq!(stats.exported(), 1);
+    provider.shutdown().unwrap();
+}
+
+#[test]
+fn test_otlp_batches_by_max_batch_size() {
+    let collector = MockCollector::start(200, Duration::ZERO);
+    let mut c = collector.config(OtlpProtocol::HttpJson);
+    c.max_batch_size = 4;
+    c.export_interval = Duration::from_secs(60);
+    let (provider, stats) = build_provider(&c);
+
+    emit_many(&provider, 10);
+    provider.force_flush().unwrap();
+
+    let sizes: Vec<usize> = collector
+        .requests()
+        .iter()
+        .map(|r| {
+            let body: serde_json::Value = serde_json::from_slice(&r.body).unwrap();
+            body["resourceLogs"][0]["scopeLogs"][0]["logRecords"]
+                .as_array()
+                .unwrap()
+                .len()
+        })
+        .collect();
+    // 每批不超过 MaxBatchSize，批次划分取决于发送线程何时被唤醒
+    assert!(sizes.iter().all(|&n| n <= 4), "{sizes:?}");
+    assert_eq!(sizes.iter().sum::<usize>(), 10);
+    assert_eq!(stats.exported(), 10);
+    provider.shutdown().unwrap();
+}
+
+#[test]
+fn test_otlp_drops_when_queue_full() {
+    // Collector 响应慢，发送线程阻塞时新日志只能进入容量为 2 的队列
+    let collector = MockCollector::start(200, Duration::from_millis(300));
+    let mut c = collector.config(OtlpProtocol::HttpJson);
+    c.max_queue_size = 2;
+    c.max_batch_size = 1;
+    let (provider, stats) = build_provider(&c);
+
+    emit_many(&provider, 20);
+    provider.force_flush().unwrap();
+
+    assert!(stats.dropped_queue_full() >= 15, "{stats:?}");
+    assert_eq!(
+        stats.exported() + stats.dropped_queue_full(),
+        20,
+        "{stats:?}"
+    );
+    assert_eq!(stats.dropped_export_failed(), 0);
+    provider.shutdown().unwrap();
+}
+
+#[test]
+fn test_otlp_pending_count_does_not_drift() {
+    use opentelemetry::InstrumentationScope;
+    use opentelemetry::logs::{LogRecord, Logger, LoggerProvider};
+    use opentelemetry_sdk::logs::LogProcessor;
+
+    let collector = MockCollector::start(200, Duration::ZERO);
+    let mut c = collector.config(OtlpProtocol::HttpJson);
+    c.max_queue_size = 2;
+    c.max_batch_size = 1;
+    let processor = OtlpLogProcessor::new(&c).unwrap();
+    let stats = processor.stats();
+    let logger = SdkLoggerProvider::builder().build().logger("otlp_test");
+    let scope = InstrumentationScope::default();
+    let emit = |n: usize| {
+        for _ in 0..n {
+            let mut record = logger.create_log_record();
+            record.set_observed_timestamp(std::time::SystemTime::now());
+            processor.emit(&mut record, &scope);
+        }
+    };
+
+    // 每轮填满队列后 flush，计数应回到 0
+    for _ in 0..3 {
+        emit(2);
+        processor.force_flush().unwrap();
+    }
+    assert_eq!(stats.exported(), 6, "{stats:?}");
+    assert_eq!(stats.dropped_queue_full(), 0, "{stats:?}");
+
+    // 关闭后的日志不进入队列，也不误计为 queue_full
+    processor.shutdown().unwrap();
+    emit(5);
+    assert_eq!(stats.exported(), 6, "{stats:?}");
+    assert_eq!(stats.dropped_queue_full(), 0, "{stats:?}");
+}
+
+#[test]
+fn test_otlp_counts_export_failures() {
+    let collector = MockCollector::start(503, Duration::ZERO);
+    let (provider, stats) = build_provider(&collector.config(OtlpProtocol::HttpProtobuf));
+
+    emit_many(&provider, 3);
+    // flush 返回本次发送的错误
+    assert!(provider.force_flush().is_err());
+
+    assert_eq!(collector.requests().len(), 1);
+    assert_eq!(stats.exported(), 0);
+    assert_eq!(stats.dropped_export_failed(), 3);
+    provider.shutdown().unwrap();
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
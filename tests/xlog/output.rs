@@ -0,0 +1,137 @@
+use tracing_subscriber::Registry;
+use x_one::xlog::LogTimezone;
+use x_one::xlog::config::{LogOutput, LogSinkConfig, XLogConfig};
+use x_one::xlog::output::{LogOutputs, build_outputs};
+
+fn build(c: &XLogConfig) -> Result<LogOutputs<Registry>, x_one::error::XOneError> {
+    build_outputs(c, LogTimezone::Utc)
+}
+
+fn config(path: &std::path::Path, output: LogOutput) -> XLogConfig {
+    XLogConfig {
+        path: path.display().to_string(),
+        output,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_output_config_parse() {
+    assert_eq!(XLogConfig::default().output, LogOutput::File);
+    for (text, output) in [
+        ("file", LogOutput::File),
+        ("stdout", LogOutput::Stdout),
+        ("stderr", LogOutput::Stderr),
+        ("both", LogOutput::Both),
+    ] {
+        let c: XLogConfig = serde_yaml::from_str(&format!("Output: {text}\n")).unwrap();
+        assert_eq!(c.output, output);
+    }
+    assert!(serde_yaml::from_str::<XLogConfig>("Output: syslog\n").is_err());
+
+    assert!(LogOutput::File.writes_file());
+    assert!(LogOutput::Both.writes_file());
+    assert!(!LogOutput::Stdout.writes_file());
+    assert!(!LogOutput::Stderr.writes_file());
+}
+
+#[test]
+fn test_stdout_and_stderr_output_do_not_create_log_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("log");
+
+    for output in [LogOutput::Stdout, LogOutput::Stderr] {
+        let mut c = config(&path, output);
+        c.error_file = true;
+        let outputs = build(&c).unwrap();
+        assert_eq!(outputs.output, output);
+        assert_eq!(outputs.layers.len(), 1);
+        assert!(outputs.filenames.is_empty());
+        assert!(outputs.fallback.is_none());
+        assert!(!path.exists(), "{output:?} 不应创建日志目录");
+    }
+}
+
+#[test]
+fn test_file_and_both_output_create_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("log");
+
+    let mut c = config(&path, LogOutput::File);
+    c.error_file = true;
+    let outputs = build(&c).unwrap();
+    assert_eq!(outputs.output, LogOutput::File);
+    assert_eq!(outputs.filenames, ["app.log", "app.error.log"]);
+    assert_eq!(outputs.layers.len(), 2);
+    assert!(path.is_dir());
+    drop(outputs);
+
+    let outputs = build(&config(&path, LogOutput::Both)).unwrap();
+    assert_eq!(outputs.output, LogOutput::Both);
+    assert_eq!(outputs.filenames, ["app.log"]);
+    // 主日志文件 + stdout
+    assert_eq!(outputs.layers.len(), 2);
+}
+
+#[test]
+fn test_sink_level_is_independent_of_main_level() {
+    use tracing_subscriber::prelude::*;
+
+    let dir = tempfile::tempdir().unwrap();
+    let mut c = config(dir.path(), LogOutput::File);
+    c.rotate_time = std::time::Duration::ZERO;
+    c.sinks = vec![LogSinkConfig {
+        name: "debug".to_string(),
+        level: x_one::xlog::LogLevel::Debug,
+        ..Default::default()
+    }];
+    let outputs = build(&c).unwrap();
+
+    let subscriber = tracing_subscriber::registry().with(outputs.layers);
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::debug!("verbose detail");
+        tracing::info!("normal entry");
+    });
+    drop(outputs.guards);
+
+    let read = |name: &str| std::fs::read_to_string(dir.path().join(name)).unwrap();
+    let main = read("app.log");
+    assert!(main.contains("normal entry"), "{main}");
+    assert!(!main.contains("verbose detail"), "{main}");
+    let debug = read("debug.log");
+    assert!(debug.contains("verbose detail"), "{debug}");
+    assert!(debug.contains("normal entry"), "{debug}");
+}
+
+#[test]
+fn test_unwritable_log_dir_falls_back_to_stdout() {
+    // 以普通文件作为父目录，目录必然无法创建（root 用户同样失败）
+    let dir = tempfile::tempdir().unwrap();
+    let blocker = dir.path().join("readonly");
+    std::fs::write(&blocker, b"").unwrap();
+    let path = blocker.join("log");
+
+    for output in [LogOutput::File, LogOutput::Both] {
+        let outputs = build(&config(&path, output)).unwrap();
+        assert_eq!(outputs.output, LogOutput::Stdout);
+        assert_eq!(outputs.layers.len(), 1);
+        assert!(outputs.filenames.is_empty());
+        let reason = outputs.fallback.expect("应记录回退原因");
+        assert!(reason.contains("create log dir failed"), "{reason}");
+    }
+}
+
+#[test]
+fn test_invalid_config_is_not_swallowed_by_fallback() {
+    let dir = tempfile::tempdir().unwrap();
+    let blocker = dir.path().join("readonly");
+    std::fs::write(&blocker, b"").unwrap();
+
+    let mut c = config(&blocker.join("log"), LogOutput::File);
+    c.sinks = vec![LogSinkConfig {
+        name: "app".to_string(),
+        ..Default::default()
+    }];
+    let err = build(&c).err().expect("sink 重名应返回错误");
+    assert!(err.to_string().contains("XLog.Sinks name"), "{err}");
+}
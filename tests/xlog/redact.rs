@@ -0,0 +1,172 @@
+use serde_json::json;
+use serial_test::serial;
+use x_one::xlog::config::{MaskMode, RedactConfig, RedactValueRule};
+use x_one::xlog::otel_fmt::{OtelConsoleFormat, OtelJsonFormat};
+use x_one::xlog::redact::*;
+
+fn card_policy() -> RedactPolicy {
+    RedactPolicy::from_config(&RedactConfig {
+        keys: vec!["password".to_string(), "*token*".to_string()],
+        mask: MaskMode::Partial,
+        values: vec![RedactValueRule {
+            pattern: r"\b\d{16}\b".to_string(),
+            mask: MaskMode::Partial,
+        }],
+        ..Default::default()
+    })
+    .unwrap()
+}
+
+/// 使用全局策略格式化一条日志，返回输出（console 为 false 时输出 JSON）
+fn capture(console: bool, log: impl FnOnce()) -> String {
+    use tracing_subscriber::prelude::*;
+
+    let buf = std::sync::Arc::new(parking_lot::Mutex::new(Vec::new()));
+    let buf_clone = buf.clone();
+    let writer =
+        move || -> Box<dyn std::io::Write + Send> { Box::new(SharedWriter(buf_clone.clone())) };
+    let registry = tracing_subscriber::registry().with(x_one::xlog::kv_layer::KvLayer);
+    if console {
+        let layer = tracing_subscriber::fmt::layer()
+            .event_format(OtelConsoleFormat::default())
+            .with_writer(writer);
+        tracing::subscriber::with_default(registry.with(layer), log);
+    } else {
+        let layer = tracing_subscriber::fmt::layer()
+            .event_format(OtelJsonFormat::default())
+            .with_writer(writer);
+        tracing::subscriber::with_default(registry.with(layer), log);
+    }
+    String::from_utf8(buf.lock().clone()).unwrap()
+}
+
+#[test]
+fn test_sensitive_key_patterns() {
+    let policy = RedactPolicy::default();
+    assert!(policy.is_sensitive_key("password"));
+    assert!(policy.is_sensitive_key("Access_Token"));
+    assert!(policy.is_sensitive_key("user.phone"));
+    assert!(policy.is_sensitive_key("x_api_key"));
+    assert!(!policy.is_sensitive_key("x-request-id"));
+    assert!(!policy.is_sensitive_key("user_id"));
+    assert!(!policy.is_sensitive_key("phone_brand"));
+
+    let disabled = RedactPolicy::from_config(&RedactConfig {
+        enable: false,
+        ..Default::default()
+    })
+    .unwrap();
+    assert!(!disabled.is_sensitive_key("password"));
+}
+
+#[test]
+fn test_mask_value_modes() {
+    assert_eq!(mask_value("13800138000", MaskMode::Partial), "138****8000");
+    assert_eq!(
+        mask_value("6222020200112233", MaskMode::Partial),
+        "622****2233"
+    );
+    assert_eq!(mask_value("abc", MaskMode::Partial), REDACTED);
+    assert_eq!(mask_value("anything", MaskMode::Full), REDACTED);
+}
+
+#[test]
+fn test_redact_fields_by_key_and_value_pattern() {
+    let policy = card_policy();
+    let mut value = json!({
+        "user_id": 42,
+        "password": "p@ssw0rd-123",
+        "refresh_token": 1234567890,
+        "message": "pay with card 6222020200112233 done",
+        "nested": {"items": [{"password": "secret-value"}]}
+    });
+
+    policy.redact_value(&mut value);
+
+    assert_eq!(value["user_id"], 42);
+    assert_eq!(value["password"], "p@s****-123");
+    assert_eq!(value["refresh_token"], "123****7890");
+    assert_eq!(value["message"], "pay with card 622****2233 done");
+    assert_eq!(value["nested"]["items"][0]["password"], "sec****alue");
+}
+
+#[test]
+fn test_redact_body_json_and_text() {
+    let policy = card_policy();
+
+    let body = policy.redact_body(r#"{"name":"a","password":"p@ssw0rd-123"}"#);
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["name"], "a");
+    assert_eq!(json["password"], "p@s****-123");
+
+    let untouched = r#"{"name": "a"}"#;
+    assert!(matches!(
+        policy.redact_body(untouched),
+        std::borrow::Cow::Borrowed(_)
+    ));
+
+    assert_eq!(
+        policy.redact_body("card=6222020200112233&name=a"),
+        "card=622****2233&name=a"
+    );
+}
+
+#[test]
+fn test_invalid_value_pattern_returns_error() {
+    let err = RedactPolicy::from_config(&RedactConfig {
+        values: vec![RedactValueRule {
+            pattern: "(".to_string(),
+            mask: MaskMode::Full,
+        }],
+        ..Default::default()
+    })
+    .unwrap_err();
+    assert!(
+        err.to_string().contains("Redact.Values[0].Pattern"),
+        "{err}"
+    );
+}
+
+#[test]
+#[serial]
+fn test_json_format_redacts_event_and_kv_fields() {
+    set_policy(card_policy());
+
+    let output = capture(false, || {
+        let _guard = tracing::info_span!("", session_token = "tok-abcdefgh").entered();
+        tracing::info!(password = "p@ssw0rd-123", "card 6222020200112233");
+    });
+    set_policy(RedactPolicy::default());
+
+    let json: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+    assert_eq!(json["fields"]["password"], "p@s****-123");
+    assert_eq!(json["fields"]["session_token"], "tok****efgh");
+    assert_eq!(json["fields"]["message"], "card 622****2233");
+}
+
+#[test]
+#[serial]
+fn test_console_format_redacts_fields() {
+    set_policy(RedactPolicy::default());
+
+    let output = capture(true, || {
+        tracing::info!(api_key = "k-123456", user = "alice", "login");
+    });
+
+    assert!(output.contains("api_key=***"), "{output}");
+    assert!(output.contains("user=alice"), "{output}");
+    assert!(!output.contains("k-123456"), "{output}");
+}
+
+struct SharedWriter(std::sync::Arc<parking_lot::Mutex<Vec<u8>>>);
+
+impl std::io::Write for SharedWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
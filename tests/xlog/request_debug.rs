@@ -0,0 +1,240 @@
+use serial_test::serial;
+use std::time::Duration;
+use tracing_subscriber::EnvFilter;
+use x_one::xlog::config::{RequestDebugConfig, XLogConfig};
+use x_one::xlog::kv_layer::KvLayer;
+use x_one::xlog::otel_fmt::OtelJsonFormat;
+use x_one::xlog::request_debug::*;
+
+/// 在全局级别为 info、带按请求 DEBUG Layer 的 subscriber 下执行，返回输出的 JSON 行
+fn capture(enable: bool, log: impl FnOnce()) -> Vec<serde_json::Value> {
+    use tracing_subscriber::prelude::*;
+
+    let buf = std::sync::Arc::new(parking_lot::Mutex::new(Vec::new()));
+    let buf_clone = buf.clone();
+    let writer =
+        move || -> Box<dyn std::io::Write + Send> { Box::new(SharedWriter(buf_clone.clone())) };
+    let subscriber = tracing_subscriber::registry()
+        .with(RequestDebugLayer::new(EnvFilter::new("info"), enable))
+        .with(KvLayer)
+        .with(
+            tracing_subscriber::fmt::layer()
+                .event_format(OtelJsonFormat::default())
+                .with_writer(writer),
+        );
+    tracing::subscriber::with_default(subscriber, log);
+    let output = String::from_utf8(buf.lock().clone()).unwrap();
+    output
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect()
+}
+
+fn messages(lines: &[serde_json::Value]) -> Vec<&str> {
+    lines
+        .iter()
+        .map(|l| l["fields"]["message"].as_str().unwrap())
+        .collect()
+}
+
+fn enabled_config(secret: &str) -> RequestDebugConfig {
+    RequestDebugConfig {
+        enable: true,
+        secret: secret.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_config_defaults_and_parse() {
+    let c = XLogConfig::default();
+    assert!(!c.request_debug.enable);
+    assert_eq!(c.request_debug.header, "X-Debug-Log");
+    assert_eq!(c.request_debug.max_ttl, Duration::from_secs(3600));
+
+    let c: XLogConfig = serde_yaml::from_str(
+        "RequestDebug:\n  Enable: true\n  Secret: abc\n  Header: X-Verbose\n  MaxTTL: 10m\n",
+    )
+    .unwrap();
+    assert!(c.request_debug.enable);
+    assert_eq!(c.request_debug.secret, "abc");
+    assert_eq!(c.request_debug.header, "X-Verbose");
+    assert_eq!(c.request_debug.max_ttl, Duration::from_secs(600));
+}
+
+#[test]
+fn test_config_invalid_values_return_error() {
+    assert!(
+        RequestDebugPolicy::from_config(&RequestDebugConfig::default())
+            .unwrap()
+            .is_none()
+    );
+
+    let err = RequestDebugPolicy::from_config(&enabled_config("")).unwrap_err();
+    assert!(err.to_string().contains("XLog.RequestDebug"), "{err}");
+
+    let mut c = enabled_config("abc");
+    c.header = "X Debug".to_string();
+    let err = RequestDebugPolicy::from_config(&c).unwrap_err();
+    assert!(
+        err.to_string().contains("XLog.RequestDebug.Header"),
+        "{err}"
+    );
+
+    let mut c = enabled_config("abc");
+    c.max_ttl = Duration::ZERO;
+    let err = RequestDebugPolicy::from_config(&c).unwrap_err();
+    assert!(
+        err.to_string().contains("XLog.RequestDebug.MaxTTL"),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_verify_token() {
+    let policy = RequestDebugPolicy::from_config(&enabled_config("s3cret"))
+        .unwrap()
+        .unwrap();
+
+    assert!(policy.verify(&sign_token("s3cret", Duration::from_secs(60))));
+    assert!(!policy.verify(&sign_token("other", Duration::from_secs(60))));
+    // 有效期超过 MaxTTL
+    assert!(!policy.verify(&sign_token("s3cret", Duration::from_secs(7200))));
+    // 已过期（签名正确）
+    let expires = (std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        - 10)
+        .to_string();
+    let key = ring::hmac::Key::new(ring::hmac::HMAC_SHA256, b"s3cret");
+    let signature = ring::hmac::sign(&key, expires.as_bytes());
+    let expired = format!(
+        "{expires}.{}",
+        base64::Engine::encode(
+            &base64::engine::general_purpose::URL_SAFE_NO_PAD,
+            signature.as_ref()
+        )
+    );
+    assert!(!policy.verify(&expired));
+    for token in ["", "abc", "123", "123.!!!", "x.y"] {
+        assert!(!policy.verify(token), "{token}");
+    }
+}
+
+#[test]
+fn test_marked_span_enables_debug() {
+    let lines = capture(true, || {
+        tracing::debug!("before");
+        {
+            let _span =
+                tracing::info_span!("request", { REQUEST_DEBUG_FIELD } = "token-1").entered();
+            tracing::debug!("in request");
+            tracing::trace!("trace in request");
+            let _child = tracing::debug_span!("child", step = 1).entered();
+            tracing::debug!("in child");
+        }
+        tracing::debug!("after");
+        tracing::info!("info");
+    });
+    assert_eq!(messages(&lines), ["in request", "in child", "info"]);
+    // token 不写入日志
+    assert_eq!(lines[0]["fields"][REQUEST_DEBUG_FIELD], true);
+    assert_eq!(lines[1]["fields"]["step"], 1);
+}
+
+#[test]
+fn test_marked_span_enables_debug_as_per_layer_filter() {
+    use tracing_subscriber::prelude::*;
+
+    let buf = std::sync::Arc::new(parking_lot::Mutex::new(Vec::new()));
+    let buf_clone = buf.clone();
+    let writer =
+        move || -> Box<dyn std::io::Write + Send> { Box::new(SharedWriter(buf_clone.clone())) };
+    let subscriber = tracing_subscriber::registry().with(KvLayer).with(
+        tracing_subscriber::fmt::layer()
+            .event_format(OtelJsonFormat::default())
+            .with_writer(writer)
+            .with_filter(RequestDebugLayer::new(EnvFilter::new("info"), true)),
+    );
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::debug!("before");
+        let _span = tracing::info_span!("request", { REQUEST_DEBUG_FIELD } = "token-4").entered();
+        assert_eq!(current_token().as_deref(), Some("token-4"));
+        tracing::debug!("in request");
+        tracing::trace!("trace in request");
+    });
+
+    let output = String::from_utf8(buf.lock().clone()).unwrap();
+    let lines: Vec<serde_json::Value> = output
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(messages(&lines), ["in request"]);
+}
+
+#[test]
+fn test_marker_span_created_when_level_disabled() {
+    use tracing_subscriber::prelude::*;
+
+    let subscriber = tracing_subscriber::registry()
+        .with(RequestDebugLayer::new(EnvFilter::new("error"), true))
+        .with(KvLayer);
+    tracing::subscriber::with_default(subscriber, || {
+        let span = tracing::info_span!("request", { REQUEST_DEBUG_FIELD } = "token-2");
+        assert!(!span.is_disabled());
+        let _guard = span.entered();
+        assert!(is_active());
+        assert_eq!(current_token().as_deref(), Some("token-2"));
+        assert!(tracing::enabled!(tracing::Level::DEBUG));
+        assert!(!tracing::enabled!(tracing::Level::TRACE));
+    });
+}
+
+#[test]
+fn test_disabled_layer_passes_through() {
+    let lines = capture(false, || {
+        let _span = tracing::info_span!("request", { REQUEST_DEBUG_FIELD } = "token-3").entered();
+        assert!(!is_active());
+        tracing::debug!("in request");
+        tracing::info!("info");
+    });
+    assert_eq!(messages(&lines), ["info"]);
+}
+
+#[test]
+fn test_current_token_outside_request() {
+    assert!(current_token().is_none());
+    capture(true, || {
+        assert!(current_token().is_none());
+        let _span = tracing::info_span!("job", { REQUEST_DEBUG_FIELD } = true).entered();
+        // bool 字段只开启本地 DEBUG，不向下游传递
+        assert!(is_active());
+        assert!(current_token().is_none());
+    });
+}
+
+#[test]
+#[serial]
+fn test_global_policy() {
+    set_policy(None);
+    assert!(policy().is_none());
+
+    set_policy(RequestDebugPolicy::from_config(&enabled_config("abc")).unwrap());
+    assert_eq!(policy().unwrap().header(), "X-Debug-Log");
+
+    set_policy(None);
+}
+
+struct SharedWriter(std::sync::Arc<parking_lot::Mutex<Vec<u8>>>);
+
+impl std::io::Write for SharedWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
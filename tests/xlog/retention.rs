@@ -0,0 +1,176 @@
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use x_one::xlog::config::XLogConfig;
+use x_one::xlog::retention::*;
+
+const DAY: Duration = Duration::from_secs(24 * 3600);
+
+/// 创建文件并将修改时间设为 `now - age`
+fn touch(dir: &Path, name: &str, size: usize, now: SystemTime, age: Duration) {
+    let path = dir.join(name);
+    std::fs::write(&path, vec![b'x'; size]).unwrap();
+    let f = std::fs::File::options().write(true).open(&path).unwrap();
+    f.set_modified(now - age).unwrap();
+}
+
+fn remaining(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_cleanup_removes_files_older_than_max_age() {
+    let dir = tempfile::tempdir().unwrap();
+    let now = SystemTime::now();
+    touch(dir.path(), "app.log.2026-10-17", 10, now, Duration::ZERO);
+    touch(
+        dir.path(),
+        "app.log.2026-10-10",
+        10,
+        now,
+        DAY * 7 - Duration::from_secs(60),
+    );
+    touch(dir.path(), "app.log.2026-10-01", 10, now, DAY * 16);
+    touch(dir.path(), "other.log.2026-10-01", 10, now, DAY * 16);
+
+    let policy = RetentionPolicy {
+        max_age: DAY * 7,
+        ..Default::default()
+    };
+    let removed = cleanup_rotated_files(dir.path(), "app.log", &policy, now).unwrap();
+
+    assert_eq!(removed.len(), 1);
+    assert_eq!(
+        remaining(dir.path()),
+        vec![
+            "app.log.2026-10-10",
+            "app.log.2026-10-17",
+            "other.log.2026-10-01"
+        ]
+    );
+}
+
+#[test]
+fn test_cleanup_keeps_at_most_max_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let now = SystemTime::now();
+    for (i, day) in ["17", "16", "15", "14"].iter().enumerate() {
+        touch(
+            dir.path(),
+            &format!("app.log.2026-10-{day}"),
+            10,
+            now,
+            DAY * i as u32,
+        );
+    }
+
+    let policy = RetentionPolicy {
+        max_files: 2,
+        ..Default::default()
+    };
+    cleanup_rotated_files(dir.path(), "app.log", &policy, now).unwrap();
+
+    assert_eq!(
+        remaining(dir.path()),
+        vec!["app.log.2026-10-16", "app.log.2026-10-17"]
+    );
+}
+
+#[test]
+fn test_cleanup_enforces_max_total_size_from_oldest() {
+    let dir = tempfile::tempdir().unwrap();
+    let now = SystemTime::now();
+    touch(dir.path(), "app.log.2026-10-17", 40, now, Duration::ZERO);
+    touch(dir.path(), "app.log.2026-10-16", 40, now, DAY);
+    touch(dir.path(), "app.log.2026-10-15", 40, now, DAY * 2);
+
+    let policy = RetentionPolicy {
+        max_total_size: 100,
+        ..Default::default()
+    };
+    cleanup_rotated_files(dir.path(), "app.log", &policy, now).unwrap();
+
+    assert_eq!(
+        remaining(dir.path()),
+        vec!["app.log.2026-10-16", "app.log.2026-10-17"]
+    );
+}
+
+#[test]
+fn test_cleanup_always_keeps_newest_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let now = SystemTime::now();
+    touch(dir.path(), "app.log.2026-09-01", 1000, now, DAY * 46);
+
+    let policy = RetentionPolicy {
+        max_age: DAY,
+        max_files: 1,
+        max_total_size: 10,
+    };
+    let removed = cleanup_rotated_files(dir.path(), "app.log", &policy, now).unwrap();
+
+    assert!(removed.is_empty());
+    assert_eq!(remaining(dir.path()), vec!["app.log.2026-09-01"]);
+}
+
+#[test]
+fn test_cleanup_orders_by_file_name_not_mtime() {
+    // 压缩会刷新修改时间：旧周期的 .gz 比当前写入的文件更"新"
+    let dir = tempfile::tempdir().unwrap();
+    let now = SystemTime::now();
+    touch(dir.path(), "app.log.2026-10-17", 10, now, DAY);
+    touch(dir.path(), "app.log.2026-10-16.gz", 10, now, Duration::ZERO);
+    touch(dir.path(), "app.log.2026-10-15.gz", 10, now, Duration::ZERO);
+    touch(dir.path(), "app.log.log", 10, now, Duration::ZERO);
+
+    let policy = RetentionPolicy {
+        max_files: 1,
+        ..Default::default()
+    };
+    cleanup_rotated_files(dir.path(), "app.log", &policy, now).unwrap();
+
+    // 其他 sink 的文件不参与清理
+    assert_eq!(
+        remaining(dir.path()),
+        vec!["app.log.2026-10-17", "app.log.log"]
+    );
+
+    // 仅按大小切割：序号按数值比较
+    let dir = tempfile::tempdir().unwrap();
+    touch(dir.path(), "app.log.gz", 10, now, Duration::ZERO);
+    touch(dir.path(), "app.log.9.gz", 10, now, Duration::ZERO);
+    touch(dir.path(), "app.log.10", 10, now, DAY);
+
+    cleanup_rotated_files(dir.path(), "app.log", &policy, now).unwrap();
+
+    assert_eq!(remaining(dir.path()), vec!["app.log.10"]);
+}
+
+#[test]
+fn test_cleanup_missing_dir_returns_error() {
+    let policy = RetentionPolicy::default();
+    assert!(
+        cleanup_rotated_files(
+            Path::new("/not/exist/log"),
+            "app.log",
+            &policy,
+            SystemTime::now()
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_policy_from_config() {
+    let c = XLogConfig {
+        max_age: Duration::ZERO,
+        ..Default::default()
+    };
+    let policy = RetentionPolicy::from_config(&c);
+    assert!(!policy.is_enabled());
+    assert!(RetentionPolicy::from_config(&XLogConfig::default()).is_enabled());
+}
@@ -0,0 +1,219 @@
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use x_one::xlog::LogTimezone;
+use x_one::xlog::config::XLogConfig;
+use x_one::xlog::rolling::*;
+
+fn at(secs: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+fn names(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_rotated_file_name_by_period() {
+    // 2026-10-17 09:15:30 UTC
+    let t = at(1_792_228_530);
+    let day = Duration::from_secs(86400);
+
+    assert_eq!(
+        rotated_file_name("app.log", day, LogTimezone::Utc, t, 0),
+        "app.log.2026-10-17"
+    );
+    assert_eq!(
+        rotated_file_name("app.log", Duration::from_secs(3600), LogTimezone::Utc, t, 2),
+        "app.log.2026-10-17-09.2"
+    );
+    assert_eq!(
+        rotated_file_name(
+            "app.log",
+            Duration::from_secs(6 * 3600),
+            LogTimezone::Utc,
+            t,
+            0
+        ),
+        "app.log.2026-10-17-06"
+    );
+    assert_eq!(
+        rotated_file_name("app.log", Duration::from_secs(900), LogTimezone::Utc, t, 0),
+        "app.log.2026-10-17-09-15"
+    );
+    assert_eq!(
+        rotated_file_name("app.log", Duration::from_secs(10), LogTimezone::Utc, t, 0),
+        "app.log.2026-10-17-09-15-30"
+    );
+    assert_eq!(
+        rotated_file_name("app.log", Duration::ZERO, LogTimezone::Utc, t, 0),
+        "app.log"
+    );
+    assert_eq!(
+        rotated_file_name("app.log", Duration::ZERO, LogTimezone::Utc, t, 3),
+        "app.log.3"
+    );
+}
+
+#[test]
+fn test_rotated_file_name_uses_timezone() {
+    // 2026-10-17 20:15:30 UTC = 2026-10-18 04:15:30 +08:00
+    let t = at(1_792_268_130);
+    let tz = LogTimezone::parse("Asia/Shanghai").unwrap();
+
+    assert_eq!(
+        rotated_file_name("app.log", Duration::from_secs(86400), tz, t, 0),
+        "app.log.2026-10-18"
+    );
+    assert_eq!(
+        rotated_file_name("app.log", Duration::from_secs(3600), tz, t, 0),
+        "app.log.2026-10-18-04"
+    );
+    assert_eq!(
+        rotated_file_name(
+            "app.log",
+            Duration::from_secs(86400),
+            LogTimezone::Utc,
+            t,
+            0
+        ),
+        "app.log.2026-10-17"
+    );
+}
+
+#[test]
+fn test_policy_from_config() {
+    let c: XLogConfig =
+        serde_yaml::from_str("RotateTime: 1h\nRotateSize: 512MiB\nCompress: true\n").unwrap();
+    let policy = RotationPolicy::from_config(&c).unwrap();
+    assert_eq!(policy.period, Duration::from_secs(3600));
+    assert_eq!(policy.max_size, 512 << 20);
+    assert!(policy.compress);
+
+    assert!(matches!(policy.timezone, LogTimezone::Named(_)));
+
+    let c: XLogConfig = serde_yaml::from_str("RotateTime: 500ms\n").unwrap();
+    assert!(RotationPolicy::from_config(&c).is_err());
+    let c: XLogConfig = serde_yaml::from_str("Timezone: Mars/Olympus\n").unwrap();
+    assert!(RotationPolicy::from_config(&c).is_err());
+}
+
+#[test]
+fn test_size_rotation_writes_numbered_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let policy = RotationPolicy {
+        max_size: 10,
+        ..Default::default()
+    };
+    let mut w = RollingFileWriter::new(dir.path().to_str().unwrap(), "app.log", policy).unwrap();
+
+    w.write_all(b"0123456789").unwrap();
+    w.write_all(b"abcdef").unwrap();
+    w.write_all(b"ghijkl").unwrap();
+    w.flush().unwrap();
+
+    assert_eq!(names(dir.path()), vec!["app.log", "app.log.1", "app.log.2"]);
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("app.log.1")).unwrap(),
+        "abcdef"
+    );
+}
+
+#[test]
+fn test_new_writer_continues_last_file_of_period() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("app.log"), "full-file!").unwrap();
+    std::fs::write(dir.path().join("app.log.1.gz"), "").unwrap();
+    let policy = RotationPolicy {
+        max_size: 10,
+        ..Default::default()
+    };
+
+    let w = RollingFileWriter::new(dir.path().to_str().unwrap(), "app.log", policy).unwrap();
+
+    assert_eq!(w.current_path(), dir.path().join("app.log.2"));
+}
+
+#[test]
+fn test_time_rotation_compresses_previous_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let policy = RotationPolicy {
+        period: Duration::from_secs(1),
+        compress: true,
+        ..Default::default()
+    };
+    let mut w = RollingFileWriter::new(dir.path().to_str().unwrap(), "app.log", policy).unwrap();
+    w.write_all(b"first period\n").unwrap();
+    let first = w.current_path().to_path_buf();
+
+    std::thread::sleep(Duration::from_millis(1100));
+    w.write_all(b"second period\n").unwrap();
+    let second = w.current_path().to_path_buf();
+    // drop 时等待后台压缩完成
+    drop(w);
+
+    assert_ne!(first, second);
+    assert!(!first.exists());
+    let gz = std::fs::File::open(format!("{}.gz", first.display())).unwrap();
+    let mut content = String::new();
+    flate2::read::GzDecoder::new(gz)
+        .read_to_string(&mut content)
+        .unwrap();
+    assert_eq!(content, "first period\n");
+    assert_eq!(std::fs::read_to_string(second).unwrap(), "second period\n");
+}
+
+#[test]
+fn test_size_rotation_never_compresses_active_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let policy = RotationPolicy {
+        max_size: 10,
+        compress: true,
+        ..Default::default()
+    };
+    let mut w = RollingFileWriter::new(dir.path().to_str().unwrap(), "app.log", policy).unwrap();
+    w.write_all(b"0123456789").unwrap();
+    w.write_all(b"abcdef").unwrap();
+    w.write_all(b"ghijkl").unwrap();
+    drop(w);
+    assert_eq!(
+        names(dir.path()),
+        vec!["app.log.1.gz", "app.log.2", "app.log.gz"]
+    );
+
+    // 重启续写未写满的文件：当前文件、同目录其他写入器的文件均不压缩
+    std::fs::write(dir.path().join("app.log.log"), "other sink").unwrap();
+    let w = RollingFileWriter::new(dir.path().to_str().unwrap(), "app.log", policy).unwrap();
+    assert_eq!(w.current_path(), dir.path().join("app.log.2"));
+    drop(w);
+    assert_eq!(
+        names(dir.path()),
+        vec!["app.log.1.gz", "app.log.2", "app.log.gz", "app.log.log"]
+    );
+
+    // 未压缩的首个文件仍在写入时不压缩
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("app.log"), "active").unwrap();
+    let w = RollingFileWriter::new(dir.path().to_str().unwrap(), "app.log", policy).unwrap();
+    assert_eq!(w.current_path(), dir.path().join("app.log"));
+    drop(w);
+    assert_eq!(names(dir.path()), vec!["app.log"]);
+}
+
+#[test]
+fn test_compress_file_replaces_original() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.log.2026-10-16");
+    std::fs::write(&path, "hello").unwrap();
+
+    let target = compress_file(&path).unwrap();
+
+    assert!(!path.exists());
+    assert!(target.ends_with("app.log.2026-10-16.gz"));
+    assert_eq!(names(dir.path()), vec!["app.log.2026-10-16.gz"]);
+}
@@ -0,0 +1,97 @@
+use x_one::xlog::LogTimezone;
+use x_one::xlog::config::{LogFormat, LogLevel, LogSinkConfig, XLogConfig};
+use x_one::xlog::sink::build_file_sinks;
+
+#[test]
+fn test_file_sinks_error_file_and_custom() {
+    let c: XLogConfig = serde_yaml::from_str(
+        "Name: app\nErrorFile: true\nSinks:\n  - Name: audit\n    Level: debug\n    Targets:\n      sqlx: error\n    Format: text\n",
+    )
+    .unwrap();
+
+    let sinks = c.file_sinks().unwrap();
+
+    assert_eq!(sinks.len(), 2);
+    assert_eq!(sinks[0].name, "app.error");
+    assert_eq!(sinks[0].level, LogLevel::Warn);
+    assert_eq!(sinks[0].format, LogFormat::Json);
+    assert_eq!(sinks[1].name, "audit");
+    assert_eq!(sinks[1].format, LogFormat::Text);
+    assert_eq!(sinks[1].filter_directives().unwrap(), "debug,sqlx=error");
+
+    assert!(XLogConfig::default().file_sinks().unwrap().is_empty());
+}
+
+#[test]
+fn test_file_sinks_invalid_names_return_error() {
+    for name in ["", "app", "logs/audit", "audit"] {
+        let c = XLogConfig {
+            sinks: vec![
+                LogSinkConfig {
+                    name: "audit".to_string(),
+                    ..Default::default()
+                },
+                LogSinkConfig {
+                    name: name.to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let err = c.file_sinks().unwrap_err();
+        assert!(err.to_string().contains("XLog.Sinks name"), "{err}");
+    }
+}
+
+#[test]
+fn test_file_sinks_route_events_by_level_and_format() {
+    use tracing_subscriber::prelude::*;
+
+    let dir = tempfile::tempdir().unwrap();
+    let c: XLogConfig = serde_yaml::from_str(&format!(
+        "Path: \"{}\"\nName: app\nRotateTime: 0s\nErrorFile: true\nSinks:\n  - Name: audit\n    Level: error\n    Targets:\n      sink_audit: info\n    Format: text\n",
+        dir.path().display()
+    ))
+    .unwrap();
+
+    let sinks = build_file_sinks(&c, LogTimezone::Utc).unwrap();
+    assert_eq!(sinks.filenames, ["app.error.log", "audit.log"]);
+
+    let subscriber = tracing_subscriber::registry().with(sinks.layers);
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!(target: "sink_app", "plain info");
+        tracing::warn!(target: "sink_app", "something odd");
+        tracing::info!(target: "sink_audit", "audit entry");
+    });
+    drop(sinks.guards);
+
+    let read = |name: &str| std::fs::read_to_string(dir.path().join(name)).unwrap();
+    let error = read("app.error.log");
+    assert!(!error.contains("plain info"), "{error}");
+    assert!(!error.contains("audit entry"), "{error}");
+    let line: serde_json::Value = serde_json::from_str(error.trim()).unwrap();
+    assert_eq!(line["fields"]["message"], "something odd");
+
+    let audit = read("audit.log");
+    assert!(audit.starts_with("INFO["), "{audit}");
+    assert!(audit.contains("audit entry"), "{audit}");
+    assert!(!audit.contains("something odd"), "{audit}");
+    assert!(!audit.contains('\x1b'), "{audit}");
+}
+
+#[test]
+fn test_file_sinks_invalid_config_creates_no_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let c = XLogConfig {
+        path: dir.path().display().to_string(),
+        error_file: true,
+        sinks: vec![LogSinkConfig {
+            name: "app.error".to_string(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    assert!(build_file_sinks::<tracing_subscriber::Registry>(&c, LogTimezone::Utc).is_err());
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+}
@@ -0,0 +1,194 @@
+use serial_test::serial;
+use std::time::Duration;
+use x_one::xlog::config::XLogConfig;
+use x_one::xlog::otel_fmt::OtelJsonFormat;
+use x_one::xlog::throttle::*;
+
+/// 在带限流 Layer 的 subscriber 下执行，返回输出的 JSON 行
+fn capture(layer: ThrottleLayer, log: impl FnOnce()) -> Vec<serde_json::Value> {
+    use tracing_subscriber::prelude::*;
+
+    let buf = std::sync::Arc::new(parking_lot::Mutex::new(Vec::new()));
+    let buf_clone = buf.clone();
+    let writer =
+        move || -> Box<dyn std::io::Write + Send> { Box::new(SharedWriter(buf_clone.clone())) };
+    let subscriber = tracing_subscriber::registry().with(layer).with(
+        tracing_subscriber::fmt::layer()
+            .event_format(OtelJsonFormat::default())
+            .with_writer(writer),
+    );
+    tracing::subscriber::with_default(subscriber, log);
+    let output = String::from_utf8(buf.lock().clone()).unwrap();
+    output
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect()
+}
+
+fn rate_limit_policy(max_events: u64) -> ThrottlePolicy {
+    ThrottlePolicy {
+        rate_limit: Some((max_events, Duration::from_secs(3600))),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_policy_from_config() {
+    let c: XLogConfig = serde_yaml::from_str(
+        "RateLimit:\n  Enable: true\n  MaxEvents: 10\n  Interval: 5s\nSampling:\n  Debug: 0.1\n",
+    )
+    .unwrap();
+    let policy = ThrottlePolicy::from_config(&c).unwrap();
+    assert_eq!(policy.rate_limit, Some((10, Duration::from_secs(5))));
+    assert_eq!(policy.sample_rates, [1.0, 0.1, 1.0]);
+    assert!(policy.is_enabled());
+
+    let policy = ThrottlePolicy::from_config(&XLogConfig::default()).unwrap();
+    assert!(!policy.is_enabled());
+}
+
+#[test]
+fn test_policy_invalid_values_return_error() {
+    let c: XLogConfig = serde_yaml::from_str("Sampling:\n  Info: 1.5\n").unwrap();
+    let err = ThrottlePolicy::from_config(&c).unwrap_err();
+    assert!(err.to_string().contains("XLog.Sampling.Info"), "{err}");
+
+    let c: XLogConfig =
+        serde_yaml::from_str("RateLimit:\n  Enable: true\n  MaxEvents: 0\n").unwrap();
+    let err = ThrottlePolicy::from_config(&c).unwrap_err();
+    assert!(err.to_string().contains("XLog.RateLimit"), "{err}");
+}
+
+#[test]
+fn test_rate_limit_per_callsite_with_summary() {
+    let layer = ThrottleLayer::new(rate_limit_policy(3));
+    let flush_layer = layer.clone();
+
+    let lines = capture(layer, || {
+        for i in 0..10 {
+            tracing::error!(i, "dependency down");
+        }
+        tracing::error!("other callsite");
+        assert_eq!(flush_layer.flush_summaries(), 1);
+        // 已汇总的数量不重复输出
+        assert_eq!(flush_layer.flush_summaries(), 0);
+    });
+
+    let messages: Vec<&str> = lines
+        .iter()
+        .map(|l| l["fields"]["message"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "dependency down",
+            "dependency down",
+            "dependency down",
+            "other callsite",
+            "log events suppressed by rate limit"
+        ]
+    );
+    let summary = &lines[4];
+    assert_eq!(summary["level"], "WARN");
+    assert_eq!(summary["target"], SUMMARY_TARGET);
+    assert_eq!(summary["fields"]["suppressed"], 7);
+    assert!(
+        summary["fields"]["suppressed_caller"]
+            .as_str()
+            .unwrap()
+            .contains("throttle.rs:")
+    );
+}
+
+#[test]
+fn test_rate_limit_window_resets() {
+    let layer = ThrottleLayer::new(ThrottlePolicy {
+        rate_limit: Some((1, Duration::from_millis(50))),
+        ..Default::default()
+    });
+
+    let lines = capture(layer, || {
+        for _ in 0..3 {
+            for _ in 0..5 {
+                tracing::warn!("flood");
+            }
+            std::thread::sleep(Duration::from_millis(60));
+        }
+    });
+
+    assert_eq!(lines.len(), 3);
+}
+
+#[test]
+fn test_sampling_skips_warn_and_error() {
+    let layer = ThrottleLayer::new(ThrottlePolicy {
+        sample_rates: [0.0, 0.0, 0.0],
+        ..Default::default()
+    });
+
+    let lines = capture(layer, || {
+        for _ in 0..20 {
+            tracing::info!("sampled out");
+            tracing::debug!("sampled out");
+        }
+        tracing::warn!("kept warn");
+        tracing::error!("kept error");
+    });
+
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["fields"]["message"], "kept warn");
+    assert_eq!(lines[1]["fields"]["message"], "kept error");
+}
+
+#[test]
+fn test_sampling_rate_is_approximate() {
+    let layer = ThrottleLayer::new(ThrottlePolicy {
+        sample_rates: [1.0, 1.0, 0.5],
+        ..Default::default()
+    });
+
+    let lines = capture(layer, || {
+        for _ in 0..2000 {
+            tracing::info!("half");
+        }
+    });
+
+    assert!((800..1200).contains(&lines.len()), "kept {}", lines.len());
+}
+
+#[test]
+#[serial]
+fn test_suppressed_events_exported_to_metric() {
+    x_one::xmetric::reset_metrics();
+    let layer = ThrottleLayer::new(rate_limit_policy(1));
+
+    capture(layer, || {
+        for _ in 0..4 {
+            tracing::error!("metric flood");
+        }
+    });
+
+    let mut output = String::new();
+    prometheus_client::encoding::text::encode(&mut output, &x_one::xmetric::registry().read())
+        .unwrap();
+    let line = output
+        .lines()
+        .find(|l| l.starts_with(SUPPRESSED_METRIC) && l.contains(r#"reason="rate_limit""#))
+        .unwrap_or_else(|| panic!("metric not found: {output}"));
+    assert!(line.contains(r#"level="ERROR""#), "{line}");
+    assert!(line.ends_with(" 3"), "{line}");
+    x_one::xmetric::reset_metrics();
+}
+
+struct SharedWriter(std::sync::Arc<parking_lot::Mutex<Vec<u8>>>);
+
+impl std::io::Write for SharedWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+use chrono::{TimeZone, Utc};
+use x_one::xlog::timezone::*;
+
+#[test]
+fn test_parse_utc_and_local() {
+    assert_eq!(LogTimezone::parse("").unwrap(), LogTimezone::Utc);
+    assert_eq!(LogTimezone::parse("UTC").unwrap(), LogTimezone::Utc);
+    assert_eq!(LogTimezone::parse("local").unwrap(), LogTimezone::Local);
+}
+
+#[test]
+fn test_parse_fixed_offsets() {
+    let at = Utc.with_ymd_and_hms(2026, 10, 17, 0, 0, 0).unwrap();
+    for (s, secs) in [
+        ("+08:00", 8 * 3600),
+        ("-0530", -(5 * 3600 + 30 * 60)),
+        ("UTC+8", 8 * 3600),
+        ("GMT-03:30", -(3 * 3600 + 30 * 60)),
+    ] {
+        let tz = LogTimezone::parse(s).unwrap();
+        assert!(matches!(tz, LogTimezone::Fixed(_)), "{s}");
+        assert_eq!(tz.offset_at(at).local_minus_utc(), secs, "{s}");
+    }
+    assert!(LogTimezone::parse("+25:00").is_err());
+}
+
+#[test]
+fn test_named_timezone_follows_dst() {
+    let tz = LogTimezone::parse("America/New_York").unwrap();
+    let summer = Utc.with_ymd_and_hms(2026, 7, 1, 12, 0, 0).unwrap();
+    let winter = Utc.with_ymd_and_hms(2026, 12, 1, 12, 0, 0).unwrap();
+
+    assert_eq!(tz.offset_at(summer).local_minus_utc(), -4 * 3600);
+    assert_eq!(tz.offset_at(winter).local_minus_utc(), -5 * 3600);
+}
+
+#[test]
+fn test_format_rfc3339_with_offset() {
+    let at = Utc.with_ymd_and_hms(2026, 10, 17, 20, 15, 30).unwrap();
+
+    assert_eq!(
+        LogTimezone::parse("Asia/Shanghai")
+            .unwrap()
+            .format_rfc3339(at),
+        "2026-10-18T04:15:30.000000+08:00"
+    );
+    assert_eq!(
+        LogTimezone::Utc.format_rfc3339(at),
+        "2026-10-17T20:15:30.000000Z"
+    );
+}
+
+#[test]
+fn test_parse_invalid_timezone() {
+    let err = LogTimezone::parse("Mars/Olympus").unwrap_err().to_string();
+    assert!(err.contains("Mars/Olympus"), "{err}");
+}
+
+#[test]
+fn test_otel_json_format_with_timezone_offset() {
+    use tracing_subscriber::prelude::*;
+    use x_one::xlog::otel_fmt::OtelJsonFormat;
+
+    let buf = std::sync::Arc::new(parking_lot::Mutex::new(Vec::new()));
+    let buf_clone = buf.clone();
+    let writer =
+        move || -> Box<dyn std::io::Write + Send> { Box::new(SharedWriter(buf_clone.clone())) };
+    let tz = LogTimezone::parse("Asia/Shanghai").unwrap();
+
+    let subscriber = tracing_subscriber::registry().with(
+        tracing_subscriber::fmt::layer()
+            .event_format(OtelJsonFormat.with_timezone(tz))
+            .with_writer(writer),
+    );
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!("with timezone");
+    });
+
+    let output = String::from_utf8(buf.lock().clone()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+    let timestamp = json["timestamp"].as_str().unwrap();
+    assert!(timestamp.ends_with("+08:00"), "{timestamp}");
+    assert!(chrono::DateTime::parse_from_rfc3339(timestamp).is_ok());
+}
+
+// ---- 辅助工具 ----
+
+/// 共享写入器，用于捕获 tracing 输出到内存
+struct SharedWriter(std::sync::Arc<parking_lot::Mutex<Vec<u8>>>);
+
+impl std::io::Write for SharedWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
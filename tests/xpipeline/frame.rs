@@ -15,7 +15,7 @@ fn test_end_frame_type() {
 #[test]
 fn test_error_frame_type() {
     let f = ErrorFrame {
-        err: Box::new(std::io::Error::new(std::io::ErrorKind::Other, "test")),
+        err: Box::new(std::io::Error::other("test")),
         message: "test error".to_string(),
     };
     assert_eq!(f.frame_type(), "error");
@@ -5,6 +5,7 @@ use x_one::XOneError;
 use x_one::xpipeline::{Frame, Pipeline, Processor};
 
 /// 测试用文本帧
+#[allow(dead_code)]
 struct TextFrame(String);
 impl Frame for TextFrame {
     fn frame_type(&self) -> &str {
@@ -37,6 +38,7 @@ impl Processor for PassthroughProcessor {
 }
 
 /// 添加前缀处理器
+#[allow(dead_code)]
 struct PrefixProcessor;
 
 impl Processor for PrefixProcessor {
@@ -1,3 +1,4 @@
+use std::time::Duration;
 use x_one::xredis::{XREDIS_CONFIG_KEY, XRedisConfig};
 
 #[test]
@@ -12,9 +13,9 @@ fn test_default_config() {
     assert_eq!(config.password, "");
     assert_eq!(config.db, 0);
     assert_eq!(config.username, "");
-    assert_eq!(config.dial_timeout, "500ms");
-    assert_eq!(config.read_timeout, "500ms");
-    assert_eq!(config.write_timeout, "500ms");
+    assert_eq!(config.dial_timeout, Duration::from_millis(500));
+    assert_eq!(config.read_timeout, Duration::from_millis(500));
+    assert_eq!(config.write_timeout, Duration::from_millis(500));
     assert_eq!(config.max_retries, 3);
     assert_eq!(config.name, "");
 }
@@ -52,9 +53,9 @@ Name: "session"
     assert_eq!(config.password, "pass123");
     assert_eq!(config.db, 5);
     assert_eq!(config.username, "admin");
-    assert_eq!(config.dial_timeout, "1s");
-    assert_eq!(config.read_timeout, "2s");
-    assert_eq!(config.write_timeout, "3s");
+    assert_eq!(config.dial_timeout, Duration::from_secs(1));
+    assert_eq!(config.read_timeout, Duration::from_secs(2));
+    assert_eq!(config.write_timeout, Duration::from_secs(3));
     assert_eq!(config.max_retries, 5);
     assert_eq!(config.name, "session");
 }
@@ -68,9 +69,9 @@ fn test_deserialize_config_empty_yaml_uses_defaults() {
     assert_eq!(config.password, "");
     assert_eq!(config.db, 0);
     assert_eq!(config.username, "");
-    assert_eq!(config.dial_timeout, "500ms");
-    assert_eq!(config.read_timeout, "500ms");
-    assert_eq!(config.write_timeout, "500ms");
+    assert_eq!(config.dial_timeout, Duration::from_millis(500));
+    assert_eq!(config.read_timeout, Duration::from_millis(500));
+    assert_eq!(config.write_timeout, Duration::from_millis(500));
     assert_eq!(config.max_retries, 3);
     assert_eq!(config.name, "");
 }
@@ -88,7 +89,7 @@ DB: 3
     // 其余应为默认值
     assert_eq!(config.password, "");
     assert_eq!(config.max_retries, 3);
-    assert_eq!(config.dial_timeout, "500ms");
+    assert_eq!(config.dial_timeout, Duration::from_millis(500));
 }
 
 #[test]
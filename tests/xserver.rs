@@ -3,3 +3,6 @@ mod main;
 
 #[path = "xserver/blocking.rs"]
 mod blocking;
+
+#[path = "xserver/cli.rs"]
+mod cli;
@@ -0,0 +1,117 @@
+use serial_test::serial;
+use x_one::xserver::cli::*;
+
+/// 写入配置文件并通过环境变量指定配置路径
+fn with_config_file(content: &str, f: impl FnOnce()) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("application.yml");
+    std::fs::write(&path, content).unwrap();
+    unsafe { std::env::set_var("SERVER_CONFIG_LOCATION", path.to_str().unwrap()) };
+    f();
+    unsafe { std::env::remove_var("SERVER_CONFIG_LOCATION") };
+    x_one::xconfig::reset_config();
+}
+
+#[test]
+fn test_cli_command_flag() {
+    assert_eq!(CliCommand::Version.flag(), "version");
+    assert_eq!(CliCommand::PrintConfig.flag(), "print-config");
+    assert_eq!(CliCommand::CheckConfig.flag(), "check-config");
+}
+
+#[test]
+#[serial]
+fn test_detect_cli_command_none_without_flags() {
+    assert_eq!(detect_cli_command(), None);
+}
+
+#[test]
+#[serial]
+fn test_run_cli_command_version() {
+    with_config_file("Server:\n  Name: app\n  Version: v1.2.3\n", || {
+        assert_eq!(run_cli_command(CliCommand::Version).unwrap(), "v1.2.3");
+    });
+}
+
+#[test]
+#[serial]
+fn test_run_cli_command_print_config_redacts_secrets() {
+    with_config_file(
+        "Server:\n  Name: app\nXOrm:\n  DSN: postgres://user:pass@db\n",
+        || {
+            let output = run_cli_command(CliCommand::PrintConfig).unwrap();
+            assert!(output.contains("Name: app"));
+            assert!(output.contains("DSN: '***'"));
+            assert!(!output.contains("pass@db"));
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_run_cli_command_print_config_redacts_secret_sources() {
+    let dir = tempfile::tempdir().unwrap();
+    let secrets = dir.path().join("secrets");
+    std::fs::create_dir(&secrets).unwrap();
+    std::fs::write(secrets.join("myapp.upstream"), "from-dir\n").unwrap();
+    let file = dir.path().join("conn");
+    std::fs::write(&file, "from-file\n").unwrap();
+
+    with_config_file(
+        &format!(
+            "Server:\n  Name: app\n  Sources:\n    - Type: dir\n      Path: \"{}\"\nMyApp:\n  Upstream: origin\n  Conn: \"${{file:{}}}\"\n",
+            secrets.display(),
+            file.display()
+        ),
+        || {
+            let output = run_cli_command(CliCommand::PrintConfig).unwrap();
+            assert!(output.contains("Upstream: '***'"), "{output}");
+            assert!(output.contains("Conn: '***'"), "{output}");
+            assert!(!output.contains("from-dir"), "{output}");
+            assert!(!output.contains("from-file"), "{output}");
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_run_cli_command_check_config() {
+    with_config_file("Server:\n  Name: app\n", || {
+        assert!(run_cli_command(CliCommand::CheckConfig).is_ok());
+    });
+    with_config_file("Server: [unclosed\n", || {
+        assert!(run_cli_command(CliCommand::CheckConfig).is_err());
+    });
+    // lenient 模式下启动不报错，但 --check-config 仍报告 schema 违规
+    with_config_file("Server:\n  Name: app\nXLog:\n  Levle: debug\n", || {
+        let err = run_cli_command(CliCommand::CheckConfig).unwrap_err();
+        assert!(err.to_string().contains("XLog.Levle: unknown key"));
+    });
+}
+
+#[test]
+#[serial]
+fn test_run_cli_command_does_not_start_watcher() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("application.yml");
+    std::fs::write(
+        &path,
+        "Server:\n  Name: app\n  Reload:\n    Enable: true\n    Interval: 20ms\n",
+    )
+    .unwrap();
+    unsafe { std::env::set_var("SERVER_CONFIG_LOCATION", path.to_str().unwrap()) };
+
+    run_cli_command(CliCommand::CheckConfig).unwrap();
+    std::fs::write(
+        &path,
+        "Server:\n  Name: changed\n  Reload:\n    Enable: true\n    Interval: 20ms\n",
+    )
+    .unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(200));
+    // 未启动监听，配置保持为首次加载的值
+    assert_eq!(x_one::xconfig::get_string("Server.Name"), "app");
+
+    unsafe { std::env::remove_var("SERVER_CONFIG_LOCATION") };
+    x_one::xconfig::stop_watch().unwrap();
+    x_one::xconfig::reset_config();
+}
@@ -6,3 +6,12 @@ mod init;
 
 #[path = "xtrace/mod.rs"]
 mod main;
+
+#[path = "xtrace/otlp.rs"]
+mod otlp;
+
+#[path = "xtrace/sampler.rs"]
+mod sampler;
+
+#[path = "xtrace/propagation.rs"]
+mod propagation;
@@ -12,6 +12,7 @@ fn test_is_enabled_true() {
     let config = XTraceConfig {
         enable: true,
         console: false,
+        ..Default::default()
     };
     assert!(config.is_enabled());
 }
@@ -21,6 +22,7 @@ fn test_is_enabled_false() {
     let config = XTraceConfig {
         enable: false,
         console: false,
+        ..Default::default()
     };
     assert!(!config.is_enabled());
 }
@@ -44,3 +46,118 @@ fn test_deserialize_minimal_yaml() {
     assert!(config.enable);
     assert!(!config.console);
 }
+
+#[test]
+fn test_sampler_default_config() {
+    let config = XTraceConfig::default();
+    assert_eq!(
+        config.sampler.sampler_type,
+        SamplerType::ParentBasedAlwaysOn
+    );
+    assert_eq!(config.sampler.ratio, 1.0);
+    assert!(config.sampler.rules.is_empty());
+    assert!(config.sampler.validate().is_ok());
+}
+
+#[test]
+fn test_sampler_deserialize_from_yaml() {
+    let yaml = r#"
+Sampler:
+  Type: "parentbased_traceidratio"
+  Ratio: 0.05
+  Rules:
+    - Path: "/healthz"
+      Ratio: 0
+    - Path: "/api/pay/*"
+      Method: "POST"
+      Ratio: 1
+"#;
+    let config: XTraceConfig = serde_yaml::from_str(yaml).unwrap();
+    let sampler = &config.sampler;
+    assert_eq!(sampler.sampler_type, SamplerType::ParentBasedTraceIdRatio);
+    assert_eq!(sampler.ratio, 0.05);
+    assert_eq!(sampler.rules.len(), 2);
+    assert_eq!(sampler.rules[0].path, "/healthz");
+    assert_eq!(sampler.rules[0].ratio, 0.0);
+    assert!(sampler.rules[0].method.is_empty());
+    assert_eq!(sampler.rules[1].method, "POST");
+    assert!(sampler.validate().is_ok());
+}
+
+#[test]
+fn test_sampler_type_names() {
+    for (name, expected) in [
+        ("always_on", SamplerType::AlwaysOn),
+        ("always_off", SamplerType::AlwaysOff),
+        ("traceidratio", SamplerType::TraceIdRatio),
+        ("parentbased_always_on", SamplerType::ParentBasedAlwaysOn),
+        ("parentbased_always_off", SamplerType::ParentBasedAlwaysOff),
+        (
+            "parentbased_traceidratio",
+            SamplerType::ParentBasedTraceIdRatio,
+        ),
+    ] {
+        let t: SamplerType = serde_yaml::from_str(name).unwrap();
+        assert_eq!(t, expected, "{name}");
+    }
+    assert!(serde_yaml::from_str::<SamplerType>("sometimes").is_err());
+}
+
+#[test]
+fn test_sampler_validate_ratio_out_of_range() {
+    let c = SamplerConfig {
+        ratio: 1.5,
+        ..Default::default()
+    };
+    let err = c.validate().unwrap_err().to_string();
+    assert!(err.contains("XTrace.Sampler.Ratio"), "{err}");
+
+    let c = SamplerConfig {
+        rules: vec![SamplingRuleConfig {
+            path: "/api/*".to_string(),
+            ratio: -0.1,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let err = c.validate().unwrap_err().to_string();
+    assert!(err.contains("XTrace.Sampler.Rules[0].Ratio"), "{err}");
+}
+
+#[test]
+fn test_sampler_validate_empty_rule_path() {
+    let c = SamplerConfig {
+        rules: vec![SamplingRuleConfig {
+            path: " ".to_string(),
+            ratio: 1.0,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let err = c.validate().unwrap_err().to_string();
+    assert!(err.contains("XTrace.Sampler.Rules[0].Path"), "{err}");
+}
+
+#[test]
+fn test_propagators_default_and_yaml() {
+    assert_eq!(
+        XTraceConfig::default().propagators,
+        vec![PropagatorType::TraceContext, PropagatorType::Baggage]
+    );
+    let config: XTraceConfig = serde_yaml::from_str("{}").unwrap();
+    assert_eq!(config.propagators, XTraceConfig::default().propagators);
+
+    let yaml = r#"Propagators: ["tracecontext", "baggage", "b3", "b3multi", "jaeger"]"#;
+    let config: XTraceConfig = serde_yaml::from_str(yaml).unwrap();
+    assert_eq!(
+        config.propagators,
+        vec![
+            PropagatorType::TraceContext,
+            PropagatorType::Baggage,
+            PropagatorType::B3,
+            PropagatorType::B3Multi,
+            PropagatorType::Jaeger,
+        ]
+    );
+    assert!(serde_yaml::from_str::<XTraceConfig>(r#"Propagators: ["xray"]"#).is_err());
+}
@@ -1,3 +1,4 @@
+use serial_test::serial;
 use x_one::xtrace::init::*;
 
 #[test]
@@ -7,8 +8,20 @@ fn test_is_trace_enabled_default_false() {
 }
 
 #[test]
+#[serial]
 fn test_shutdown_when_not_enabled() {
     // 未初始化时 shutdown 应直接返回 Ok
     let result = shutdown_xtrace();
     assert!(result.is_ok());
 }
+
+#[test]
+#[serial]
+fn test_init_invalid_sampler_ratio() {
+    x_one::xconfig::set_config(
+        serde_yaml::from_str("XTrace:\n  Enable: true\n  Sampler:\n    Ratio: 2").unwrap(),
+    );
+    let err = init_xtrace().unwrap_err().to_string();
+    assert!(err.contains("XTrace.Sampler.Ratio"), "{err}");
+    x_one::xconfig::reset_config();
+}
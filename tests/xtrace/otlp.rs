@@ -0,0 +1,458 @@
+use opentelemetry::KeyValue;
+use opentelemetry::trace::{Span, TraceContextExt, Tracer, TracerProvider};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use serial_test::serial;
+use std::io::Read;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use x_one::xtrace::config::{OtlpCompression, OtlpConfig, OtlpProtocol, XTraceConfig};
+use x_one::xtrace::otlp::build_processor;
+
+/// Collector 收到的请求
+#[derive(Debug, Clone)]
+struct Captured {
+    path: String,
+    content_type: String,
+    content_encoding: String,
+    grpc_encoding: String,
+    api_key: String,
+    body: Vec<u8>,
+}
+
+/// 本地 mock OTLP Collector（HTTP/1 与 h2c 自动识别）
+struct MockCollector {
+    addr: SocketAddr,
+    requests: Arc<parking_lot::Mutex<Vec<Captured>>>,
+    _runtime: tokio::runtime::Runtime,
+}
+
+impl MockCollector {
+    fn start() -> Self {
+        Self::start_with_grpc_status(0)
+    }
+
+    /// gRPC 请求以 `grpc_status` 作为 trailers 中的状态码响应
+    fn start_with_grpc_status(grpc_status: u32) -> Self {
+        use axum::extract::Request;
+        use http_body_util::BodyExt;
+        use hyper_util::rt::{TokioExecutor, TokioIo};
+        use hyper_util::server::conn::auto;
+        use tower::ServiceExt;
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .enable_all()
+            .build()
+            .unwrap();
+        let requests = Arc::new(parking_lot::Mutex::new(Vec::new()));
+
+        let captured = requests.clone();
+        let router = axum::Router::new().fallback(move |req: Request| {
+            let captured = captured.clone();
+            async move {
+                let headers = req.headers().clone();
+                let header = |name: &str| {
+                    headers
+                        .get(name)
+                        .and_then(|v| v.to_str().ok())
+                        .unwrap_or_default()
+                        .to_string()
+                };
+                let path = req.uri().path().to_string();
+                let content_type = header("content-type");
+                let body = axum::body::to_bytes(req.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                let grpc = content_type.starts_with("application/grpc");
+                captured.lock().push(Captured {
+                    path,
+                    content_type,
+                    content_encoding: header("content-encoding"),
+                    grpc_encoding: header("grpc-encoding"),
+                    api_key: header("x-api-key"),
+                    body: body.to_vec(),
+                });
+                if grpc {
+                    // 空的 ExportTraceServiceResponse 消息帧，grpc-status 放在 trailers
+                    let mut trailers = axum::http::HeaderMap::new();
+                    trailers.insert("grpc-status", grpc_status.into());
+                    let body = http_body_util::Full::new(axum::body::Bytes::from(vec![0u8; 5]))
+                        .with_trailers(async move { Some(Ok(trailers)) });
+                    axum::http::Response::builder()
+                        .header("content-type", "application/grpc")
+                        .body(axum::body::Body::new(body))
+                        .unwrap()
+                } else {
+                    axum::http::Response::new(axum::body::Body::empty())
+                }
+            }
+        });
+
+        let listener = runtime
+            .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+            .unwrap();
+        let addr = listener.local_addr().unwrap();
+        runtime.spawn(async move {
+            let builder = auto::Builder::new(TokioExecutor::new());
+            loop {
+                let (socket, _) = listener.accept().await.unwrap();
+                let router = router.clone();
+                let service =
+                    hyper::service::service_fn(move |req: Request<hyper::body::Incoming>| {
+                        router.clone().oneshot(req.map(axum::body::Body::new))
+                    });
+                let conn = builder
+                    .serve_connection(TokioIo::new(socket), service)
+                    .into_owned();
+                tokio::spawn(conn);
+            }
+        });
+
+        Self {
+            addr,
+            requests,
+            _runtime: runtime,
+        }
+    }
+
+    fn config(&self, protocol: OtlpProtocol) -> OtlpConfig {
+        OtlpConfig {
+            enable: true,
+            endpoint: format!("http://{}", self.addr),
+            protocol,
+            ..Default::default()
+        }
+    }
+
+    fn requests(&self) -> Vec<Captured> {
+        self.requests.lock().clone()
+    }
+
+    /// 等待收到至少 `n` 个请求
+    fn wait_for(&self, n: usize, timeout: Duration) -> Vec<Captured> {
+        let deadline = Instant::now() + timeout;
+        while self.requests.lock().len() < n && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        self.requests()
+    }
+}
+
+fn build_provider(c: &OtlpConfig) -> SdkTracerProvider {
+    SdkTracerProvider::builder()
+        .with_span_processor(build_processor(c).unwrap())
+        .with_resource(
+            Resource::builder_empty()
+                .with_service_name("otlp-trace-test")
+                .build(),
+        )
+        .build()
+}
+
+/// 创建一个父 span 与一个带属性、事件的子 span
+fn record_order_spans(provider: &SdkTracerProvider) {
+    let tracer = provider.tracer("order-service");
+    tracer.in_span("checkout", |cx| {
+        let mut child = tracer.start_with_context("charge card", &cx);
+        child.set_attribute(KeyValue::new("order.id", 42));
+        child.add_event("card declined", vec![KeyValue::new("retry", true)]);
+        child.end();
+    });
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+fn gunzip(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    flate2::read::GzDecoder::new(data)
+        .read_to_end(&mut out)
+        .unwrap();
+    out
+}
+
+#[test]
+fn test_otlp_config_defaults_and_parse() {
+    let c = OtlpConfig::default();
+    assert!(!c.enable);
+    assert_eq!(c.protocol, OtlpProtocol::HttpProtobuf);
+    assert_eq!(c.compression, OtlpCompression::None);
+    assert_eq!(c.timeout, Duration::from_secs(10));
+    assert_eq!(c.max_queue_size, 2048);
+    assert_eq!(c.max_batch_size, 512);
+    assert_eq!(c.export_interval, Duration::from_secs(5));
+
+    let c: XTraceConfig = serde_yaml::from_str(
+        "Otlp:\n  Enable: true\n  Endpoint: \"https://collector:4318/v1/traces\"\n  Protocol: grpc\n  Compression: gzip\n  Headers:\n    x-api-key: abc\n  MaxQueueSize: 100\n  ExportInterval: 2s\n",
+    )
+    .unwrap();
+    assert!(c.enable);
+    assert!(c.otlp.enable);
+    assert_eq!(c.otlp.protocol, OtlpProtocol::Grpc);
+    assert_eq!(c.otlp.compression, OtlpCompression::Gzip);
+    assert_eq!(c.otlp.headers["x-api-key"], "abc");
+    assert_eq!(c.otlp.max_queue_size, 100);
+    assert_eq!(c.otlp.export_interval, Duration::from_secs(2));
+}
+
+#[test]
+fn test_otlp_export_url() {
+    let mut c = OtlpConfig {
+        endpoint: "http://collector:4318/v1/traces".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(c.export_url(), "http://collector:4318/v1/traces");
+
+    c.endpoint = "http://collector:4318/".to_string();
+    c.protocol = OtlpProtocol::HttpJson;
+    assert_eq!(c.export_url(), "http://collector:4318/v1/traces");
+
+    c.endpoint = String::new();
+    c.protocol = OtlpProtocol::Grpc;
+    assert_eq!(
+        c.export_url(),
+        "http://localhost:4317/opentelemetry.proto.collector.trace.v1.TraceService/Export"
+    );
+}
+
+#[test]
+fn test_otlp_invalid_config_returns_error() {
+    for c in [
+        OtlpConfig {
+            max_queue_size: 0,
+            ..Default::default()
+        },
+        OtlpConfig {
+            export_interval: Duration::ZERO,
+            ..Default::default()
+        },
+        OtlpConfig {
+            endpoint: "collector:4318".to_string(),
+            ..Default::default()
+        },
+    ] {
+        let err = c.validate().unwrap_err();
+        assert!(err.to_string().contains("XTrace.Otlp"), "{err}");
+    }
+
+    let mut c = OtlpConfig::default();
+    c.headers.insert("bad header".to_string(), "v".to_string());
+    let err = build_processor(&c).unwrap_err();
+    assert!(err.to_string().contains("XTrace.Otlp.Headers"), "{err}");
+}
+
+#[test]
+fn test_otlp_http_protobuf_with_headers() {
+    let collector = MockCollector::start();
+    let mut c = collector.config(OtlpProtocol::HttpProtobuf);
+    c.headers.insert("x-api-key".to_string(), "k1".to_string());
+    let provider = build_provider(&c);
+
+    record_order_spans(&provider);
+    provider.force_flush().unwrap();
+
+    let requests = collector.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/v1/traces");
+    assert_eq!(requests[0].content_type, "application/x-protobuf");
+    assert_eq!(requests[0].api_key, "k1");
+    let body = &requests[0].body;
+    for needle in [
+        &b"checkout"[..],
+        b"charge card",
+        b"order.id",
+        b"card declined",
+        b"order-service",
+        b"otlp-trace-test",
+    ] {
+        assert!(
+            contains(body, needle),
+            "{}",
+            String::from_utf8_lossy(needle)
+        );
+    }
+    provider.shutdown().unwrap();
+}
+
+#[test]
+fn test_otlp_http_json_encodes_span_tree() {
+    let collector = MockCollector::start();
+    let provider = build_provider(&collector.config(OtlpProtocol::HttpJson));
+
+    record_order_spans(&provider);
+    provider.force_flush().unwrap();
+
+    let requests = collector.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].content_type, "application/json");
+    let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+    let resource_spans = &body["resourceSpans"][0];
+    assert!(
+        resource_spans["resource"]["attributes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|kv| kv["value"]["stringValue"] == "otlp-trace-test")
+    );
+    let scope_spans = &resource_spans["scopeSpans"][0];
+    assert_eq!(scope_spans["scope"]["name"], "order-service");
+    let spans = scope_spans["spans"].as_array().unwrap();
+    assert_eq!(spans.len(), 2);
+
+    let child = spans.iter().find(|s| s["name"] == "charge card").unwrap();
+    let parent = spans.iter().find(|s| s["name"] == "checkout").unwrap();
+    assert_eq!(child["traceId"], parent["traceId"]);
+    assert_eq!(child["parentSpanId"], parent["spanId"]);
+    assert_eq!(parent["parentSpanId"].as_str().unwrap_or_default(), "");
+    assert_eq!(child["kind"], 1);
+    assert_eq!(child["attributes"][0]["key"], "order.id");
+    assert_eq!(child["attributes"][0]["value"]["intValue"], "42");
+    assert_eq!(child["events"][0]["name"], "card declined");
+    assert_eq!(
+        child["events"][0]["attributes"][0]["value"]["boolValue"],
+        true
+    );
+    assert_eq!(child["status"]["code"], 0);
+    provider.shutdown().unwrap();
+}
+
+#[test]
+fn test_otlp_http_gzip_compression() {
+    let collector = MockCollector::start();
+    let mut c = collector.config(OtlpProtocol::HttpProtobuf);
+    c.compression = OtlpCompression::Gzip;
+    let provider = build_provider(&c);
+
+    record_order_spans(&provider);
+    provider.force_flush().unwrap();
+
+    let requests = collector.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].content_encoding, "gzip");
+    assert!(!contains(&requests[0].body, b"charge card"));
+    assert!(contains(&gunzip(&requests[0].body), b"charge card"));
+    provider.shutdown().unwrap();
+}
+
+#[test]
+fn test_otlp_grpc_sends_framed_protobuf() {
+    let collector = MockCollector::start();
+    let mut c = collector.config(OtlpProtocol::Grpc);
+    c.compression = OtlpCompression::Gzip;
+    let provider = build_provider(&c);
+
+    record_order_spans(&provider);
+    provider.force_flush().unwrap();
+
+    let requests = collector.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(
+        requests[0].path,
+        "/opentelemetry.proto.collector.trace.v1.TraceService/Export"
+    );
+    assert_eq!(requests[0].content_type, "application/grpc");
+    assert_eq!(requests[0].grpc_encoding, "gzip");
+    let body = &requests[0].body;
+    assert_eq!(body[0], 1, "压缩标志");
+    let len = u32::from_be_bytes(body[1..5].try_into().unwrap()) as usize;
+    assert_eq!(len, body.len() - 5);
+    assert!(contains(&gunzip(&body[5..]), b"charge card"));
+    provider.shutdown().unwrap();
+}
+
+#[test]
+fn test_otlp_grpc_error_status_in_trailers_fails_export() {
+    // 14 = UNAVAILABLE
+    let collector = MockCollector::start_with_grpc_status(14);
+    let provider = build_provider(&collector.config(OtlpProtocol::Grpc));
+
+    record_order_spans(&provider);
+    assert!(provider.force_flush().is_err());
+    assert_eq!(collector.requests().len(), 1);
+    let _ = provider.shutdown();
+}
+
+#[test]
+fn test_otlp_batches_by_max_batch_size() {
+    let collector = MockCollector::start();
+    let mut c = collector.config(OtlpProtocol::HttpJson);
+    c.max_batch_size = 2;
+    c.export_interval = Duration::from_secs(3600);
+    let provider = build_provider(&c);
+
+    let tracer = provider.tracer("batch");
+    // 满一批即发送，不等待 ExportInterval
+    for round in 1..=2 {
+        for i in 0..2 {
+            tracer.in_span(format!("span-{round}-{i}"), |cx| {
+                assert!(cx.span().span_context().is_valid());
+            });
+        }
+        assert_eq!(
+            collector.wait_for(round, Duration::from_secs(5)).len(),
+            round
+        );
+    }
+
+    let requests = collector.requests();
+    for request in &requests {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        let spans = &body["resourceSpans"][0]["scopeSpans"][0]["spans"];
+        assert_eq!(spans.as_array().unwrap().len(), 2);
+    }
+    provider.shutdown().unwrap();
+}
+
+#[test]
+fn test_otlp_exports_by_interval() {
+    let collector = MockCollector::start();
+    let mut c = collector.config(OtlpProtocol::HttpProtobuf);
+    c.export_interval = Duration::from_millis(100);
+    let provider = build_provider(&c);
+
+    provider.tracer("interval").in_span("tick", |_| {});
+
+    let requests = collector.wait_for(1, Duration::from_secs(5));
+    assert_eq!(requests.len(), 1);
+    assert!(contains(&requests[0].body, b"tick"));
+    provider.shutdown().unwrap();
+}
+
+#[test]
+#[serial]
+fn test_init_xtrace_exports_and_flushes_on_shutdown() {
+    let collector = MockCollector::start();
+    let yaml = format!(
+        "Server:\n  Name: trace-e2e\nXTrace:\n  Enable: true\n  Otlp:\n    Enable: true\n    Endpoint: \"http://{}\"\n    ExportInterval: 1h\n",
+        collector.addr
+    );
+    x_one::xconfig::set_config(serde_yaml::from_str(&yaml).unwrap());
+
+    x_one::xtrace::init::init_xtrace().unwrap();
+    assert!(x_one::xtrace::is_trace_enabled());
+    opentelemetry::global::tracer("e2e").in_span("pending span", |_| {});
+    assert!(collector.requests().is_empty(), "ExportInterval 内不发送");
+
+    x_one::xtrace::init::shutdown_xtrace().unwrap();
+    x_one::xconfig::reset_config();
+
+    let requests = collector.requests();
+    assert_eq!(requests.len(), 1);
+    assert!(contains(&requests[0].body, b"pending span"));
+    assert!(contains(&requests[0].body, b"trace-e2e"));
+}
+
+#[test]
+#[serial]
+fn test_init_xtrace_invalid_otlp_config_returns_error() {
+    x_one::xconfig::set_config(
+        serde_yaml::from_str("XTrace:\n  Otlp:\n    Enable: true\n    MaxBatchSize: 0\n").unwrap(),
+    );
+    let err = x_one::xtrace::init::init_xtrace().unwrap_err();
+    x_one::xconfig::reset_config();
+    assert!(err.to_string().contains("XTrace.Otlp"), "{err}");
+    assert!(!x_one::xtrace::is_trace_enabled());
+}
@@ -0,0 +1,301 @@
+use opentelemetry::baggage::BaggageExt;
+use opentelemetry::propagation::TextMapPropagator;
+use opentelemetry::trace::{
+    SamplingDecision, SpanContext, SpanId, SpanKind, TraceContextExt, TraceFlags, TraceId,
+    TraceState,
+};
+use opentelemetry::{Context, KeyValue};
+use opentelemetry_sdk::trace::{Sampler, ShouldSample};
+use std::collections::HashMap;
+use x_one::xtrace::config::PropagatorType;
+use x_one::xtrace::propagation::*;
+
+const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+const SPAN_ID: &str = "00f067aa0ba902b7";
+
+fn headers(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+fn span_context_of(cx: &Context) -> SpanContext {
+    cx.span().span_context().clone()
+}
+
+fn sampled_cx() -> Context {
+    let sc = SpanContext::new(
+        TraceId::from_hex(TRACE_ID).unwrap(),
+        SpanId::from_hex(SPAN_ID).unwrap(),
+        TraceFlags::SAMPLED,
+        false,
+        TraceState::default(),
+    );
+    Context::new().with_remote_span_context(sc)
+}
+
+fn inject(propagator: &dyn TextMapPropagator, cx: &Context) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    propagator.inject_context(cx, &mut out);
+    out
+}
+
+#[test]
+fn test_b3_single_extract() {
+    let p = B3Propagator::single_header();
+    let cx = p.extract(&headers(&[("b3", &format!("{TRACE_ID}-{SPAN_ID}-1"))]));
+    let sc = span_context_of(&cx);
+    assert_eq!(sc.trace_id().to_string(), TRACE_ID);
+    assert_eq!(sc.span_id().to_string(), SPAN_ID);
+    assert!(sc.is_sampled());
+    assert!(sc.is_remote());
+
+    // debug 隐含采样，第四段父 span id 忽略
+    let cx = p.extract(&headers(&[(
+        "b3",
+        &format!("{TRACE_ID}-{SPAN_ID}-d-0000000000000001"),
+    )]));
+    assert!(span_context_of(&cx).is_sampled());
+
+    let cx = p.extract(&headers(&[("b3", &format!("{TRACE_ID}-{SPAN_ID}-0"))]));
+    let sc = span_context_of(&cx);
+    assert!(sc.is_valid());
+    assert!(!sc.is_sampled());
+}
+
+#[test]
+fn test_b3_extract_64bit_trace_id() {
+    let p = B3Propagator::single_header();
+    let cx = p.extract(&headers(&[(
+        "b3",
+        &format!("a3ce929d0e0e4736-{SPAN_ID}-1"),
+    )]));
+    assert_eq!(
+        span_context_of(&cx).trace_id().to_string(),
+        "0000000000000000a3ce929d0e0e4736"
+    );
+}
+
+#[test]
+fn test_b3_multi_extract() {
+    let p = B3Propagator::multiple_headers();
+    let cx = p.extract(&headers(&[
+        ("x-b3-traceid", TRACE_ID),
+        ("x-b3-spanid", SPAN_ID),
+        ("x-b3-sampled", "1"),
+    ]));
+    let sc = span_context_of(&cx);
+    assert_eq!(sc.trace_id().to_string(), TRACE_ID);
+    assert!(sc.is_sampled());
+
+    let cx = p.extract(&headers(&[
+        ("x-b3-traceid", TRACE_ID),
+        ("x-b3-spanid", SPAN_ID),
+        ("x-b3-flags", "1"),
+    ]));
+    assert!(span_context_of(&cx).is_sampled());
+
+    // 单头格式的 propagator 同样接受多头
+    let cx = B3Propagator::single_header().extract(&headers(&[
+        ("x-b3-traceid", TRACE_ID),
+        ("x-b3-spanid", SPAN_ID),
+    ]));
+    let sc = span_context_of(&cx);
+    assert!(sc.is_valid());
+    assert!(!sc.is_sampled());
+
+    // 兼容旧版 true / false 写法
+    let cx = p.extract(&headers(&[
+        ("x-b3-traceid", TRACE_ID),
+        ("x-b3-spanid", SPAN_ID),
+        ("x-b3-sampled", "true"),
+    ]));
+    assert!(span_context_of(&cx).is_sampled());
+}
+
+#[test]
+fn test_b3_single_accepts_true_false() {
+    let p = B3Propagator::single_header();
+    let cx = p.extract(&headers(&[("b3", &format!("{TRACE_ID}-{SPAN_ID}-true"))]));
+    assert!(span_context_of(&cx).is_sampled());
+    assert!(!is_sampling_deferred(&cx));
+
+    let cx = p.extract(&headers(&[("b3", &format!("{TRACE_ID}-{SPAN_ID}-false"))]));
+    let sc = span_context_of(&cx);
+    assert!(sc.is_valid());
+    assert!(!sc.is_sampled());
+    assert!(!is_sampling_deferred(&cx));
+}
+
+#[test]
+fn test_b3_without_sampling_state_is_deferred() {
+    let single = B3Propagator::single_header();
+    let cx = single.extract(&headers(&[("b3", &format!("{TRACE_ID}-{SPAN_ID}"))]));
+    let sc = span_context_of(&cx);
+    assert!(sc.is_valid());
+    assert!(!sc.is_sampled());
+    assert!(is_sampling_deferred(&cx));
+
+    let multi = B3Propagator::multiple_headers();
+    let cx = multi.extract(&headers(&[
+        ("x-b3-traceid", TRACE_ID),
+        ("x-b3-spanid", SPAN_ID),
+    ]));
+    assert!(span_context_of(&cx).is_valid());
+    assert!(is_sampling_deferred(&cx));
+
+    // 显式不采样不是推迟
+    let cx = single.extract(&headers(&[("b3", &format!("{TRACE_ID}-{SPAN_ID}-0"))]));
+    assert!(!is_sampling_deferred(&cx));
+    let cx = multi.extract(&headers(&[
+        ("x-b3-traceid", TRACE_ID),
+        ("x-b3-spanid", SPAN_ID),
+        ("x-b3-sampled", "0"),
+    ]));
+    assert!(!is_sampling_deferred(&cx));
+}
+
+#[test]
+fn test_deferred_marker_ignored_after_other_format_overrides() {
+    // b3 推迟，随后 W3C traceparent 覆盖 span 上下文并明确不采样
+    let propagator = build_propagator(&[PropagatorType::B3, PropagatorType::TraceContext]);
+    let cx = propagator.extract(&headers(&[
+        ("b3", &format!("{TRACE_ID}-{SPAN_ID}")),
+        (
+            "traceparent",
+            "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-00",
+        ),
+    ]));
+    assert_eq!(
+        span_context_of(&cx).trace_id().to_string(),
+        "0af7651916cd43dd8448eb211c80319c"
+    );
+    assert!(!is_sampling_deferred(&cx));
+}
+
+#[test]
+fn test_deferred_aware_sampler_lets_root_sampler_decide() {
+    let sampler = DeferredAwareSampler::new(Sampler::ParentBased(Box::new(Sampler::AlwaysOn)));
+    let decide = |cx: &Context| {
+        sampler
+            .should_sample(
+                Some(cx),
+                span_context_of(cx).trace_id(),
+                "test",
+                &SpanKind::Server,
+                &[],
+                &[],
+            )
+            .decision
+    };
+
+    let p = B3Propagator::single_header();
+    let deferred = p.extract(&headers(&[("b3", &format!("{TRACE_ID}-{SPAN_ID}"))]));
+    assert_eq!(decide(&deferred), SamplingDecision::RecordAndSample);
+
+    let not_sampled = p.extract(&headers(&[("b3", &format!("{TRACE_ID}-{SPAN_ID}-0"))]));
+    assert_eq!(decide(&not_sampled), SamplingDecision::Drop);
+}
+
+#[test]
+fn test_b3_extract_invalid() {
+    let p = B3Propagator::single_header();
+    for value in [
+        "",
+        "xyz-00f067aa0ba902b7-1",
+        "00000000000000000000000000000000-00f067aa0ba902b7-1",
+        "4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-x",
+        "4bf92f3577b34da6a3ce929d0e0e4736",
+    ] {
+        let cx = p.extract(&headers(&[("b3", value)]));
+        assert!(!span_context_of(&cx).is_valid(), "{value}");
+    }
+}
+
+#[test]
+fn test_b3_inject() {
+    let out = inject(&B3Propagator::single_header(), &sampled_cx());
+    assert_eq!(out.get("b3").unwrap(), &format!("{TRACE_ID}-{SPAN_ID}-1"));
+
+    let out = inject(&B3Propagator::multiple_headers(), &sampled_cx());
+    assert_eq!(out.get("x-b3-traceid").unwrap(), TRACE_ID);
+    assert_eq!(out.get("x-b3-spanid").unwrap(), SPAN_ID);
+    assert_eq!(out.get("x-b3-sampled").unwrap(), "1");
+
+    // 无有效 span 时不注入
+    assert!(inject(&B3Propagator::single_header(), &Context::new()).is_empty());
+}
+
+#[test]
+fn test_jaeger_extract_and_inject() {
+    let p = JaegerPropagator::new();
+    let cx = p.extract(&headers(&[(
+        "uber-trace-id",
+        &format!("{TRACE_ID}:{SPAN_ID}:0:1"),
+    )]));
+    let sc = span_context_of(&cx);
+    assert_eq!(sc.trace_id().to_string(), TRACE_ID);
+    assert_eq!(sc.span_id().to_string(), SPAN_ID);
+    assert!(sc.is_sampled());
+
+    // URL 编码的冒号与短 id
+    let cx = p.extract(&headers(&[(
+        "uber-trace-id",
+        "a3ce929d0e0e4736%3Aabc%3A0%3A0",
+    )]));
+    let sc = span_context_of(&cx);
+    assert_eq!(
+        sc.trace_id().to_string(),
+        "0000000000000000a3ce929d0e0e4736"
+    );
+    assert_eq!(sc.span_id().to_string(), "0000000000000abc");
+    assert!(!sc.is_sampled());
+
+    let cx = p.extract(&headers(&[("uber-trace-id", "bad")]));
+    assert!(!span_context_of(&cx).is_valid());
+
+    let out = inject(&p, &sampled_cx());
+    assert_eq!(
+        out.get("uber-trace-id").unwrap(),
+        &format!("{TRACE_ID}:{SPAN_ID}:0:1")
+    );
+}
+
+#[test]
+fn test_build_propagator_composite() {
+    let p = build_propagator(&[
+        PropagatorType::TraceContext,
+        PropagatorType::Baggage,
+        PropagatorType::B3,
+        PropagatorType::B3Multi,
+        PropagatorType::Jaeger,
+    ]);
+    let cx = sampled_cx().with_baggage(vec![KeyValue::new("tenant", "acme")]);
+    let out = inject(&p, &cx);
+    assert_eq!(
+        out.get("traceparent").unwrap(),
+        &format!("00-{TRACE_ID}-{SPAN_ID}-01")
+    );
+    assert_eq!(out.get("baggage").unwrap(), "tenant=acme");
+    assert!(out.contains_key("b3"));
+    assert!(out.contains_key("x-b3-traceid"));
+    assert!(out.contains_key("uber-trace-id"));
+
+    // 仅携带 B3 头的请求同样能提取
+    let cx = p.extract(&headers(&[
+        ("b3", &format!("{TRACE_ID}-{SPAN_ID}-1")),
+        ("baggage", "tenant=acme"),
+    ]));
+    assert_eq!(span_context_of(&cx).trace_id().to_string(), TRACE_ID);
+    assert_eq!(cx.baggage().get("tenant").unwrap().to_string(), "acme");
+}
+
+#[test]
+fn test_build_propagator_dedup_and_fields() {
+    let p = build_propagator(&[PropagatorType::Jaeger, PropagatorType::Jaeger]);
+    assert_eq!(p.fields().collect::<Vec<_>>(), vec!["uber-trace-id"]);
+
+    let p = build_propagator(&[]);
+    assert!(inject(&p, &sampled_cx()).is_empty());
+}
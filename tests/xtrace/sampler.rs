@@ -0,0 +1,134 @@
+use opentelemetry::Context;
+use opentelemetry::trace::{
+    SamplingDecision, SpanContext, SpanId, SpanKind, TraceContextExt, TraceFlags, TraceId,
+    TraceState,
+};
+use x_one::xtrace::config::*;
+use x_one::xtrace::sampler::*;
+
+fn rule(path: &str, method: &str, ratio: f64) -> SamplingRuleConfig {
+    SamplingRuleConfig {
+        path: path.to_string(),
+        method: method.to_string(),
+        ratio,
+    }
+}
+
+fn policy_of(sampler_type: SamplerType, rules: Vec<SamplingRuleConfig>) -> SamplingPolicy {
+    let c = SamplerConfig {
+        sampler_type,
+        rules,
+        ..Default::default()
+    };
+    SamplingPolicy::from_config(&c).unwrap().unwrap()
+}
+
+fn remote_parent(sampled: bool) -> Context {
+    let flags = if sampled {
+        TraceFlags::SAMPLED
+    } else {
+        TraceFlags::default()
+    };
+    let sc = SpanContext::new(
+        TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736").unwrap(),
+        SpanId::from_hex("00f067aa0ba902b7").unwrap(),
+        flags,
+        true,
+        TraceState::default(),
+    );
+    Context::new().with_remote_span_context(sc)
+}
+
+#[test]
+fn test_policy_none_without_rules() {
+    assert!(
+        SamplingPolicy::from_config(&SamplerConfig::default())
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_policy_invalid_config() {
+    let c = SamplerConfig {
+        rules: vec![rule("/api/*", "", 2.0)],
+        ..Default::default()
+    };
+    assert!(SamplingPolicy::from_config(&c).is_err());
+}
+
+#[test]
+fn test_match_rule_first_match_wins() {
+    let policy = policy_of(
+        SamplerType::ParentBasedTraceIdRatio,
+        vec![
+            rule("/healthz", "", 0.0),
+            rule("/api/pay/*", "", 1.0),
+            rule("/api/*", "", 0.5),
+        ],
+    );
+    assert!(policy.match_rule("GET", "/healthz").unwrap().is_drop());
+    assert_eq!(
+        policy.match_rule("POST", "/api/pay/order").unwrap().ratio(),
+        1.0
+    );
+    assert_eq!(policy.match_rule("GET", "/api/user").unwrap().ratio(), 0.5);
+    assert!(policy.match_rule("GET", "/static/app.js").is_none());
+}
+
+#[test]
+fn test_match_rule_method() {
+    let policy = policy_of(SamplerType::AlwaysOn, vec![rule("/api/*", "post", 1.0)]);
+    assert!(policy.match_rule("POST", "/api/order").is_some());
+    assert!(policy.match_rule("GET", "/api/order").is_none());
+}
+
+#[test]
+fn test_rule_ratio_sampling() {
+    let policy = policy_of(
+        SamplerType::TraceIdRatio,
+        vec![rule("/all", "", 1.0), rule("/half", "", 0.5)],
+    );
+    let cx = Context::new();
+    let all = policy.match_rule("GET", "/all").unwrap();
+    let half = policy.match_rule("GET", "/half").unwrap();
+
+    // 低 64 位右移 1 位后小于 2^62 时采样
+    let low = TraceId::from_hex("00000000000000000000000000000001").unwrap();
+    let high = TraceId::from_hex("0000000000000000ffffffffffffffff").unwrap();
+    for trace_id in [low, high] {
+        let result = all.should_sample(&cx, trace_id, "GET /all", &SpanKind::Server);
+        assert_eq!(result.decision, SamplingDecision::RecordAndSample);
+    }
+    let result = half.should_sample(&cx, low, "GET /half", &SpanKind::Server);
+    assert_eq!(result.decision, SamplingDecision::RecordAndSample);
+    let result = half.should_sample(&cx, high, "GET /half", &SpanKind::Server);
+    assert_eq!(result.decision, SamplingDecision::Drop);
+}
+
+#[test]
+fn test_rule_parent_based_follows_parent() {
+    let policy = policy_of(
+        SamplerType::ParentBasedAlwaysOn,
+        vec![rule("/api/*", "", 1.0)],
+    );
+    let r = policy.match_rule("GET", "/api/user").unwrap();
+    let parent = remote_parent(false);
+    let trace_id = parent.span().span_context().trace_id();
+    let result = r.should_sample(&parent, trace_id, "GET /api/user", &SpanKind::Server);
+    assert_eq!(result.decision, SamplingDecision::Drop);
+
+    let parent = remote_parent(true);
+    let result = r.should_sample(&parent, trace_id, "GET /api/user", &SpanKind::Server);
+    assert_eq!(result.decision, SamplingDecision::RecordAndSample);
+}
+
+#[test]
+fn test_rule_not_parent_based_ignores_parent() {
+    let policy = policy_of(SamplerType::AlwaysOn, vec![rule("/api/*", "", 1.0)]);
+    let r = policy.match_rule("GET", "/api/user").unwrap();
+    let parent = remote_parent(false);
+    let trace_id = parent.span().span_context().trace_id();
+    let result = r.should_sample(&parent, trace_id, "GET /api/user", &SpanKind::Server);
+    assert_eq!(result.decision, SamplingDecision::RecordAndSample);
+}
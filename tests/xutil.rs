@@ -33,3 +33,6 @@ mod future;
 
 #[path = "xutil/pool.rs"]
 mod pool;
+
+#[path = "xutil/pattern.rs"]
+mod pattern;
@@ -58,3 +58,23 @@ fn test_to_duration_invalid_returns_none() {
 fn test_to_duration_7d() {
     assert_eq!(to_duration("7d"), Some(Duration::from_secs(7 * 86400)));
 }
+
+#[test]
+fn test_to_byte_size_units() {
+    assert_eq!(to_byte_size("512"), Some(512));
+    assert_eq!(to_byte_size("512B"), Some(512));
+    assert_eq!(to_byte_size("10KB"), Some(10_000));
+    assert_eq!(to_byte_size("10k"), Some(10_000));
+    assert_eq!(to_byte_size("64MiB"), Some(64 * 1024 * 1024));
+    assert_eq!(to_byte_size("1 GiB"), Some(1 << 30));
+    assert_eq!(to_byte_size("1.5gb"), Some(1_500_000_000));
+}
+
+#[test]
+fn test_to_byte_size_invalid_returns_none() {
+    assert_eq!(to_byte_size(""), None);
+    assert_eq!(to_byte_size("MiB"), None);
+    assert_eq!(to_byte_size("10XB"), None);
+    assert_eq!(to_byte_size("-1KB"), None);
+    assert_eq!(to_byte_size("99999999999TiB"), None);
+}
@@ -111,11 +111,13 @@ fn test_take_or_default_u64_non_zero() {
 }
 
 #[test]
+#[allow(clippy::approx_constant)]
 fn test_default_if_empty_f64_zero() {
     assert_eq!(default_if_empty(&0.0_f64, &3.14), &3.14);
 }
 
 #[test]
+#[allow(clippy::approx_constant)]
 fn test_default_if_empty_f64_non_zero() {
     assert_eq!(default_if_empty(&2.7_f64, &3.14), &2.7);
 }
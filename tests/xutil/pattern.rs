@@ -0,0 +1,30 @@
+use x_one::xutil::pattern::*;
+
+#[test]
+fn test_glob_match_exact() {
+    assert!(glob_match("/healthz", "/healthz"));
+    assert!(!glob_match("/healthz", "/healthz/live"));
+    assert!(!glob_match("/healthz", "/health"));
+}
+
+#[test]
+fn test_glob_match_prefix_and_suffix() {
+    assert!(glob_match("/api/pay/*", "/api/pay/order"));
+    assert!(glob_match("/api/pay/*", "/api/pay/"));
+    assert!(!glob_match("/api/pay/*", "/api/payment"));
+    assert!(glob_match("*.json", "config.json"));
+    assert!(!glob_match("*.json", "config.yaml"));
+}
+
+#[test]
+fn test_glob_match_middle() {
+    assert!(glob_match("/api/*/detail", "/api/user/detail"));
+    assert!(glob_match("*token*", "access_token_v2"));
+    assert!(!glob_match("a*b*c", "acb"));
+}
+
+#[test]
+fn test_glob_match_star_only() {
+    assert!(glob_match("*", ""));
+    assert!(glob_match("*", "/any/path"));
+}
@@ -88,7 +88,7 @@ async fn test_submit_returns_false_when_channel_full() {
     // worker_count=1 -> channel 容量 = 1*16 = 16
     // 用一个阻塞任务占住 worker，然后填满 channel
     let pool = Pool::new(1);
-    let channel_capacity = 1 * 16;
+    let channel_capacity = 16;
 
     // 提交一个长时间运行的任务，阻塞 worker 不消费后续任务
     let (notify_tx, notify_rx) = tokio::sync::oneshot::channel::<()>();